@@ -0,0 +1,164 @@
+use crate::{
+    BPS_DENOMINATOR, CapPolicy, Concentration, DelegationError, POWER_CAP, PowerCap,
+    PowerCapConfig, checkpoints, events, power, registry, source,
+};
+use soroban_sdk::Env;
+
+/// Get the cap on received delegated power and what happens to delegations exceeding it
+///
+/// # Arguments
+/// * `env` - The contract environment
+///
+/// # Returns
+/// * `PowerCapConfig` - The configuration (defaults to no cap)
+pub fn get_config(env: &Env) -> PowerCapConfig {
+    env.storage()
+        .persistent()
+        .get(&POWER_CAP)
+        .unwrap_or(PowerCapConfig {
+            cap: PowerCap::None,
+            policy: CapPolicy::Reject,
+        })
+}
+
+/// Set the cap on received delegated power and what happens to delegations exceeding it
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `config` - The new configuration
+///
+/// # Returns
+/// * `Result<(), DelegationError>` - Success, or `InvalidPowerCap` for a non-positive amount or
+///   a percentage above 100%
+pub fn set_config(env: &Env, config: &PowerCapConfig) -> Result<(), DelegationError> {
+    let valid = match config.cap {
+        PowerCap::None => true,
+        PowerCap::Absolute(amount) => amount > 0,
+        PowerCap::Percentage(share_bps) => share_bps > 0 && share_bps <= BPS_DENOMINATOR,
+    };
+    if !valid {
+        return Err(DelegationError::InvalidPowerCap);
+    }
+
+    env.storage().persistent().set(&POWER_CAP, config);
+    events::power_cap_set(env, config);
+    Ok(())
+}
+
+/// Get the most delegated power a member may receive
+///
+/// # Arguments
+/// * `env` - The contract environment
+///
+/// # Returns
+/// * `Option<i128>` - The limit, or `None` without a cap
+pub fn get_limit(env: &Env) -> Option<i128> {
+    match get_config(env).cap {
+        PowerCap::None => None,
+        PowerCap::Absolute(amount) => Some(amount),
+        PowerCap::Percentage(share_bps) => Some(power::share_of(total_power(env), share_bps)),
+    }
+}
+
+/// Helper function to get the total voting power that caps and concentration are measured
+/// against
+///
+/// An external power source reports the power of all its members, synced or not; manual base
+/// power is summed over the members taking part in delegation.
+///
+/// # Arguments
+/// * `env` - The contract environment
+///
+/// # Returns
+/// * `i128` - The total power
+fn total_power(env: &Env) -> i128 {
+    source::read_total_power(env).unwrap_or_else(|| checkpoints::get_total_power(env))
+}
+
+/// Reduce a delegation share so that the power it carries fits under the cap
+///
+/// # Arguments
+/// * `limit` - The most delegated power the delegate may receive
+/// * `received` - The delegated power the delegate already receives
+/// * `power` - The delegator's effective voting power
+/// * `share_bps` - The requested share in basis points
+///
+/// # Returns
+/// * `Result<u32, DelegationError>` - The allowed share, or `PowerCapExceeded` if none fits
+pub fn allowed_share(
+    limit: i128,
+    received: i128,
+    power: i128,
+    share_bps: u32,
+) -> Result<u32, DelegationError> {
+    let headroom = limit - received;
+    // Without power to carry, the share adds nothing to the delegate
+    if power <= 0 || power::share_of(power, share_bps) <= headroom {
+        return Ok(share_bps);
+    }
+
+    let denominator = BPS_DENOMINATOR as i128;
+    let allowed = headroom
+        .checked_mul(denominator)
+        .map_or_else(|| headroom / (power / denominator), |scaled| scaled / power);
+
+    if allowed <= 0 {
+        return Err(DelegationError::PowerCapExceeded);
+    }
+
+    Ok(allowed as u32)
+}
+
+/// Measure how concentrated delegated power is among the registered delegates
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `top` - The number of delegates with the most received power to measure, capped at
+///   `MAX_PAGE_SIZE`
+///
+/// # Returns
+/// * `Concentration` - The received power of the top delegates and its share of the total power
+pub fn get_concentration(env: Env, top: u32) -> Concentration {
+    let total_power = total_power(&env);
+    let delegates = registry::list_delegates(env, 0, top);
+
+    let mut top_power = 0i128;
+    for delegate in delegates.iter() {
+        top_power += delegate.received_power;
+    }
+    // Expired delegations may leave a delegate below those ranked after it
+    let largest_power = delegates
+        .iter()
+        .map(|delegate| delegate.received_power)
+        .max()
+        .unwrap_or(0);
+
+    Concentration {
+        total_power,
+        top_power,
+        top_share_bps: share_bps(top_power, total_power),
+        largest_share_bps: share_bps(largest_power, total_power),
+    }
+}
+
+/// Helper function to express an amount of power in basis points of the total power
+///
+/// # Arguments
+/// * `power` - The amount of power
+/// * `total_power` - The total power
+///
+/// # Returns
+/// * `u32` - The share in basis points (0 without any total power)
+fn share_bps(power: i128, total_power: i128) -> u32 {
+    if total_power <= 0 {
+        return 0;
+    }
+
+    let denominator = BPS_DENOMINATOR as i128;
+    let share = power.checked_mul(denominator).map_or_else(
+        || power / (total_power / denominator).max(1),
+        |scaled| scaled / total_power,
+    );
+
+    share.clamp(0, u32::MAX as i128) as u32
+}
@@ -0,0 +1,310 @@
+use crate::{CHECKPOINTS, Checkpoint, DelegationError, MAX_CHECKPOINTS, TOTAL_CHECKPOINTS};
+use soroban_sdk::{Address, Env, Symbol, Vec};
+
+/// Record a change in the effective voting power of a member
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `member` - The address whose power changed
+/// * `previous` - The effective power before the change
+/// * `power` - The effective power after the change
+/// * `expiring` - The earliest expiry among the delegations counted in `power`, with the power
+///   left without the expiring delegations, or `None` if none expires
+pub fn record(
+    env: &Env,
+    member: &Address,
+    previous: i128,
+    power: i128,
+    expiring: Option<(u64, i128)>,
+) {
+    let key = checkpoints_key(member);
+    let checkpoints = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+
+    env.storage()
+        .persistent()
+        .set(&key, &push(env, checkpoints, previous, power, expiring));
+}
+
+/// Record a change in the total base voting power
+///
+/// The total saturates instead of overflowing on extreme base powers. Unlike member
+/// checkpoints, the total history is never pruned, as every past vote is measured against it;
+/// each checkpoint is stored in its own entry so the history can grow without bound.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delta` - The change in total power
+pub fn record_total(env: &Env, delta: i128) {
+    if delta == 0 {
+        return;
+    }
+
+    let ledger = env.ledger().sequence();
+    let power = get_total_power(env).saturating_add(delta);
+    let mut len = total_len(env);
+
+    if len == 0 && ledger > 0 {
+        set_total_checkpoint(
+            env,
+            0,
+            &Checkpoint {
+                ledger: 0,
+                timestamp: 0,
+                power: 0,
+                expires_at: u64::MAX,
+                lasting_power: 0,
+            },
+        );
+        len = 1;
+    }
+
+    // A later change in the same ledger overwrites its checkpoint
+    let index = match len.checked_sub(1) {
+        Some(last) if total_checkpoint(env, last).ledger == ledger => last,
+        _ => {
+            len += 1;
+            len - 1
+        }
+    };
+
+    set_total_checkpoint(
+        env,
+        index,
+        &Checkpoint {
+            ledger,
+            timestamp: env.ledger().timestamp(),
+            power,
+            expires_at: u64::MAX,
+            lasting_power: power,
+        },
+    );
+    env.storage().persistent().set(&TOTAL_CHECKPOINTS, &len);
+}
+
+/// Get the effective voting power of a member at the end of a past ledger
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `member` - The address to get voting power for
+/// * `ledger` - The ledger sequence, which must be before the current one
+///
+/// # Returns
+/// * `Result<Option<i128>, DelegationError>` - The power at that ledger, or `None` if it never
+///   changed and equals the current power
+pub fn get_past_power(
+    env: &Env,
+    member: &Address,
+    ledger: u32,
+) -> Result<Option<i128>, DelegationError> {
+    let checkpoints: Vec<Checkpoint> = env
+        .storage()
+        .persistent()
+        .get(&checkpoints_key(member))
+        .unwrap_or(Vec::new(env));
+
+    lookup(
+        env,
+        checkpoints.len(),
+        |index| checkpoints.get_unchecked(index),
+        ledger,
+    )
+}
+
+/// Get the total base voting power at the end of a past ledger
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `ledger` - The ledger sequence, which must be before the current one
+///
+/// # Returns
+/// * `Result<i128, DelegationError>` - The total power at that ledger
+pub fn get_past_total_power(env: &Env, ledger: u32) -> Result<i128, DelegationError> {
+    lookup(
+        env,
+        total_len(env),
+        |index| total_checkpoint(env, index),
+        ledger,
+    )
+    .map(|power| power.unwrap_or(0))
+}
+
+/// Get the current total base voting power
+///
+/// # Arguments
+/// * `env` - The contract environment
+///
+/// # Returns
+/// * `i128` - The total power (0 before any member is synced)
+pub fn get_total_power(env: &Env) -> i128 {
+    total_len(env)
+        .checked_sub(1)
+        .map_or(0, |last| total_checkpoint(env, last).power)
+}
+
+/// Helper function to append a checkpoint for the current ledger
+///
+/// The first checkpoint is preceded by one at ledger 0 holding the power before any change.
+/// A later change in the same ledger overwrites its checkpoint, and the oldest checkpoints are
+/// dropped beyond `MAX_CHECKPOINTS`.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `checkpoints` - The existing checkpoints, oldest first
+/// * `previous` - The power before the change
+/// * `power` - The power after the change
+/// * `expiring` - The earliest expiry counted in `power`, with the power left without it
+///
+/// # Returns
+/// * `Vec<Checkpoint>` - The updated checkpoints
+fn push(
+    env: &Env,
+    mut checkpoints: Vec<Checkpoint>,
+    previous: i128,
+    power: i128,
+    expiring: Option<(u64, i128)>,
+) -> Vec<Checkpoint> {
+    let ledger = env.ledger().sequence();
+
+    if checkpoints.is_empty() && ledger > 0 {
+        checkpoints.push_back(Checkpoint {
+            ledger: 0,
+            timestamp: 0,
+            power: previous,
+            expires_at: u64::MAX,
+            lasting_power: previous,
+        });
+    }
+
+    let (expires_at, lasting_power) = expiring.unwrap_or((u64::MAX, power));
+    let checkpoint = Checkpoint {
+        ledger,
+        timestamp: env.ledger().timestamp(),
+        power,
+        expires_at,
+        lasting_power,
+    };
+    match checkpoints.last() {
+        Some(last) if last.ledger == ledger => checkpoints.set(checkpoints.len() - 1, checkpoint),
+        _ => checkpoints.push_back(checkpoint),
+    }
+
+    while checkpoints.len() > MAX_CHECKPOINTS {
+        checkpoints.pop_front();
+    }
+
+    checkpoints
+}
+
+/// Helper function to binary search the power in effect at the end of a ledger
+///
+/// The time of a past ledger is only known to lie before the next checkpoint, or before now
+/// after the last one. Once the earliest expiry counted in a checkpoint falls in that window,
+/// the ledger may follow it, so the power that can have expired no longer counts.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `len` - The number of checkpoints
+/// * `checkpoint` - Reads the checkpoint at an index, oldest first
+/// * `ledger` - The ledger sequence, which must be before the current one
+///
+/// # Returns
+/// * `Result<Option<i128>, DelegationError>` - The power at that ledger (`None` without
+///   checkpoints), or `HistoryUnavailable` if the checkpoint covering it was pruned
+fn lookup(
+    env: &Env,
+    len: u32,
+    checkpoint: impl Fn(u32) -> Checkpoint,
+    ledger: u32,
+) -> Result<Option<i128>, DelegationError> {
+    if ledger >= env.ledger().sequence() {
+        return Err(DelegationError::FutureLedger);
+    }
+
+    // Find the number of checkpoints recorded at or before `ledger`
+    let (mut low, mut high) = (0, len);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if checkpoint(mid).ledger <= ledger {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+
+    if len == 0 {
+        return Ok(None);
+    }
+
+    if low == 0 {
+        return Err(DelegationError::HistoryUnavailable);
+    }
+
+    let found = checkpoint(low - 1);
+    let until = if low < len {
+        checkpoint(low).timestamp
+    } else {
+        env.ledger().timestamp()
+    };
+
+    if found.expires_at <= until {
+        return Ok(Some(found.lasting_power));
+    }
+
+    Ok(Some(found.power))
+}
+
+/// Helper function to get the number of total power checkpoints
+///
+/// # Arguments
+/// * `env` - The contract environment
+///
+/// # Returns
+/// * `u32` - The number of checkpoints
+fn total_len(env: &Env) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&TOTAL_CHECKPOINTS)
+        .unwrap_or(0)
+}
+
+/// Helper function to load a total power checkpoint
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `index` - The checkpoint index, oldest first
+///
+/// # Returns
+/// * `Checkpoint` - The checkpoint
+fn total_checkpoint(env: &Env, index: u32) -> Checkpoint {
+    env.storage()
+        .persistent()
+        .get(&(TOTAL_CHECKPOINTS, index))
+        .unwrap()
+}
+
+/// Helper function to store a total power checkpoint
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `index` - The checkpoint index, oldest first
+/// * `checkpoint` - The checkpoint
+fn set_total_checkpoint(env: &Env, index: u32, checkpoint: &Checkpoint) {
+    env.storage()
+        .persistent()
+        .set(&(TOTAL_CHECKPOINTS, index), checkpoint);
+}
+
+/// Generate storage key for a member's checkpoints
+///
+/// # Arguments
+/// * `member` - The member address
+///
+/// # Returns
+/// * Tuple for storage key
+fn checkpoints_key(member: &Address) -> (Symbol, Address) {
+    (CHECKPOINTS, member.clone())
+}
@@ -1,8 +1,26 @@
-use crate::{DELEGATIONS, DELEGATORS, DelegationError};
-use soroban_sdk::{Address, Env, Symbol, Vec};
+use crate::{
+    BPS_DENOMINATOR, CapPolicy, DEFAULT_MAX_CHAIN_DEPTH, DELEGATIONS, DELEGATORS, Delegation,
+    DelegationError, DelegationScope, DelegationShare, ExpiringDelegation, MAX_DELEGATES,
+    MAX_DEPTH, MIN_EXPIRY, TOPIC_DELEGATIONS, TOPIC_DELEGATORS, TOPICS, Topic, caps, checkpoints,
+    events, power, registry,
+};
+use soroban_sdk::{Address, Env, Map, Symbol, Vec, vec};
+
+/// Which delegations to follow when walking the delegation graph
+pub enum Edges {
+    /// Every stored delegation, including expired ones not cleared yet; matches the cached power
+    Stored,
+    /// Only delegations that have not expired
+    Active,
+    /// Stored delegations plus the default delegations a topic falls back to once its
+    /// delegations expire; used to keep every possible graph free of cycles
+    Potential,
+}
 
 /// Delegate voting power to another member
 ///
+/// Delegates the full share and replaces any existing delegations of the delegator.
+///
 /// # Arguments
 /// * `env` - The contract environment
 /// * `delegator` - The address delegating their voting power
@@ -15,25 +33,69 @@ pub fn delegate_votes(
     delegator: Address,
     delegate: Address,
 ) -> Result<(), DelegationError> {
-    // Prevent self-delegation
-    if delegator == delegate {
-        return Err(DelegationError::SelfDelegation);
-    }
+    let shares = vec![
+        &env,
+        DelegationShare {
+            delegate,
+            share_bps: BPS_DENOMINATOR,
+            expires_at: None,
+        },
+    ];
 
-    // Remove any existing delegation first
-    if let Some(current_delegate) = get_delegate(env.clone(), delegator.clone()) {
-        remove_delegator_from_delegate(&env, &delegator, &current_delegate);
-    }
+    delegate_shares(env, delegator, shares)
+}
 
-    // Store the new delegation
-    env.storage()
-        .persistent()
-        .set(&delegation_key(&delegator), &delegate);
+/// Split voting power across several delegates
+///
+/// Replaces any existing delegations of the delegator. The shares may add up to less than
+/// 100%, in which case the rest is not delegated. Shares with an expiry stop applying once the
+/// ledger timestamp reaches it.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegator` - The address delegating their voting power
+/// * `shares` - The delegates and the basis points of power each receives
+///
+/// # Returns
+/// * `Result<(), DelegationError>` - Success or error
+pub fn delegate_shares(
+    env: Env,
+    delegator: Address,
+    shares: Vec<DelegationShare>,
+) -> Result<(), DelegationError> {
+    validate_shares(&env, &delegator, &shares)?;
 
-    // Update reverse lookup - add delegator to delegate's list
-    add_delegator_to_delegate(&env, &delegator, &delegate);
+    // Delegate the delegator's current base power
+    power::sync_base_power(env.clone(), delegator.clone());
 
-    Ok(())
+    replace_delegations(&env, &delegator, &None, &shares)
+}
+
+/// Set the delegates of a member for one topic
+///
+/// Replaces any existing delegations of the delegator for that topic. The topic no longer
+/// follows the default delegation until `undelegate_topic` is called.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegator` - The address delegating their voting power
+/// * `topic` - The topic to delegate
+/// * `shares` - The delegates and the basis points of power each receives
+///
+/// # Returns
+/// * `Result<(), DelegationError>` - Success or error
+pub fn delegate_topic_shares(
+    env: Env,
+    delegator: Address,
+    topic: Topic,
+    shares: Vec<DelegationShare>,
+) -> Result<(), DelegationError> {
+    validate_shares(&env, &delegator, &shares)?;
+
+    // Delegate the delegator's current base power
+    power::sync_base_power(env.clone(), delegator.clone());
+
+    replace_delegations(&env, &delegator, &Some(topic), &shares)
 }
 
 /// Remove delegation and restore direct control
@@ -46,24 +108,112 @@ pub fn delegate_votes(
 /// * `Result<(), DelegationError>` - Success or error
 pub fn undelegate(env: Env, delegator: Address) -> Result<(), DelegationError> {
     // Check if delegation exists
-    let delegate = match get_delegate(env.clone(), delegator.clone()) {
-        Some(delegate) => delegate,
-        None => return Err(DelegationError::NoDelegation),
-    };
+    if get_scoped_delegations(&env, &delegator, &None).is_empty() {
+        return Err(DelegationError::NoDelegation);
+    }
 
-    // Remove delegation from storage
-    env.storage()
-        .persistent()
-        .remove(&delegation_key(&delegator));
+    replace_delegations(&env, &delegator, &None, &Vec::new(&env))
+}
+
+/// Remove the delegation of a member for one topic, which then follows the default delegation
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegator` - The address removing their delegation
+/// * `topic` - The topic to remove the delegation for
+///
+/// # Returns
+/// * `Result<(), DelegationError>` - Success or error
+pub fn undelegate_topic(env: Env, delegator: Address, topic: Topic) -> Result<(), DelegationError> {
+    // Check if delegation exists
+    let scope = Some(topic);
+    if get_scoped_delegations(&env, &delegator, &scope).is_empty() {
+        return Err(DelegationError::NoDelegation);
+    }
 
-    // Update reverse lookup - remove delegator from delegate's list
-    remove_delegator_from_delegate(&env, &delegator, &delegate);
+    replace_delegations(&env, &delegator, &scope, &Vec::new(&env))
+}
 
-    Ok(())
+/// Remove the expired delegations of a member, by default and for every topic
+///
+/// Power queries already ignore expired delegations; clearing them restores the cheap cached
+/// reads for the delegates. Anyone may call this.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegator` - The address whose expired delegations to remove
+///
+/// # Returns
+/// * `Result<u32, DelegationError>` - The number of delegations removed
+pub fn clear_expired(env: Env, delegator: Address) -> Result<u32, DelegationError> {
+    Ok(retain_delegations(&env, &delegator, |delegation| {
+        is_active(&env, delegation)
+    }))
+}
+
+/// Remove every delegation a delegator made to a delegate, by default and for every topic
+///
+/// Topics whose delegations are all removed follow the default delegation again.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegate` - The delegate removing the delegator
+/// * `delegator` - The delegator to remove
+///
+/// # Returns
+/// * `Result<u32, DelegationError>` - The number of delegations removed, or `NoDelegation` if
+///   the delegator does not delegate to the delegate
+pub fn remove_delegator(
+    env: Env,
+    delegate: Address,
+    delegator: Address,
+) -> Result<u32, DelegationError> {
+    let removed = retain_delegations(&env, &delegator, |delegation| {
+        delegation.delegate != delegate
+    });
+
+    if removed == 0 {
+        return Err(DelegationError::NoDelegation);
+    }
+
+    Ok(removed)
+}
+
+/// List the delegations of a member that expire within a time window
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegator` - The address to check delegations for
+/// * `within` - The window, in seconds from the current ledger timestamp
+///
+/// # Returns
+/// * `Vec<ExpiringDelegation>` - The delegations that are still active and expire in the window
+pub fn get_expiring(env: Env, delegator: Address, within: u64) -> Vec<ExpiringDelegation> {
+    let horizon = env.ledger().timestamp().saturating_add(within);
+    let mut expiring = Vec::new(&env);
+
+    for scope in scopes(&env).iter() {
+        for delegation in get_scoped_delegations(&env, &delegator, &scope).iter() {
+            let expires_soon = delegation
+                .expires_at
+                .is_some_and(|expires_at| expires_at <= horizon);
+
+            if expires_soon && is_active(&env, &delegation) {
+                expiring.push_back(ExpiringDelegation {
+                    scope: to_scope(&scope),
+                    delegation,
+                });
+            }
+        }
+    }
+
+    expiring
 }
 
 /// Retrieve the current delegate of a member
 ///
+/// For split delegations, this is the first delegate; see `get_delegations` for all of them.
+///
 /// # Arguments
 /// * `env` - The contract environment
 /// * `delegator` - The address to check delegation for
@@ -71,7 +221,170 @@ pub fn undelegate(env: Env, delegator: Address) -> Result<(), DelegationError> {
 /// # Returns
 /// * `Option<Address>` - The delegate address if delegation exists
 pub fn get_delegate(env: Env, delegator: Address) -> Option<Address> {
-    env.storage().persistent().get(&delegation_key(&delegator))
+    get_delegations(env, delegator)
+        .first()
+        .map(|delegation| delegation.delegate)
+}
+
+/// Retrieve all delegations made by a member, with their shares
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegator` - The address to check delegations for
+///
+/// # Returns
+/// * `Vec<Delegation>` - The delegations of the member
+pub fn get_delegations(env: Env, delegator: Address) -> Vec<Delegation> {
+    active_only(&env, get_scoped_delegations(&env, &delegator, &None))
+}
+
+/// Retrieve the delegations a member set for one topic, with their shares
+///
+/// Empty if the topic follows the default delegation.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegator` - The address to check delegations for
+/// * `topic` - The topic to check
+///
+/// # Returns
+/// * `Vec<Delegation>` - The topic delegations of the member
+pub fn get_topic_delegations(env: Env, delegator: Address, topic: Topic) -> Vec<Delegation> {
+    active_only(&env, get_scoped_delegations(&env, &delegator, &Some(topic)))
+}
+
+/// Retrieve the delegations of a member that apply to a scope
+///
+/// A topic without its own delegations follows the default delegation.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegator` - The address to check delegations for
+/// * `scope` - The topic, or `None` for the default delegation
+/// * `edges` - Which delegations to consider
+///
+/// # Returns
+/// * `Vec<Delegation>` - The applicable delegations
+pub fn effective_delegations(
+    env: &Env,
+    delegator: &Address,
+    scope: &Option<Topic>,
+    edges: &Edges,
+) -> Vec<Delegation> {
+    let mut delegations = get_scoped_delegations(env, delegator, scope);
+    if let Edges::Active = edges {
+        delegations = active_only(env, delegations);
+    }
+
+    if scope.is_none() || !follows_default(&delegations, edges) {
+        return delegations;
+    }
+
+    let mut defaults = get_scoped_delegations(env, delegator, &None);
+    if let Edges::Active = edges {
+        defaults = active_only(env, defaults);
+    }
+
+    delegations.append(&defaults);
+    delegations
+}
+
+/// Retrieve the delegations received by a member that apply to a scope
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegate` - The address to get delegations for
+/// * `scope` - The topic, or `None` for the default delegation
+/// * `edges` - Which delegations to consider
+///
+/// # Returns
+/// * `Vec<Delegation>` - The applicable delegations
+pub fn effective_received(
+    env: &Env,
+    delegate: &Address,
+    scope: &Option<Topic>,
+    edges: &Edges,
+) -> Vec<Delegation> {
+    let mut delegations = get_scoped_received(env, delegate, scope);
+
+    if scope.is_some() {
+        // Default delegations count for delegators that did not delegate the topic
+        for delegation in get_scoped_received(env, delegate, &None).iter() {
+            let mut topic_delegations = get_scoped_delegations(env, &delegation.delegator, scope);
+            if let Edges::Active = edges {
+                topic_delegations = active_only(env, topic_delegations);
+            }
+
+            if follows_default(&topic_delegations, edges) {
+                delegations.push_back(delegation);
+            }
+        }
+    }
+
+    match edges {
+        Edges::Active => active_only(env, delegations),
+        _ => delegations,
+    }
+}
+
+/// Get the earliest expiry among the delegations whose power reaches a member, directly or
+/// through other delegates
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `member` - The address to check
+///
+/// # Returns
+/// * `u64` - The earliest expiry timestamp (`u64::MAX` if none)
+pub fn get_min_expiry(env: &Env, member: &Address) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&min_expiry_key(member))
+        .unwrap_or(u64::MAX)
+}
+
+/// Check whether a delegation still applies at the current ledger timestamp
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegation` - The delegation to check
+///
+/// # Returns
+/// * `bool` - True unless the delegation has expired
+pub fn is_active(env: &Env, delegation: &Delegation) -> bool {
+    delegation
+        .expires_at
+        .is_none_or(|expires_at| env.ledger().timestamp() < expires_at)
+}
+
+/// List the default scope followed by every topic
+///
+/// # Arguments
+/// * `env` - The contract environment
+///
+/// # Returns
+/// * `Vec<Option<Topic>>` - `None` for the default delegation, then each topic
+pub fn scopes(env: &Env) -> Vec<Option<Topic>> {
+    let mut scopes = Vec::from_array(env, [None]);
+    for topic in TOPICS {
+        scopes.push_back(Some(topic));
+    }
+
+    scopes
+}
+
+/// Convert a scope into its contract type
+///
+/// # Arguments
+/// * `scope` - The topic, or `None` for the default delegation
+///
+/// # Returns
+/// * `DelegationScope` - The matching scope
+pub fn to_scope(scope: &Option<Topic>) -> DelegationScope {
+    match scope {
+        None => DelegationScope::Default,
+        Some(topic) => DelegationScope::Topic(topic.clone()),
+    }
 }
 
 /// Return all addresses delegating to a member
@@ -83,11 +396,59 @@ pub fn get_delegate(env: Env, delegator: Address) -> Option<Address> {
 /// # Returns
 /// * `Vec<Address>` - Vector of addresses delegating to the member
 pub fn get_delegators(env: Env, delegate: Address) -> Vec<Address> {
+    let mut delegators = Vec::new(&env);
+
+    for delegation in get_received_delegations(env.clone(), delegate).iter() {
+        delegators.push_back(delegation.delegator);
+    }
+
+    delegators
+}
+
+/// Return all delegations received by a member, with their shares
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegate` - The address to get delegations for
+///
+/// # Returns
+/// * `Vec<Delegation>` - The delegations to the member
+pub fn get_received_delegations(env: Env, delegate: Address) -> Vec<Delegation> {
+    active_only(&env, get_scoped_received(&env, &delegate, &None))
+}
+
+/// Set the longest delegation chain, in hops, that a new delegation may create
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `depth` - The maximum number of hops, at least 1
+///
+/// # Returns
+/// * `Result<(), DelegationError>` - Success or error
+pub fn set_max_chain_depth(env: Env, depth: u32) -> Result<(), DelegationError> {
+    if depth == 0 {
+        return Err(DelegationError::InvalidChainDepth);
+    }
+
+    env.storage().persistent().set(&MAX_DEPTH, &depth);
+    events::max_chain_depth_set(&env, depth);
+    Ok(())
+}
+
+/// Get the longest delegation chain, in hops, that a new delegation may create
+///
+/// # Arguments
+/// * `env` - The contract environment
+///
+/// # Returns
+/// * `u32` - The maximum number of hops (defaults to `DEFAULT_MAX_CHAIN_DEPTH`)
+pub fn get_max_chain_depth(env: Env) -> u32 {
     env.storage()
         .persistent()
-        .get(&delegators_key(&delegate))
-        .unwrap_or(Vec::new(&env))
+        .get(&MAX_DEPTH)
+        .unwrap_or(DEFAULT_MAX_CHAIN_DEPTH)
 }
+
 /// Check if an address has any delegation relationships
 ///
 /// # Arguments
@@ -97,14 +458,18 @@ pub fn get_delegators(env: Env, delegate: Address) -> Vec<Address> {
 /// # Returns
 /// * `bool` - True if the address has delegation relationships
 pub fn has_delegation(env: Env, address: Address) -> bool {
-    // Check if address is a delegator
-    if get_delegate(env.clone(), address.clone()).is_some() {
-        return true;
-    }
+    // Check if address is a delegator or has delegators, by default or for a topic
+    scopes(&env).iter().any(|scope| {
+        let delegations = get_scoped_delegations(&env, &address, &scope);
+        let received = get_scoped_received(&env, &address, &scope);
 
-    // Check if address has delegators
-    let delegators = get_delegators(env, address);
-    !delegators.is_empty()
+        delegations
+            .iter()
+            .any(|delegation| is_active(&env, &delegation))
+            || received
+                .iter()
+                .any(|delegation| is_active(&env, &delegation))
+    })
 }
 
 /// Generate storage key for delegation mapping
@@ -129,50 +494,744 @@ fn delegators_key(delegate: &Address) -> (Symbol, Address) {
     (DELEGATORS, delegate.clone())
 }
 
-/// Helper function to add a delegator to a delegate's list of delegators
+/// Generate storage key for topic delegation mapping
 ///
 /// # Arguments
-/// * `env` - The contract environment
 /// * `delegator` - The delegator address
+/// * `topic` - The delegated topic
+///
+/// # Returns
+/// * Tuple for storage key
+fn topic_delegation_key(delegator: &Address, topic: &Topic) -> (Symbol, Address, Topic) {
+    (TOPIC_DELEGATIONS, delegator.clone(), topic.clone())
+}
+
+/// Generate storage key for topic delegators mapping
+///
+/// # Arguments
 /// * `delegate` - The delegate address
-fn add_delegator_to_delegate(env: &Env, delegator: &Address, delegate: &Address) {
-    let mut delegators = get_delegators(env.clone(), delegate.clone());
+/// * `topic` - The delegated topic
+///
+/// # Returns
+/// * Tuple for storage key
+fn topic_delegators_key(delegate: &Address, topic: &Topic) -> (Symbol, Address, Topic) {
+    (TOPIC_DELEGATORS, delegate.clone(), topic.clone())
+}
+
+/// Generate storage key for earliest upstream expiry mapping
+///
+/// # Arguments
+/// * `member` - The member address
+///
+/// # Returns
+/// * Tuple for storage key
+fn min_expiry_key(member: &Address) -> (Symbol, Address) {
+    (MIN_EXPIRY, member.clone())
+}
 
-    // Check if delegator is already in the list
-    for addr in delegators.iter() {
-        if addr == *delegator {
-            return; // Already exists
+/// Helper function to keep only the delegations that have not expired
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegations` - The delegations to filter
+///
+/// # Returns
+/// * `Vec<Delegation>` - The active delegations
+fn active_only(env: &Env, delegations: Vec<Delegation>) -> Vec<Delegation> {
+    let mut active = Vec::new(env);
+
+    for delegation in delegations.iter() {
+        if is_active(env, &delegation) {
+            active.push_back(delegation);
         }
     }
 
-    delegators.push_back(delegator.clone());
-    env.storage()
-        .persistent()
-        .set(&delegators_key(delegate), &delegators);
+    active
 }
 
-/// Helper function to remove a delegator from a delegate's list of delegators
+/// Helper function to decide whether a delegator's topic follows its default delegation
+///
+/// # Arguments
+/// * `topic_delegations` - The delegator's delegations for the topic, as seen through `edges`
+/// * `edges` - Which delegations are considered
+///
+/// # Returns
+/// * `bool` - True if the default delegations apply to the topic
+fn follows_default(topic_delegations: &Vec<Delegation>, edges: &Edges) -> bool {
+    match edges {
+        Edges::Stored | Edges::Active => topic_delegations.is_empty(),
+        Edges::Potential => {
+            topic_delegations.is_empty()
+                || topic_delegations
+                    .iter()
+                    .any(|delegation| delegation.expires_at.is_some())
+        }
+    }
+}
+
+/// Helper function to load the delegations a member set for exactly one scope
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegator` - The delegator address
+/// * `scope` - The topic, or `None` for the default delegation
+///
+/// # Returns
+/// * `Vec<Delegation>` - The stored delegations
+fn get_scoped_delegations(
+    env: &Env,
+    delegator: &Address,
+    scope: &Option<Topic>,
+) -> Vec<Delegation> {
+    let delegations = match scope {
+        None => env.storage().persistent().get(&delegation_key(delegator)),
+        Some(topic) => env
+            .storage()
+            .persistent()
+            .get(&topic_delegation_key(delegator, topic)),
+    };
+
+    delegations.unwrap_or(Vec::new(env))
+}
+
+/// Helper function to store the delegations a member set for exactly one scope
 ///
 /// # Arguments
 /// * `env` - The contract environment
 /// * `delegator` - The delegator address
+/// * `scope` - The topic, or `None` for the default delegation
+/// * `delegations` - The delegations to store, removing the entry when empty
+fn set_scoped_delegations(
+    env: &Env,
+    delegator: &Address,
+    scope: &Option<Topic>,
+    delegations: &Vec<Delegation>,
+) {
+    let storage = env.storage().persistent();
+
+    match (scope, delegations.is_empty()) {
+        (None, true) => storage.remove(&delegation_key(delegator)),
+        (None, false) => storage.set(&delegation_key(delegator), delegations),
+        (Some(topic), true) => storage.remove(&topic_delegation_key(delegator, topic)),
+        (Some(topic), false) => storage.set(&topic_delegation_key(delegator, topic), delegations),
+    }
+}
+
+/// Helper function to load the delegations a member received for exactly one scope
+///
+/// # Arguments
+/// * `env` - The contract environment
 /// * `delegate` - The delegate address
-fn remove_delegator_from_delegate(env: &Env, delegator: &Address, delegate: &Address) {
-    let mut delegators = get_delegators(env.clone(), delegate.clone());
-    let mut new_delegators = Vec::new(env);
+/// * `scope` - The topic, or `None` for the default delegation
+///
+/// # Returns
+/// * `Vec<Delegation>` - The stored delegations
+fn get_scoped_received(env: &Env, delegate: &Address, scope: &Option<Topic>) -> Vec<Delegation> {
+    let delegations = match scope {
+        None => env.storage().persistent().get(&delegators_key(delegate)),
+        Some(topic) => env
+            .storage()
+            .persistent()
+            .get(&topic_delegators_key(delegate, topic)),
+    };
 
-    // Filter out the delegator
-    for addr in delegators.iter() {
-        if addr != *delegator {
-            new_delegators.push_back(addr);
+    delegations.unwrap_or(Vec::new(env))
+}
+
+/// Helper function to store the delegations a member received for exactly one scope
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegate` - The delegate address
+/// * `scope` - The topic, or `None` for the default delegation
+/// * `delegations` - The delegations to store, removing the entry when empty
+fn set_scoped_received(
+    env: &Env,
+    delegate: &Address,
+    scope: &Option<Topic>,
+    delegations: &Vec<Delegation>,
+) {
+    let storage = env.storage().persistent();
+
+    match (scope, delegations.is_empty()) {
+        (None, true) => storage.remove(&delegators_key(delegate)),
+        (None, false) => storage.set(&delegators_key(delegate), delegations),
+        (Some(topic), true) => storage.remove(&topic_delegators_key(delegate, topic)),
+        (Some(topic), false) => storage.set(&topic_delegators_key(delegate, topic), delegations),
+    }
+}
+
+/// Helper function to keep only the delegations of a delegator that satisfy `keep`, by default
+/// and for every topic
+///
+/// Removing delegations never adds a new one, so the kept delegations keep their shares without
+/// going through the depth and power cap checks again.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegator` - The delegator address
+/// * `keep` - Returns true for the delegations to keep
+///
+/// # Returns
+/// * `u32` - The number of delegations removed
+fn retain_delegations(env: &Env, delegator: &Address, keep: impl Fn(&Delegation) -> bool) -> u32 {
+    let mut removed = 0;
+
+    for scope in scopes(env).iter() {
+        let stored = get_scoped_delegations(env, delegator, &scope);
+        let mut remaining = Vec::new(env);
+
+        for delegation in stored.iter() {
+            if keep(&delegation) {
+                remaining.push_back(delegation);
+            }
+        }
+
+        if remaining.len() < stored.len() {
+            removed += stored.len() - remaining.len();
+            remove_delegations(env, delegator, &scope, &remaining);
+        }
+    }
+
+    removed
+}
+
+/// Helper function to remove some of the delegations of a delegator for one scope
+///
+/// The power carried by the removed delegations is withdrawn from their delegates, and a topic
+/// left without delegations sends its power through the default delegation again.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegator` - The delegator address
+/// * `scope` - The topic, or `None` for the default delegation
+/// * `remaining` - The delegations to keep
+fn remove_delegations(
+    env: &Env,
+    delegator: &Address,
+    scope: &Option<Topic>,
+    remaining: &Vec<Delegation>,
+) {
+    let shifted = following_scopes(env, delegator, scope, &Edges::Stored);
+
+    for affected in shifted.iter() {
+        shift_power(env, delegator, &affected, -1);
+    }
+
+    let previous = store_delegations(env, delegator, scope, remaining);
+
+    for affected in shifted.iter() {
+        shift_power(env, delegator, &affected, 1);
+    }
+
+    refresh_outgoing_expiries(env, delegator, previous);
+}
+
+/// Helper function to check the shares of a delegation request
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegator` - The address delegating their voting power
+/// * `shares` - The requested shares
+///
+/// # Returns
+/// * `Result<(), DelegationError>` - `InvalidShares` unless every share is positive, delegates
+///   are distinct and the total is at most 100%; `NotAccepting` for delegates that are not
+///   registered or refuse delegations; `InvalidExpiry` for expiries not in the future
+fn validate_shares(
+    env: &Env,
+    delegator: &Address,
+    shares: &Vec<DelegationShare>,
+) -> Result<(), DelegationError> {
+    if shares.is_empty() {
+        return Err(DelegationError::InvalidShares);
+    }
+
+    if shares.len() > MAX_DELEGATES {
+        return Err(DelegationError::TooManyDelegates);
+    }
+
+    let mut delegates = Vec::new(env);
+    let mut total = 0u32;
+
+    for share in shares.iter() {
+        // Prevent self-delegation
+        if share.delegate == *delegator {
+            return Err(DelegationError::SelfDelegation);
+        }
+
+        if !registry::is_accepting(env, &share.delegate) {
+            return Err(DelegationError::NotAccepting);
+        }
+
+        if share.share_bps == 0 || delegates.contains(&share.delegate) {
+            return Err(DelegationError::InvalidShares);
+        }
+
+        if share
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= env.ledger().timestamp())
+        {
+            return Err(DelegationError::InvalidExpiry);
+        }
+
+        total = total.saturating_add(share.share_bps);
+        delegates.push_back(share.delegate);
+    }
+
+    if total > BPS_DENOMINATOR {
+        return Err(DelegationError::InvalidShares);
+    }
+
+    Ok(())
+}
+
+/// Helper function to replace the delegations of a delegator for one scope
+///
+/// Changing the default delegation also changes every topic that follows it. The power carried
+/// by the old delegations is withdrawn from their delegates and the new delegations' power is
+/// added, after checking that no affected scope gains a cycle or an overly long chain. With the
+/// `CountBack` cap policy, shares are reduced to fit under the power cap of their delegates; no
+/// member down the new chains may otherwise gain power beyond the cap.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegator` - The delegator address
+/// * `scope` - The topic, or `None` for the default delegation
+/// * `shares` - The new delegates and their shares; empty to remove the delegations
+///
+/// # Returns
+/// * `Result<(), DelegationError>` - Success or error
+fn replace_delegations(
+    env: &Env,
+    delegator: &Address,
+    scope: &Option<Topic>,
+    shares: &Vec<DelegationShare>,
+) -> Result<(), DelegationError> {
+    let mut delegations: Vec<Delegation> = Vec::new(env);
+    for share in shares.iter() {
+        delegations.push_back(Delegation {
+            delegator: delegator.clone(),
+            delegate: share.delegate,
+            share_bps: share.share_bps,
+            expires_at: share.expires_at,
+        });
+    }
+
+    // Scopes whose cached power changes, and scopes whose graph may gain new delegations
+    let shifted = following_scopes(env, delegator, scope, &Edges::Stored);
+    let checked = following_scopes(env, delegator, scope, &Edges::Potential);
+
+    // Reject delegations that would close a cycle or build an overly long chain
+    let max_depth = get_max_chain_depth(env.clone());
+    for affected in checked.iter() {
+        // A topic falls back to the default delegations once its own are removed or expire
+        let mut applicable = delegations.clone();
+        if scope.is_some() && follows_default(&delegations, &Edges::Potential) {
+            applicable.append(&get_scoped_delegations(env, delegator, &None));
+        }
+
+        let upstream = upstream_depth(env, delegator, &affected, max_depth);
+        for delegation in applicable.iter() {
+            let downstream =
+                downstream_depth(env, delegator, &delegation.delegate, &affected, max_depth)?;
+            if upstream + 1 + downstream > max_depth {
+                return Err(DelegationError::ChainTooDeep);
+            }
+        }
+    }
+
+    // Delegated power of the members down the new chains, before the change
+    let limit = caps::get_limit(env);
+    let mut gaining = Vec::new(env);
+    if limit.is_some() {
+        let mut applicable = delegations.clone();
+        if scope.is_some() && delegations.is_empty() {
+            applicable = get_scoped_delegations(env, delegator, &None);
+        }
+
+        for affected in shifted.iter() {
+            let mut before = Map::new(env);
+            for delegation in applicable.iter() {
+                collect_downstream(env, &delegation.delegate, &affected, max_depth, &mut before);
+            }
+            gaining.push_back(before);
+        }
+    }
+
+    for affected in shifted.iter() {
+        shift_power(env, delegator, &affected, -1);
+    }
+
+    if let Some(limit) = limit
+        && caps::get_config(env).policy == CapPolicy::CountBack
+    {
+        delegations = fit_under_cap(env, delegator, &delegations, &shifted, limit)?;
+    }
+
+    let previous = store_delegations(env, delegator, scope, &delegations);
+
+    // Checkpoints recorded while adding the power note the expiries of the new delegations
+    refresh_outgoing_expiries(env, delegator, previous);
+
+    for affected in shifted.iter() {
+        shift_power(env, delegator, &affected, 1);
+    }
+
+    if let Some(limit) = limit {
+        for (affected, before) in shifted.iter().zip(gaining.iter()) {
+            for (member, previous) in before.iter() {
+                let received = power::get_active_delegated_power(env, &member, &affected);
+                if received > limit && received > previous {
+                    return Err(DelegationError::PowerCapExceeded);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Helper function to list a scope together with the topics that follow it
+///
+/// Only the default delegation has followers: the topics without delegations of their own.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegator` - The delegator address
+/// * `scope` - The topic, or `None` for the default delegation
+/// * `edges` - Whether to look at the stored or the potential delegations of the topics
+///
+/// # Returns
+/// * `Vec<Option<Topic>>` - The scope followed by the topics following it
+fn following_scopes(
+    env: &Env,
+    delegator: &Address,
+    scope: &Option<Topic>,
+    edges: &Edges,
+) -> Vec<Option<Topic>> {
+    let mut following = Vec::from_array(env, [scope.clone()]);
+
+    if scope.is_none() {
+        for topic in TOPICS {
+            let topic = Some(topic);
+            if follows_default(&get_scoped_delegations(env, delegator, &topic), edges) {
+                following.push_back(topic);
+            }
+        }
+    }
+
+    following
+}
+
+/// Helper function to store the delegations of a delegator for one scope and move the delegator
+/// between the reverse lookups of the old and new delegates
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegator` - The delegator address
+/// * `scope` - The topic, or `None` for the default delegation
+/// * `delegations` - The new delegations
+///
+/// # Returns
+/// * `Vec<Delegation>` - The delegations stored before
+fn store_delegations(
+    env: &Env,
+    delegator: &Address,
+    scope: &Option<Topic>,
+    delegations: &Vec<Delegation>,
+) -> Vec<Delegation> {
+    let previous = get_scoped_delegations(env, delegator, scope);
+    for delegation in previous.iter() {
+        remove_delegator_from_delegate(env, delegator, &delegation.delegate, scope);
+    }
+    for delegation in delegations.iter() {
+        add_delegator_to_delegate(env, &delegation, scope);
+    }
+    set_scoped_delegations(env, delegator, scope, delegations);
+    events::delegations_set(env, delegator, scope, delegations);
+
+    previous
+}
+
+/// Helper function to refresh the upstream expiries of the old and current delegates of a
+/// delegator after its delegations changed
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegator` - The delegator address
+/// * `previous` - The delegations replaced in the changed scope
+fn refresh_outgoing_expiries(env: &Env, delegator: &Address, previous: Vec<Delegation>) {
+    // A topic expiry can also move power onto the delegates of the default delegation
+    let mut outgoing = previous;
+    for affected in scopes(env).iter() {
+        outgoing.append(&get_scoped_delegations(env, delegator, &affected));
+    }
+
+    // Without any expiry reaching or leaving the delegator, its delegates' expiries are unchanged
+    let carries_expiry = get_min_expiry(env, delegator) < u64::MAX
+        || outgoing
+            .iter()
+            .any(|delegation| delegation.expires_at.is_some());
+    if carries_expiry {
+        for delegation in outgoing.iter() {
+            refresh_min_expiry(env, &delegation.delegate);
         }
     }
+}
+
+/// Helper function to reduce the shares of new delegations so that each delegate stays under the
+/// power cap in every affected scope
+///
+/// Expects the delegator's old delegations to be withdrawn already. The power a share no longer
+/// carries stays with the delegator.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegator` - The delegator address
+/// * `delegations` - The new delegations
+/// * `shifted` - The scopes the delegations apply to
+/// * `limit` - The most delegated power a member may receive
+///
+/// # Returns
+/// * `Result<Vec<Delegation>, DelegationError>` - The reduced delegations, or `PowerCapExceeded`
+///   if a delegate has no room left
+fn fit_under_cap(
+    env: &Env,
+    delegator: &Address,
+    delegations: &Vec<Delegation>,
+    shifted: &Vec<Option<Topic>>,
+    limit: i128,
+) -> Result<Vec<Delegation>, DelegationError> {
+    let mut fitted = Vec::new(env);
+
+    for mut delegation in delegations.iter() {
+        for affected in shifted.iter() {
+            delegation.share_bps = caps::allowed_share(
+                limit,
+                power::get_active_delegated_power(env, &delegation.delegate, &affected),
+                power::cached_power(env, delegator, &affected),
+                delegation.share_bps,
+            )?;
+        }
+        fitted.push_back(delegation);
+    }
+
+    Ok(fitted)
+}
+
+/// Helper function to record the delegated power, from delegations that have not expired, of a
+/// member and of the delegates down its delegation chains
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `member` - The address starting the chains
+/// * `scope` - The topic, or `None` for the default delegation
+/// * `limit` - The maximum chain depth to follow
+/// * `powers` - The recorded powers, by member
+fn collect_downstream(
+    env: &Env,
+    member: &Address,
+    scope: &Option<Topic>,
+    limit: u32,
+    powers: &mut Map<Address, i128>,
+) {
+    if !powers.contains_key(member.clone()) {
+        powers.set(
+            member.clone(),
+            power::get_active_delegated_power(env, member, scope),
+        );
+    }
+
+    if limit == 0 {
+        return;
+    }
+
+    for delegation in effective_delegations(env, member, scope, &Edges::Stored).iter() {
+        collect_downstream(env, &delegation.delegate, scope, limit - 1, powers);
+    }
+}
+
+/// Helper function to recompute the earliest upstream expiry of a member from its received
+/// delegations, continuing down its delegation chains while the value changes
+///
+/// Every expiry of a delegator counts, since an expired topic delegation sends the topic power
+/// through the default delegation instead.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `member` - The address to refresh
+fn refresh_min_expiry(env: &Env, member: &Address) {
+    let mut min_expiry = u64::MAX;
+
+    for scope in scopes(env).iter() {
+        for received in get_scoped_received(env, member, &scope).iter() {
+            min_expiry = min_expiry.min(get_min_expiry(env, &received.delegator));
+
+            for other in scopes(env).iter() {
+                for delegation in get_scoped_delegations(env, &received.delegator, &other).iter() {
+                    min_expiry = min_expiry.min(delegation.expires_at.unwrap_or(u64::MAX));
+                }
+            }
+        }
+    }
+
+    if min_expiry == get_min_expiry(env, member) {
+        return;
+    }
 
-    if new_delegators.is_empty() {
-        env.storage().persistent().remove(&delegators_key(delegate));
+    if min_expiry == u64::MAX {
+        env.storage().persistent().remove(&min_expiry_key(member));
     } else {
         env.storage()
             .persistent()
-            .set(&delegators_key(delegate), &new_delegators);
+            .set(&min_expiry_key(member), &min_expiry);
     }
+
+    for scope in scopes(env).iter() {
+        for delegation in get_scoped_delegations(env, member, &scope).iter() {
+            refresh_min_expiry(env, &delegation.delegate);
+        }
+    }
+}
+
+/// Helper function to add (`sign` 1) or withdraw (`sign` -1) the power a delegator passes to
+/// its delegates in a scope
+///
+/// For the default delegation, also records the power the delegator keeps.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegator` - The delegator address
+/// * `scope` - The topic, or `None` for the default delegation
+/// * `sign` - 1 to add the power, -1 to withdraw it
+fn shift_power(env: &Env, delegator: &Address, scope: &Option<Topic>, sign: i128) {
+    let delegated_power = power::cached_power(env, delegator, scope);
+    let delegations = effective_delegations(env, delegator, scope, &Edges::Stored);
+
+    // The delegator keeps the power withdrawn from its delegates
+    if scope.is_none() {
+        let kept = power::kept_power(delegated_power, &delegations);
+        let (previous, current, keeping) = if sign < 0 {
+            (kept, delegated_power, Vec::new(env))
+        } else {
+            (delegated_power, kept, delegations.clone())
+        };
+        let expiring = power::expiring_power(env, delegator, &keeping);
+        checkpoints::record(env, delegator, previous, current, expiring);
+    }
+
+    for delegation in delegations.iter() {
+        power::adjust_power(
+            env,
+            &delegation.delegate,
+            scope,
+            sign * power::share_of(delegated_power, delegation.share_bps),
+        );
+    }
+}
+
+/// Helper function to find the longest chain of delegates that follows `member`
+///
+/// Stops descending once a chain grows past `limit` hops.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegator` - The address about to delegate to `member`
+/// * `member` - The address starting the chains
+/// * `scope` - The topic, or `None` for the default delegation
+/// * `limit` - The maximum chain depth
+///
+/// # Returns
+/// * `Result<u32, DelegationError>` - The number of hops, or `CircularDelegation` if a chain
+///   leads back to `delegator`
+fn downstream_depth(
+    env: &Env,
+    delegator: &Address,
+    member: &Address,
+    scope: &Option<Topic>,
+    limit: u32,
+) -> Result<u32, DelegationError> {
+    let mut depth = 0;
+
+    for delegation in effective_delegations(env, member, scope, &Edges::Potential).iter() {
+        if delegation.delegate == *delegator {
+            return Err(DelegationError::CircularDelegation);
+        }
+
+        if limit == 0 {
+            return Ok(1);
+        }
+        depth = depth
+            .max(1 + downstream_depth(env, delegator, &delegation.delegate, scope, limit - 1)?);
+    }
+
+    Ok(depth)
+}
+
+/// Helper function to find the longest chain of delegators that ends at `member`
+///
+/// Stops descending once a chain reaches `limit` hops.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `member` - The address ending the chains
+/// * `scope` - The topic, or `None` for the default delegation
+/// * `limit` - The maximum chain depth
+///
+/// # Returns
+/// * `u32` - The number of hops in the longest chain, capped at `limit`
+fn upstream_depth(env: &Env, member: &Address, scope: &Option<Topic>, limit: u32) -> u32 {
+    if limit == 0 {
+        return 0;
+    }
+
+    let mut depth = 0;
+    for delegation in effective_received(env, member, scope, &Edges::Potential).iter() {
+        depth = depth.max(1 + upstream_depth(env, &delegation.delegator, scope, limit - 1));
+        if depth == limit {
+            break;
+        }
+    }
+
+    depth
+}
+
+/// Helper function to add a delegation to a delegate's list of received delegations
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegation` - The delegation to add
+/// * `scope` - The topic, or `None` for the default delegation
+fn add_delegator_to_delegate(env: &Env, delegation: &Delegation, scope: &Option<Topic>) {
+    let mut delegations = get_scoped_received(env, &delegation.delegate, scope);
+
+    delegations.push_back(delegation.clone());
+    set_scoped_received(env, &delegation.delegate, scope, &delegations);
+}
+
+/// Helper function to remove a delegator from a delegate's list of received delegations
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegator` - The delegator address
+/// * `delegate` - The delegate address
+/// * `scope` - The topic, or `None` for the default delegation
+fn remove_delegator_from_delegate(
+    env: &Env,
+    delegator: &Address,
+    delegate: &Address,
+    scope: &Option<Topic>,
+) {
+    let delegations = get_scoped_received(env, delegate, scope);
+    let mut new_delegations = Vec::new(env);
+
+    // Filter out the delegator
+    for delegation in delegations.iter() {
+        if delegation.delegator != *delegator {
+            new_delegations.push_back(delegation);
+        }
+    }
+
+    set_scoped_received(env, delegate, scope, &new_delegations);
 }
@@ -0,0 +1,104 @@
+use crate::{DelegateProfile, Delegation, PowerCapConfig, PowerSource, Topic, delegation};
+use soroban_sdk::{Address, Env, Symbol, Vec, symbol_short};
+
+/// Emit the admin the contract was initialized with
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `admin` - The admin address
+/// * `base_power` - The default base power of members without one set
+pub fn initialized(env: &Env, admin: &Address, base_power: i128) {
+    env.events()
+        .publish((symbol_short!("init"),), (admin.clone(), base_power));
+}
+
+/// Emit the delegations that now apply to a delegator in one scope
+///
+/// Every change to a delegator's delegations, including removals, expiry clean-ups and kicks,
+/// emits the full new list, so indexers can rebuild the delegation graph from these events.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegator` - The delegator address
+/// * `scope` - The topic, or `None` for the default delegation
+/// * `delegations` - The delegator's delegations in that scope, empty if removed
+pub fn delegations_set(
+    env: &Env,
+    delegator: &Address,
+    scope: &Option<Topic>,
+    delegations: &Vec<Delegation>,
+) {
+    env.events().publish(
+        (Symbol::new(env, "delegations_set"), delegator.clone()),
+        (delegation::to_scope(scope), delegations.clone()),
+    );
+}
+
+/// Emit a change in the base power counted for a member
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `member` - The member address
+/// * `power` - The new base power
+pub fn base_power_set(env: &Env, member: &Address, power: i128) {
+    env.events()
+        .publish((Symbol::new(env, "base_power_set"), member.clone()), power);
+}
+
+/// Emit a delegate registration or profile update
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `profile` - The delegate's profile
+pub fn delegate_registered(env: &Env, profile: &DelegateProfile) {
+    env.events().publish(
+        (
+            Symbol::new(env, "delegate_registered"),
+            profile.delegate.clone(),
+        ),
+        profile.clone(),
+    );
+}
+
+/// Emit a new maximum delegation chain depth
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `depth` - The maximum number of hops
+pub fn max_chain_depth_set(env: &Env, depth: u32) {
+    env.events()
+        .publish((Symbol::new(env, "max_depth_set"),), depth);
+}
+
+/// Emit a new base power source
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `source` - The power source
+pub fn power_source_set(env: &Env, source: &PowerSource) {
+    env.events()
+        .publish((Symbol::new(env, "power_source_set"),), source.clone());
+}
+
+/// Emit a new power cap configuration
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `config` - The cap and its policy
+pub fn power_cap_set(env: &Env, config: &PowerCapConfig) {
+    env.events()
+        .publish((Symbol::new(env, "power_cap_set"),), config.clone());
+}
+
+/// Emit an admin transfer
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `previous` - The previous admin
+/// * `admin` - The new admin
+pub fn admin_transferred(env: &Env, previous: &Address, admin: &Address) {
+    env.events().publish(
+        (Symbol::new(env, "admin_transferred"),),
+        (previous.clone(), admin.clone()),
+    );
+}
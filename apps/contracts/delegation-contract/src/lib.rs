@@ -1,19 +1,71 @@
 #![no_std]
 use soroban_sdk::{
-    Address, Env, Symbol, Vec, contract, contracterror, contractimpl, contracttype, symbol_short,
+    Address, BytesN, Env, Symbol, Vec, contract, contracterror, contractimpl, contracttype,
+    symbol_short, vec,
 };
 
+pub mod caps;
+pub mod checkpoints;
 pub mod delegation;
+pub mod events;
 pub mod power;
-
-use delegation::*;
-use power::*;
+pub mod registry;
+pub mod source;
 
 // Storage keys
 pub const DELEGATIONS: Symbol = symbol_short!("DELEG");
 pub const DELEGATORS: Symbol = symbol_short!("DLEGR");
 pub const MEMBER_POWER: Symbol = symbol_short!("POWER");
+pub const DELEGATED_POWER: Symbol = symbol_short!("DLGPOWER");
+pub const CHECKPOINTS: Symbol = symbol_short!("CHKPT");
+pub const TOTAL_CHECKPOINTS: Symbol = symbol_short!("TOTCHKPT");
+pub const TOPIC_DELEGATIONS: Symbol = symbol_short!("TDELEG");
+pub const TOPIC_DELEGATORS: Symbol = symbol_short!("TDLEGR");
+pub const TOPIC_POWER: Symbol = symbol_short!("TDLGPOWER");
 pub const ADMIN: Symbol = symbol_short!("ADMIN");
+pub const DEFAULT_POWER: Symbol = symbol_short!("DEFPOWER");
+pub const MAX_DEPTH: Symbol = symbol_short!("MAXDEPTH");
+pub const MIN_EXPIRY: Symbol = symbol_short!("EXPIRY");
+pub const DELEGATE_PROFILES: Symbol = symbol_short!("PROFILE");
+pub const REGISTERED_DELEGATES: Symbol = symbol_short!("DELEGATES");
+pub const DELEGATE_RANKS: Symbol = symbol_short!("RANK");
+pub const DELEGATE_POSITIONS: Symbol = symbol_short!("RANKPOS");
+pub const POWER_SOURCE: Symbol = symbol_short!("PWRSOURCE");
+pub const SYNCED_POWER: Symbol = symbol_short!("SYNCPOWER");
+pub const POWER_CAP: Symbol = symbol_short!("POWERCAP");
+
+/// Longest delegation chain allowed until the admin configures one
+pub const DEFAULT_MAX_CHAIN_DEPTH: u32 = 5;
+
+/// Most checkpoints kept per address; older ones are pruned
+pub const MAX_CHECKPOINTS: u32 = 100;
+
+/// Basis points representing a full (100%) delegation share
+pub const BPS_DENOMINATOR: u32 = 10_000;
+
+/// Most delegates a member can split their power across
+pub const MAX_DELEGATES: u32 = 10;
+
+/// Most entries returned by a paginated query
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Topic of a delegation, matching the proposal contract's `ProposalType`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Topic {
+    Treasury,
+    Governance,
+    Community,
+    Technical,
+}
+
+/// All topics that can be delegated separately
+pub const TOPICS: [Topic; 4] = [
+    Topic::Treasury,
+    Topic::Governance,
+    Topic::Community,
+    Topic::Technical,
+];
 
 /// Represents a delegation relationship
 #[contracttype]
@@ -21,6 +73,144 @@ pub const ADMIN: Symbol = symbol_short!("ADMIN");
 pub struct Delegation {
     pub delegator: Address,
     pub delegate: Address,
+    /// Share of the delegator's power, in basis points
+    pub share_bps: u32,
+    /// Ledger timestamp from which the delegation no longer applies, if any
+    pub expires_at: Option<u64>,
+}
+
+/// Represents a requested share of a split delegation
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DelegationShare {
+    pub delegate: Address,
+    /// Share of the delegator's power, in basis points
+    pub share_bps: u32,
+    /// Ledger timestamp from which the delegation no longer applies, if any
+    pub expires_at: Option<u64>,
+}
+
+/// Scope a delegation applies to
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DelegationScope {
+    Default,
+    Topic(Topic),
+}
+
+/// Represents a delegation that is about to expire
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExpiringDelegation {
+    pub scope: DelegationScope,
+    pub delegation: Delegation,
+}
+
+/// Where base voting power comes from
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PowerSource {
+    /// Set by the admin with `set_base_power`
+    Manual,
+    /// The member registry's `get_voting_power`
+    MemberRegistry(Address),
+    /// The Voting NFTs held, weighted by category
+    VotingNft(NftPowerConfig),
+}
+
+/// Voting NFT contract and the power each NFT category grants
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NftPowerConfig {
+    pub contract: Address,
+    pub weights: Vec<CategoryWeight>,
+    /// Power of NFTs in categories without a weight
+    pub default_weight: i128,
+}
+
+/// Power granted by each Voting NFT of a category
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CategoryWeight {
+    pub category: Symbol,
+    pub weight: i128,
+}
+
+/// Token record as returned by the voting NFT contract's `get_nfts_by_owner`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VotingNFT {
+    pub token_id: u32,
+    pub category: Symbol,
+    pub metadata: Symbol,
+    pub owner: Address,
+    pub issued_at: u64,
+}
+
+/// Limit on the delegated power a member may receive
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PowerCap {
+    None,
+    /// A fixed amount of power
+    Absolute(i128),
+    /// Basis points of the total base voting power of the members taking part in delegation
+    Percentage(u32),
+}
+
+/// What happens to a new delegation that would take a delegate over the power cap
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CapPolicy {
+    /// The delegation fails
+    Reject,
+    /// The share is reduced to fit and the excess stays with the delegator
+    CountBack,
+}
+
+/// Power cap configuration
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PowerCapConfig {
+    pub cap: PowerCap,
+    pub policy: CapPolicy,
+}
+
+/// How concentrated delegated power is among the registered delegates
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Concentration {
+    /// Total base voting power
+    pub total_power: i128,
+    /// Delegated power received by the top delegates
+    pub top_power: i128,
+    /// `top_power` in basis points of `total_power`
+    pub top_share_bps: u32,
+    /// Delegated power received by the largest delegate, in basis points of `total_power`
+    pub largest_share_bps: u32,
+}
+
+/// Public profile of a registered delegate
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DelegateProfile {
+    pub delegate: Address,
+    /// Hash of the delegate's statement, published off-chain
+    pub statement_hash: BytesN<32>,
+    /// Topics the delegate focuses on
+    pub interests: Vec<Topic>,
+    /// Whether the delegate accepts new delegations
+    pub accepting: bool,
+}
+
+/// Represents a registered delegate in the delegate listing
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DelegateSummary {
+    pub profile: DelegateProfile,
+    /// Voting power the delegate receives from its delegators, as of the last delegation
+    /// change reaching it
+    pub received_power: i128,
 }
 
 /// Represents voting power information
@@ -31,6 +221,20 @@ pub struct VotingPower {
     pub total_power: i128,
 }
 
+/// Voting power in effect from a ledger onwards
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Checkpoint {
+    pub ledger: u32,
+    /// Timestamp of the ledger in which the checkpoint was recorded
+    pub timestamp: u64,
+    pub power: i128,
+    /// Earliest expiry among the delegations counted in `power` (`u64::MAX` if none)
+    pub expires_at: u64,
+    /// The power left once every expiring delegation stops counting
+    pub lasting_power: i128,
+}
+
 #[contracterror]
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[repr(u32)]
@@ -39,6 +243,22 @@ pub enum DelegationError {
     NoDelegation = 2,
     InvalidAddress = 3,
     InsufficientPower = 4,
+    CircularDelegation = 5,
+    ChainTooDeep = 6,
+    InvalidChainDepth = 7,
+    FutureLedger = 8,
+    HistoryUnavailable = 9,
+    InvalidShares = 10,
+    TooManyDelegates = 11,
+    InvalidExpiry = 12,
+    NotAccepting = 13,
+    NotRegistered = 14,
+    ManualPowerDisabled = 15,
+    InvalidPowerSource = 16,
+    PowerCapExceeded = 17,
+    InvalidPowerCap = 18,
+    AdminNotSet = 19,
+    NotSynced = 20,
 }
 
 /// The main delegation contract
@@ -47,12 +267,59 @@ pub struct DelegationContract;
 
 #[contractimpl]
 impl DelegationContract {
+    /// Initialize the contract
+    ///
+    /// # Arguments
+    /// * `e` - The contract environment
+    /// * `admin` - The address allowed to configure the contract
+    /// * `base_power` - The manual base power of members without one set; negative values count
+    ///   as 0
     pub fn __constructor(e: Env, admin: Address, base_power: i128) {
         // Initialize the contract with the admin address
         e.storage().persistent().set(&ADMIN, &admin);
+
+        let base_power = base_power.max(0);
+        e.storage().persistent().set(&DEFAULT_POWER, &base_power);
+        events::initialized(&e, &admin, base_power);
+    }
+
+    /// Hand the admin role over to another address
+    ///
+    /// Both the current and the new admin must authorize the transfer.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `new_admin` - The address becoming admin
+    ///
+    /// # Returns
+    /// * `Result<(), DelegationError>` - Success or error
+    pub fn transfer_admin(env: Env, new_admin: Address) -> Result<(), DelegationError> {
+        let admin = require_admin(&env)?;
+        new_admin.require_auth();
+
+        env.storage().persistent().set(&ADMIN, &new_admin);
+        events::admin_transferred(&env, &admin, &new_admin);
+        Ok(())
     }
+
+    /// Get the admin address
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    ///
+    /// # Returns
+    /// * `Result<Address, DelegationError>` - The admin, or `AdminNotSet`
+    pub fn get_admin(env: Env) -> Result<Address, DelegationError> {
+        env.storage()
+            .persistent()
+            .get(&ADMIN)
+            .ok_or(DelegationError::AdminNotSet)
+    }
+
     /// Delegate voting power to another member
     ///
+    /// The delegate must be registered and accepting delegations.
+    ///
     /// # Arguments
     /// * `env` - The contract environment
     /// * `delegator` - The address delegating their voting power
@@ -71,6 +338,105 @@ impl DelegationContract {
         delegation::delegate_votes(env, delegator, delegate)
     }
 
+    /// Split voting power across several delegates
+    ///
+    /// Replaces any existing delegations. Shares are in basis points and may add up to at most
+    /// 100%; the rest of the power is not delegated. A share with an expiry stops counting once
+    /// the ledger timestamp reaches it, and its power returns to the delegator.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `delegator` - The address delegating their voting power
+    /// * `shares` - The delegates and the share each receives
+    ///
+    /// # Returns
+    /// * `Result<(), DelegationError>` - Success or error
+    pub fn delegate_shares(
+        env: Env,
+        delegator: Address,
+        shares: Vec<DelegationShare>,
+    ) -> Result<(), DelegationError> {
+        // Ensure the delegator has authorized this transaction
+        delegator.require_auth();
+
+        delegation::delegate_shares(env, delegator, shares)
+    }
+
+    /// Delegate voting power on proposals of one topic to another member
+    ///
+    /// Replaces any existing delegations of the delegator for that topic, which then no longer
+    /// follows the default delegation.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `delegator` - The address delegating their voting power
+    /// * `topic` - The topic to delegate
+    /// * `delegate` - The address receiving the delegated voting power
+    ///
+    /// # Returns
+    /// * `Result<(), DelegationError>` - Success or error
+    pub fn delegate_topic(
+        env: Env,
+        delegator: Address,
+        topic: Topic,
+        delegate: Address,
+    ) -> Result<(), DelegationError> {
+        // Ensure the delegator has authorized this transaction
+        delegator.require_auth();
+
+        let shares = vec![
+            &env,
+            DelegationShare {
+                delegate,
+                share_bps: BPS_DENOMINATOR,
+                expires_at: None,
+            },
+        ];
+        delegation::delegate_topic_shares(env, delegator, topic, shares)
+    }
+
+    /// Split voting power on proposals of one topic across several delegates
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `delegator` - The address delegating their voting power
+    /// * `topic` - The topic to delegate
+    /// * `shares` - The delegates and the share each receives
+    ///
+    /// # Returns
+    /// * `Result<(), DelegationError>` - Success or error
+    pub fn delegate_topic_shares(
+        env: Env,
+        delegator: Address,
+        topic: Topic,
+        shares: Vec<DelegationShare>,
+    ) -> Result<(), DelegationError> {
+        // Ensure the delegator has authorized this transaction
+        delegator.require_auth();
+
+        delegation::delegate_topic_shares(env, delegator, topic, shares)
+    }
+
+    /// Remove the topic delegation of a member, so the topic follows the default delegation again
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `delegator` - The address removing their delegation
+    /// * `topic` - The topic to remove the delegation for
+    ///
+    /// # Returns
+    /// * `Result<(), DelegationError>` - Success or error
+    pub fn undelegate_topic(
+        env: Env,
+        delegator: Address,
+        topic: Topic,
+    ) -> Result<(), DelegationError> {
+        // Ensure the delegator has authorized this transaction
+        delegator.require_auth();
+
+        delegation::undelegate_topic(env, delegator, topic)
+    }
+
     /// Remove delegation from a member
     ///
     /// # Arguments
@@ -86,41 +452,444 @@ impl DelegationContract {
         delegation::undelegate(env, delegator)
     }
 
+    /// Register as a delegate, or update the delegate profile
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `delegate` - The address registering as a delegate
+    /// * `statement_hash` - Hash of the delegate's statement, published off-chain
+    /// * `interests` - The topics the delegate focuses on
+    /// * `accepting` - Whether the delegate accepts new delegations
+    ///
+    /// # Returns
+    /// * `Result<(), DelegationError>` - Success or error
+    pub fn register_delegate(
+        env: Env,
+        delegate: Address,
+        statement_hash: BytesN<32>,
+        interests: Vec<Topic>,
+        accepting: bool,
+    ) -> Result<(), DelegationError> {
+        // Ensure the delegate has authorized this transaction
+        delegate.require_auth();
+
+        registry::register(env, delegate, statement_hash, interests, accepting);
+
+        Ok(())
+    }
+
+    /// Open or close a registered delegate to new delegations
+    ///
+    /// Existing delegations are kept; use `remove_delegator` to drop them.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `delegate` - The registered delegate
+    /// * `accepting` - Whether the delegate accepts new delegations
+    ///
+    /// # Returns
+    /// * `Result<(), DelegationError>` - Success or error
+    pub fn set_accepting_delegations(
+        env: Env,
+        delegate: Address,
+        accepting: bool,
+    ) -> Result<(), DelegationError> {
+        // Ensure the delegate has authorized this transaction
+        delegate.require_auth();
+
+        registry::set_accepting(env, delegate, accepting)
+    }
+
+    /// Remove every delegation a delegator made to the calling delegate
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `delegate` - The delegate removing the delegator
+    /// * `delegator` - The delegator to remove
+    ///
+    /// # Returns
+    /// * `Result<u32, DelegationError>` - The number of delegations removed
+    pub fn remove_delegator(
+        env: Env,
+        delegate: Address,
+        delegator: Address,
+    ) -> Result<u32, DelegationError> {
+        // Ensure the delegate has authorized this transaction
+        delegate.require_auth();
+
+        delegation::remove_delegator(env, delegate, delegator)
+    }
+
+    /// Get the profile of a registered delegate
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `delegate` - The address to look up
+    ///
+    /// # Returns
+    /// * `Result<Option<DelegateProfile>, DelegationError>` - The profile, if the address is
+    ///   registered
+    pub fn get_delegate_profile(
+        env: Env,
+        delegate: Address,
+    ) -> Result<Option<DelegateProfile>, DelegationError> {
+        Ok(registry::get_profile(env, delegate))
+    }
+
+    /// List registered delegates by received voting power, highest first
+    ///
+    /// Delegates with equal power are listed in registration order. The reported power leaves
+    /// out expired delegations even before they are cleared.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `start` - The index of the first delegate to return
+    /// * `limit` - The most delegates to return, capped at 100
+    ///
+    /// # Returns
+    /// * `Result<Vec<DelegateSummary>, DelegationError>` - The delegates on the page with their
+    ///   received power
+    pub fn get_registered_delegates(
+        env: Env,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<DelegateSummary>, DelegationError> {
+        Ok(registry::list_delegates(env, start, limit))
+    }
+
+    /// Remove the expired delegations of a member
+    ///
+    /// Expired delegations already stop counting; clearing them keeps power queries cheap.
+    /// Anyone may call this.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `delegator` - The address whose expired delegations to remove
+    ///
+    /// # Returns
+    /// * `Result<u32, DelegationError>` - The number of delegations removed
+    pub fn clear_expired_delegations(env: Env, delegator: Address) -> Result<u32, DelegationError> {
+        delegation::clear_expired(env, delegator)
+    }
+
+    /// List the delegations of a member that expire within a time window, to prompt renewals
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `delegator` - The address to check delegations for
+    /// * `within` - The window, in seconds from the current ledger timestamp
+    ///
+    /// # Returns
+    /// * `Result<Vec<ExpiringDelegation>, DelegationError>` - The active delegations expiring in
+    ///   the window
+    pub fn get_expiring_delegations(
+        env: Env,
+        delegator: Address,
+        within: u64,
+    ) -> Result<Vec<ExpiringDelegation>, DelegationError> {
+        Ok(delegation::get_expiring(env, delegator, within))
+    }
+
     /// get the total voting power of a member (own + delegated)
     ///
+    /// A delegating member keeps only the part of its power that its delegations do not carry.
+    ///
+    /// Reads the cached value, so the cost does not depend on the number of delegators, until a
+    /// delegation reaching the member expires and is not cleared yet.
+    ///
     /// # Arguments
     /// * `env` - The contract environment
     /// * `member` - The address to compute voting power for
     ///
     /// # Returns
-    /// * `i128` - The total effective voting power
-    pub fn get_voting_power(env: Env, member: Address) -> i128 {
-        power::get_voting_power(env, member)
+    /// * `Result<i128, DelegationError>` - The total effective voting power
+    pub fn get_voting_power(env: Env, member: Address) -> Result<i128, DelegationError> {
+        Ok(power::get_voting_power(env, member))
+    }
+
+    /// Get the total voting power of a member on proposals of one topic
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `member` - The address to get voting power for
+    /// * `topic` - The topic of the proposal
+    ///
+    /// # Returns
+    /// * `Result<i128, DelegationError>` - The total effective voting power for the topic
+    pub fn get_voting_power_for_topic(
+        env: Env,
+        member: Address,
+        topic: Topic,
+    ) -> Result<i128, DelegationError> {
+        Ok(power::get_voting_power_for_topic(env, member, topic))
+    }
+
+    /// Get the total voting power of a member at the end of a past ledger
+    ///
+    /// History follows the stored delegations and synced base power. A delegation stops counting
+    /// once it may have expired by the end of the ledger, whether or not it was cleared; as the
+    /// time of a past ledger is only bounded by the next recorded change, power that may have
+    /// expired counts for neither the delegator nor the delegate. Only synced members have a
+    /// history: the power source of a member never synced may have changed at any time.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `member` - The address to get voting power for
+    /// * `ledger` - The ledger sequence, which must be before the current one
+    ///
+    /// # Returns
+    /// * `Result<i128, DelegationError>` - The effective voting power at that ledger, or
+    ///   `NotSynced` for members never synced
+    pub fn get_past_voting_power(
+        env: Env,
+        member: Address,
+        ledger: u32,
+    ) -> Result<i128, DelegationError> {
+        match checkpoints::get_past_power(&env, &member, ledger)? {
+            Some(power) => Ok(power),
+            // Synced power only changes through checkpointed updates
+            None if power::is_synced(&env, &member) => Ok(power::get_voting_power(env, member)),
+            None => Err(DelegationError::NotSynced),
+        }
+    }
+
+    /// Get the sum of the base voting power of all members taking part in delegation, including
+    /// those with the default base power, at the end of a past ledger
+    ///
+    /// Delegations only move power between members, so neither active nor expired delegations
+    /// change the total.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `ledger` - The ledger sequence, which must be before the current one
+    ///
+    /// # Returns
+    /// * `Result<i128, DelegationError>` - The total voting power at that ledger
+    pub fn get_past_total_power(env: Env, ledger: u32) -> Result<i128, DelegationError> {
+        checkpoints::get_past_total_power(&env, ledger)
     }
 
-    /// Set the base voting power for a member
+    /// Check the cached voting power of members, by default and for every topic, against a full
+    /// recompute of their delegation trees
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `members` - The addresses to check
+    ///
+    /// # Returns
+    /// * `Result<Vec<Address>, DelegationError>` - The members whose cached power is inconsistent
+    ///   (empty if all match)
+    pub fn check_power_consistency(
+        env: Env,
+        members: Vec<Address>,
+    ) -> Result<Vec<Address>, DelegationError> {
+        Ok(power::find_inconsistent(env, members))
+    }
+
+    /// Set the base voting power for a member, with the manual power source
     ///
     /// # Arguments
     /// * `env` - The contract environment
     /// * `member` - The address to set base power for
     /// * `power` - The base voting power amount
-    pub fn set_base_power(env: Env, member: Address, power: i128) {
-        let admin: Address = env.storage().persistent().get(&ADMIN).unwrap();
+    ///
+    /// # Returns
+    /// * `Result<(), DelegationError>` - Success or error
+    pub fn set_base_power(env: Env, member: Address, power: i128) -> Result<(), DelegationError> {
+        require_admin(&env)?;
+        power::set_base_power(env, member, power)
+    }
 
-        admin.require_auth();
-        power::set_base_power(env, member, power);
+    /// Get the base voting power of a member from the power source
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `member` - The address to get base power for
+    ///
+    /// # Returns
+    /// * `Result<i128, DelegationError>` - The base voting power, 0 for non-members with an
+    ///   external source
+    pub fn get_base_power(env: Env, member: Address) -> Result<i128, DelegationError> {
+        Ok(power::get_base_power(env, member))
+    }
+
+    /// Set where base voting power comes from
+    ///
+    /// Power already delegated keeps the previous base power of each delegator until it is
+    /// synced with `sync_base_power`.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `source` - The manual mode, the member registry, or weighted Voting NFTs
+    ///
+    /// # Returns
+    /// * `Result<(), DelegationError>` - Success or error
+    pub fn set_power_source(env: Env, source: PowerSource) -> Result<(), DelegationError> {
+        require_admin(&env)?;
+        source::set_source(&env, &source)
+    }
+
+    /// Get where base voting power comes from
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    ///
+    /// # Returns
+    /// * `Result<PowerSource, DelegationError>` - The configured source
+    pub fn get_power_source(env: Env) -> Result<PowerSource, DelegationError> {
+        Ok(source::get_source(&env))
+    }
+
+    /// Read the base voting power of a member from the power source and pass any change on to
+    /// its delegates
+    ///
+    /// Anyone may call this after the source changes, e.g. when the member gains an NFT.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `member` - The address to sync
+    ///
+    /// # Returns
+    /// * `Result<i128, DelegationError>` - The synced base voting power
+    pub fn sync_base_power(env: Env, member: Address) -> Result<i128, DelegationError> {
+        Ok(power::sync_base_power(env, member))
+    }
+
+    /// Set the longest delegation chain, in hops, that a new delegation may create
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `depth` - The maximum number of hops, at least 1
+    ///
+    /// # Returns
+    /// * `Result<(), DelegationError>` - Success or error
+    pub fn set_max_chain_depth(env: Env, depth: u32) -> Result<(), DelegationError> {
+        require_admin(&env)?;
+        delegation::set_max_chain_depth(env, depth)
+    }
+
+    /// Get the longest delegation chain, in hops, that a new delegation may create
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    ///
+    /// # Returns
+    /// * `Result<u32, DelegationError>` - The maximum number of hops
+    pub fn get_max_chain_depth(env: Env) -> Result<u32, DelegationError> {
+        Ok(delegation::get_max_chain_depth(env))
+    }
+
+    /// Cap the delegated power any member may receive
+    ///
+    /// The cap applies to new delegations; power already delegated is not reduced.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `config` - The cap, as an amount or a share of the total power, and the policy for
+    ///   delegations exceeding it
+    ///
+    /// # Returns
+    /// * `Result<(), DelegationError>` - Success or error
+    pub fn set_power_cap(env: Env, config: PowerCapConfig) -> Result<(), DelegationError> {
+        require_admin(&env)?;
+        caps::set_config(&env, &config)
+    }
+
+    /// Get the power cap configuration
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    ///
+    /// # Returns
+    /// * `Result<PowerCapConfig, DelegationError>` - The cap and its policy
+    pub fn get_power_cap(env: Env) -> Result<PowerCapConfig, DelegationError> {
+        Ok(caps::get_config(&env))
+    }
+
+    /// Get the most delegated power a member may currently receive
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    ///
+    /// # Returns
+    /// * `Result<Option<i128>, DelegationError>` - The limit, or `None` without a cap
+    pub fn get_power_cap_limit(env: Env) -> Result<Option<i128>, DelegationError> {
+        Ok(caps::get_limit(&env))
+    }
+
+    /// Measure how concentrated delegated power is among the registered delegates
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `top` - The number of delegates with the most received power to measure, capped at 100
+    ///
+    /// # Returns
+    /// * `Result<Concentration, DelegationError>` - The received power of the top delegates and its
+    ///   share of the total
+    pub fn get_concentration(env: Env, top: u32) -> Result<Concentration, DelegationError> {
+        Ok(caps::get_concentration(env, top))
     }
 
     /// Retrieve the current delegate of a member
     ///
+    /// For split delegations, this is the first delegate.
+    ///
     /// # Arguments
     /// * `env` - The contract environment
     /// * `delegator` - The address to check delegation for
     ///
     /// # Returns
-    /// * `Option<Address>` - The delegate address if delegation exists
-    pub fn get_delegate(env: Env, delegator: Address) -> Option<Address> {
-        delegation::get_delegate(env, delegator)
+    /// * `Result<Option<Address>, DelegationError>` - The delegate address if delegation exists
+    pub fn get_delegate(env: Env, delegator: Address) -> Result<Option<Address>, DelegationError> {
+        Ok(delegation::get_delegate(env, delegator))
+    }
+
+    /// Retrieve all delegations of a member with their shares
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `delegator` - The address to check delegations for
+    ///
+    /// # Returns
+    /// * `Result<Vec<Delegation>, DelegationError>` - The delegations made by the member
+    pub fn get_delegations(
+        env: Env,
+        delegator: Address,
+    ) -> Result<Vec<Delegation>, DelegationError> {
+        Ok(delegation::get_delegations(env, delegator))
+    }
+
+    /// Retrieve the delegations a member set for one topic with their shares
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `delegator` - The address to check delegations for
+    /// * `topic` - The topic to check
+    ///
+    /// # Returns
+    /// * `Result<Vec<Delegation>, DelegationError>` - The topic delegations, empty if the topic
+    ///   follows the default
+    pub fn get_topic_delegations(
+        env: Env,
+        delegator: Address,
+        topic: Topic,
+    ) -> Result<Vec<Delegation>, DelegationError> {
+        Ok(delegation::get_topic_delegations(env, delegator, topic))
+    }
+
+    /// Return all delegations received by a member with their shares
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `delegate` - The address to get delegations for
+    ///
+    /// # Returns
+    /// * `Result<Vec<Delegation>, DelegationError>` - The delegations received by the member
+    pub fn get_received_delegations(
+        env: Env,
+        delegate: Address,
+    ) -> Result<Vec<Delegation>, DelegationError> {
+        Ok(delegation::get_received_delegations(env, delegate))
     }
 
     /// Return all addresses delegating to a specific member
@@ -130,9 +899,9 @@ impl DelegationContract {
     /// * `delegate` - The address to get delegators for
     ///
     /// # Returns
-    /// * `Vec<Address>` - Vector of addresses delegating to the member
-    pub fn get_delegators(env: Env, delegate: Address) -> Vec<Address> {
-        delegation::get_delegators(env, delegate)
+    /// * `Result<Vec<Address>, DelegationError>` - Vector of addresses delegating to the member
+    pub fn get_delegators(env: Env, delegate: Address) -> Result<Vec<Address>, DelegationError> {
+        Ok(delegation::get_delegators(env, delegate))
     }
 
     /// Get detailed voting power information for a member
@@ -142,12 +911,15 @@ impl DelegationContract {
     /// * `member` - The address to get detailed power info for
     ///
     /// # Returns
-    /// * `VotingPower` - Detailed voting power information
-    pub fn get_voting_power_details(env: Env, member: Address) -> VotingPower {
-        VotingPower {
+    /// * `Result<VotingPower, DelegationError>` - Detailed voting power information
+    pub fn get_voting_power_details(
+        env: Env,
+        member: Address,
+    ) -> Result<VotingPower, DelegationError> {
+        Ok(VotingPower {
             member: member.clone(),
             total_power: power::get_voting_power(env, member),
-        }
+        })
     }
 
     /// Check if an address has any delegation (either as delegator or delegate)
@@ -157,10 +929,28 @@ impl DelegationContract {
     /// * `address` - The address to check
     ///
     /// # Returns
-    /// * `bool` - True if the address has delegation relationships
-    pub fn has_delegation(env: Env, address: Address) -> bool {
-        delegation::has_delegation(env, address)
+    /// * `Result<bool, DelegationError>` - True if the address has delegation relationships
+    pub fn has_delegation(env: Env, address: Address) -> Result<bool, DelegationError> {
+        Ok(delegation::has_delegation(env, address))
     }
 }
 
+/// Helper function to require the admin's authorization
+///
+/// # Arguments
+/// * `env` - The contract environment
+///
+/// # Returns
+/// * `Result<Address, DelegationError>` - The admin, or `AdminNotSet`
+fn require_admin(env: &Env) -> Result<Address, DelegationError> {
+    let admin: Address = env
+        .storage()
+        .persistent()
+        .get(&ADMIN)
+        .ok_or(DelegationError::AdminNotSet)?;
+
+    admin.require_auth();
+    Ok(admin)
+}
+
 pub mod test;
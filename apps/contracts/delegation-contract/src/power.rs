@@ -1,58 +1,370 @@
-use crate::{MEMBER_POWER, delegation};
+use crate::{
+    BPS_DENOMINATOR, DEFAULT_POWER, DELEGATED_POWER, Delegation, DelegationError, MEMBER_POWER,
+    PowerSource, SYNCED_POWER, TOPIC_POWER, Topic, checkpoints,
+    delegation::{self, Edges},
+    events, registry, source,
+};
 use soroban_sdk::{Address, Env, Symbol, Vec};
 
-/// Compute the total effective voting power of a member (own + delegated)
+/// Get the total effective voting power of a member (own + delegated - delegated away)
 ///
-/// This function calculates voting power by:
-/// 1. Getting the member's base voting power
-/// 2. Adding delegated power from all delegators
-/// 3. Handling delegation chains appropriately
+/// Adds the cached delegated power, kept up to date by `adjust_power`, to the synced base power
+/// and subtracts the shares the member delegates. Expired delegations no longer count, even before
+/// they are cleared, while changes in the power source count once the member is synced.
 ///
 /// # Arguments
 /// * `env` - The contract environment
-/// * `member` - The address to compute voting power for
+/// * `member` - The address to get voting power for
 ///
 /// # Returns
 /// * `i128` - The total effective voting power
 pub fn get_voting_power(env: Env, member: Address) -> i128 {
-    // Get base voting power for the member
-    let base_power = get_base_power(env.clone(), member.clone());
+    get_scoped_power(&env, &member, &None)
+}
 
-    // Get all delegators for this member
-    let delegators = delegation::get_delegators(env.clone(), member.clone());
+/// Get the voting power a member receives from its delegators, directly or transitively
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `member` - The address to get delegated power for
+///
+/// # Returns
+/// * `i128` - The delegated voting power (defaults to 0)
+pub fn get_delegated_power(env: Env, member: Address) -> i128 {
+    get_active_delegated_power(&env, &member, &None)
+}
+
+/// Get the total effective voting power of a member on proposals of one topic
+///
+/// Uses the member's topic delegations, or the default delegation for delegators that did not
+/// delegate the topic.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `member` - The address to get voting power for
+/// * `topic` - The topic of the proposal
+///
+/// # Returns
+/// * `i128` - The total effective voting power for the topic
+pub fn get_voting_power_for_topic(env: Env, member: Address, topic: Topic) -> i128 {
+    get_scoped_power(&env, &member, &Some(topic))
+}
+
+/// Get the total effective voting power of a member in a scope
+///
+/// The member keeps the part of its held power that its active delegations do not carry, so
+/// every unit of power counts once across delegators and delegates.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `member` - The address to get voting power for
+/// * `scope` - The topic, or `None` for the default delegation
+///
+/// # Returns
+/// * `i128` - The total effective voting power
+pub fn get_scoped_power(env: &Env, member: &Address, scope: &Option<Topic>) -> i128 {
+    let delegations = delegation::effective_delegations(env, member, scope, &Edges::Active);
+    kept_power(held_power(env, member, scope), &delegations)
+}
+
+/// Helper function to get the power a member holds in a scope before its own delegations
+///
+/// Adds the cached delegated power to the synced base power, unless a delegation reaching the
+/// member has expired since the cache was last updated, in which case the power is summed over
+/// the delegations that are still active. Delegators and delegates both count the synced base
+/// power, so a change in the power source moves no power until the member is synced.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `member` - The member address
+/// * `scope` - The topic, or `None` for the default delegation
+///
+/// # Returns
+/// * `i128` - The base power plus the power delegated to the member
+fn held_power(env: &Env, member: &Address, scope: &Option<Topic>) -> i128 {
+    let mut power = synced_base_power(env, member);
+    if delegation::get_min_expiry(env, member) > env.ledger().timestamp() {
+        return power + get_scoped_delegated_power(env, member, scope);
+    }
+
+    for received in delegation::effective_received(env, member, scope, &Edges::Active).iter() {
+        power += share_of(
+            held_power(env, &received.delegator, scope),
+            received.share_bps,
+        );
+    }
+
+    power
+}
+
+/// Get the voting power a member receives in a scope through delegations that have not expired
+///
+/// Unlike `get_scoped_delegated_power`, power from expired delegations that were not cleared yet
+/// no longer counts.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `member` - The address to get delegated power for
+/// * `scope` - The topic, or `None` for the default delegation
+///
+/// # Returns
+/// * `i128` - The delegated voting power (defaults to 0)
+pub fn get_active_delegated_power(env: &Env, member: &Address, scope: &Option<Topic>) -> i128 {
+    held_power(env, member, scope) - synced_base_power(env, member)
+}
+
+/// Get the earliest expiry among the delegations counted in a member's cached default power,
+/// with the power the member keeps once no expiring delegation counts
+///
+/// The member's own expiring delegations keep their shares, so the remaining power never counts
+/// a unit that may also be counted by a delegate.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `member` - The member address
+/// * `delegations` - The member's stored default delegations
+///
+/// # Returns
+/// * `Option<(u64, i128)>` - The expiry and the remaining kept power, or `None` if no delegation
+///   reaching the member expires
+pub fn expiring_power(
+    env: &Env,
+    member: &Address,
+    delegations: &Vec<Delegation>,
+) -> Option<(u64, i128)> {
+    let expires_at = delegation::get_min_expiry(env, member);
+    if expires_at == u64::MAX {
+        return None;
+    }
+
+    Some((
+        expires_at,
+        kept_power(lasting_power(env, member), delegations),
+    ))
+}
+
+/// Helper function to sum the cached default power a member holds through delegations without
+/// an expiry, before its own delegations
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `member` - The member address
+///
+/// # Returns
+/// * `i128` - The base power plus the power delegated without an expiry
+fn lasting_power(env: &Env, member: &Address) -> i128 {
+    let mut power = counted_base_power(env, member);
+
+    for received in delegation::effective_received(env, member, &None, &Edges::Stored).iter() {
+        if received.expires_at.is_none() {
+            power += share_of(lasting_power(env, &received.delegator), received.share_bps);
+        }
+    }
+
+    power
+}
+
+/// Get the cached power a member holds in a scope, before its own delegations
+///
+/// This is the power its delegations split between its delegates. Matches the stored
+/// delegations, including expired ones that were not cleared yet, and the base power as of the
+/// member's last sync.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `member` - The address to get voting power for
+/// * `scope` - The topic, or `None` for the default delegation
+///
+/// # Returns
+/// * `i128` - The cached held power
+pub fn cached_power(env: &Env, member: &Address, scope: &Option<Topic>) -> i128 {
+    counted_base_power(env, member) + get_scoped_delegated_power(env, member, scope)
+}
+
+/// Compute the power a member holds (own + delegated) from scratch, before its own delegations
+/// take their shares
+///
+/// This function calculates the held power by:
+/// 1. Getting the member's base voting power
+/// 2. Adding each delegator's share of their held power
+/// 3. Handling delegation chains appropriately
+///
+/// Its cost grows with the number of transitive delegators; it is only used to verify the cache,
+/// so it follows the stored delegations like the cache does.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `member` - The address to compute held power for
+/// * `scope` - The topic, or `None` for the default delegation
+///
+/// # Returns
+/// * `i128` - The base power plus the power delegated to the member
+pub fn compute_held_power(env: &Env, member: &Address, scope: &Option<Topic>) -> i128 {
+    // Get base voting power for the member, as counted by the cache
+    let base_power = counted_base_power(env, member);
+
+    // Get all delegations received by this member
+    let delegations = delegation::effective_received(env, member, scope, &Edges::Stored);
 
     // Calculate total delegated power
     let mut delegated_power = 0i128;
 
-    for delegator in delegators.iter() {
+    for delegation in delegations.iter() {
         // Get the effective power of each delegator
         // This handles nested delegation chains
-        let delegator_power = get_voting_power(env.clone(), delegator);
-        delegated_power += delegator_power;
+        let delegator_power = compute_held_power(env, &delegation.delegator, scope);
+        delegated_power += share_of(delegator_power, delegation.share_bps);
     }
 
     base_power + delegated_power
 }
 
-/// Get the effective power that a delegator contributes
+/// Get the part of `power` carried by a delegation share, rounded down
+///
+/// # Arguments
+/// * `power` - The delegator's effective voting power
+/// * `share_bps` - The delegated share in basis points
+///
+/// # Returns
+/// * `i128` - The delegated power
+pub fn share_of(power: i128, share_bps: u32) -> i128 {
+    if share_bps == BPS_DENOMINATOR {
+        return power;
+    }
+
+    // Split the multiplication to avoid overflowing on large powers
+    let (share, denominator) = (share_bps as i128, BPS_DENOMINATOR as i128);
+    power / denominator * share + power % denominator * share / denominator
+}
+
+/// Get the part of `power` that a member keeps after its delegations take their shares
+///
+/// # Arguments
+/// * `power` - The power the member holds
+/// * `delegations` - The member's delegations
+///
+/// # Returns
+/// * `i128` - The power left to the member
+pub fn kept_power(power: i128, delegations: &Vec<Delegation>) -> i128 {
+    let mut kept = power;
+    for delegation in delegations.iter() {
+        kept -= share_of(power, delegation.share_bps);
+    }
+
+    kept
+}
+
+/// Add `delta` to the cached delegated power of a delegate and propagate the change to the
+/// delegates down its delegation chains
+///
+/// Moves registered delegates to their new place in the delegate ranking.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegate` - The address whose delegators' power changed
+/// * `scope` - The topic, or `None` for the default delegation
+/// * `delta` - The change in delegated power
+pub fn adjust_power(env: &Env, delegate: &Address, scope: &Option<Topic>, delta: i128) {
+    if delta == 0 {
+        return;
+    }
+
+    let previous = cached_power(env, delegate, scope);
+    let power = get_scoped_delegated_power(env, delegate, scope) + delta;
+    let storage = env.storage().persistent();
+
+    match (scope, power) {
+        (None, 0) => storage.remove(&delegated_power_key(delegate)),
+        (None, _) => storage.set(&delegated_power_key(delegate), &power),
+        (Some(topic), 0) => storage.remove(&topic_power_key(delegate, topic)),
+        (Some(topic), _) => storage.set(&topic_power_key(delegate, topic), &power),
+    }
+    if scope.is_none() {
+        registry::update_rank(env, delegate);
+    }
+
+    propagate(env, delegate, scope, previous, previous + delta);
+}
+
+/// Helper function to record a change in a member's held power and pass each delegate the
+/// resulting change in its share
 ///
-/// This function calculates the power that a delegator contributes when they delegate.
-/// It handles the case where a delegator might also have received delegated power.
+/// Checkpoints record the power the member keeps, and only for the default power.
 ///
 /// # Arguments
 /// * `env` - The contract environment
-/// * `delegator` - The address of the delegator
+/// * `member` - The address whose effective power changed
+/// * `scope` - The topic, or `None` for the default delegation
+/// * `previous` - The held power before the change
+/// * `power` - The held power after the change
+fn propagate(env: &Env, member: &Address, scope: &Option<Topic>, previous: i128, power: i128) {
+    let delegations = delegation::effective_delegations(env, member, scope, &Edges::Stored);
+    if scope.is_none() {
+        checkpoints::record(
+            env,
+            member,
+            kept_power(previous, &delegations),
+            kept_power(power, &delegations),
+            expiring_power(env, member, &delegations),
+        );
+    }
+
+    for delegation in delegations.iter() {
+        let delta =
+            share_of(power, delegation.share_bps) - share_of(previous, delegation.share_bps);
+        adjust_power(env, &delegation.delegate, scope, delta);
+    }
+}
+
+/// Find the members whose cached held power, by default or for any topic, differs from a
+/// full recompute
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `members` - The addresses to check
 ///
 /// # Returns
-/// * `i128` - The effective power contributed by the delegator
+/// * `Vec<Address>` - The members with an inconsistent cache
+pub fn find_inconsistent(env: Env, members: Vec<Address>) -> Vec<Address> {
+    let mut inconsistent = Vec::new(&env);
+
+    for member in members.iter() {
+        let mut consistent = true;
+
+        for scope in delegation::scopes(&env).iter() {
+            consistent &=
+                cached_power(&env, &member, &scope) == compute_held_power(&env, &member, &scope);
+        }
+
+        if !consistent {
+            inconsistent.push_back(member);
+        }
+    }
+
+    inconsistent
+}
 
 /// Set the base voting power for a member
 ///
+/// Only available with the manual power source. Propagates the change to the cached delegated
+/// power of the member's delegates, by default and for every topic, and records checkpoints
+/// for the member and the total power. Zero or negative power reverts the member to the
+/// default base power.
+///
 /// # Arguments
 /// * `env` - The contract environment
 /// * `member` - The address to set base power for
 /// * `power` - The base voting power amount
-pub fn set_base_power(env: Env, member: Address, power: i128) {
+///
+/// # Returns
+/// * `Result<(), DelegationError>` - Success, or `ManualPowerDisabled`
+pub fn set_base_power(env: Env, member: Address, power: i128) -> Result<(), DelegationError> {
+    if source::get_source(&env) != PowerSource::Manual {
+        return Err(DelegationError::ManualPowerDisabled);
+    }
+
+    let previous = counted_base_power(&env, &member);
+    let previous_total = total_base_power(&env, &member);
     if power <= 0 {
         // Remove entry for zero or negative power
         env.storage().persistent().remove(&base_power_key(&member));
@@ -61,21 +373,155 @@ pub fn set_base_power(env: Env, member: Address, power: i128) {
             .persistent()
             .set(&base_power_key(&member), &power);
     }
+    env.storage().persistent().set(
+        &synced_power_key(&member),
+        &manual_base_power(&env, &member),
+    );
+
+    apply_base_change(&env, &member, previous, previous_total);
+    Ok(())
+}
+
+/// Read the base voting power of a member from the power source and update the power counted
+/// for its delegates and in the total power
+///
+/// Delegations sync their delegator and registering syncs the delegate, so every member taking
+/// part in delegation counts towards the total; anyone may sync other members whose source
+/// changed.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `member` - The address to sync
+///
+/// # Returns
+/// * `i128` - The synced base voting power
+pub fn sync_base_power(env: Env, member: Address) -> i128 {
+    let previous = counted_base_power(&env, &member);
+    let previous_total = total_base_power(&env, &member);
+
+    let power = get_base_power(env.clone(), member.clone());
+    env.storage()
+        .persistent()
+        .set(&synced_power_key(&member), &power);
+
+    apply_base_change(&env, &member, previous, previous_total);
+    power
 }
 
 /// Get the base voting power for a member
 ///
+/// Reads the configured power source; non-members have no power with the member registry or
+/// Voting NFT sources.
+///
 /// # Arguments
 /// * `env` - The contract environment
 /// * `member` - The address to get base power for
 ///
 /// # Returns
-/// * `i128` - The base voting power (defaults to 1 if not set)
+/// * `i128` - The base voting power (defaults to the contract's default base power if not
+///   set manually)
 pub fn get_base_power(env: Env, member: Address) -> i128 {
+    source::read_base_power(&env, &member).unwrap_or_else(|| manual_base_power(&env, &member))
+}
+
+/// Helper function to get the base voting power set by the admin
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `member` - The member address
+///
+/// # Returns
+/// * `i128` - The manual base power (defaults to the contract's default base power)
+fn manual_base_power(env: &Env, member: &Address) -> i128 {
+    let storage = env.storage().persistent();
+
+    storage
+        .get(&base_power_key(member))
+        .or_else(|| storage.get(&DEFAULT_POWER))
+        .unwrap_or(1i128) // Default base power is 1
+}
+
+/// Helper function to get the base voting power counted in the cached power of the member's
+/// delegates: the last synced value, or the default base power if never synced
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `member` - The member address
+///
+/// # Returns
+/// * `i128` - The counted base power
+fn counted_base_power(env: &Env, member: &Address) -> i128 {
     env.storage()
         .persistent()
-        .get(&base_power_key(&member))
-        .unwrap_or(1i128) // Default base power is 1
+        .get(&synced_power_key(member))
+        .unwrap_or_else(|| manual_base_power(env, member))
+}
+
+/// Helper function to get the base voting power a member holds: the last synced value, or the
+/// power source's value for members never synced, whose power no delegate counts yet
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `member` - The member address
+///
+/// # Returns
+/// * `i128` - The synced base power
+fn synced_base_power(env: &Env, member: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&synced_power_key(member))
+        .unwrap_or_else(|| get_base_power(env.clone(), member.clone()))
+}
+
+/// Check whether a member's base voting power was ever synced from the power source
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `member` - The member address
+///
+/// # Returns
+/// * `bool` - True if the member was synced
+pub fn is_synced(env: &Env, member: &Address) -> bool {
+    env.storage().persistent().has(&synced_power_key(member))
+}
+
+/// Helper function to get the base voting power that counts towards the total: the last synced
+/// value
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `member` - The member address
+///
+/// # Returns
+/// * `i128` - The base power in the total (0 for members never synced)
+fn total_base_power(env: &Env, member: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&synced_power_key(member))
+        .unwrap_or(0)
+}
+
+/// Helper function to record a change in the base power of a member and propagate it
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `member` - The member address
+/// * `previous` - The counted base power before the change
+/// * `previous_total` - The base power in the total before the change
+fn apply_base_change(env: &Env, member: &Address, previous: i128, previous_total: i128) {
+    checkpoints::record_total(env, total_base_power(env, member) - previous_total);
+
+    let power = counted_base_power(env, member);
+    let delta = power - previous;
+    if delta == 0 {
+        return;
+    }
+    events::base_power_set(env, member, power);
+
+    for scope in delegation::scopes(env).iter() {
+        let effective = cached_power(env, member, &scope);
+        propagate(env, member, &scope, effective - delta, effective);
+    }
 }
 
 /// Generate storage key for base power mapping
@@ -87,4 +533,59 @@ pub fn get_base_power(env: Env, member: Address) -> i128 {
 /// * Tuple for storage key
 fn base_power_key(member: &Address) -> (Symbol, Address) {
     (MEMBER_POWER, member.clone())
-}
\ No newline at end of file
+}
+
+/// Generate storage key for synced base power mapping
+///
+/// # Arguments
+/// * `member` - The member address
+///
+/// # Returns
+/// * Tuple for storage key
+fn synced_power_key(member: &Address) -> (Symbol, Address) {
+    (SYNCED_POWER, member.clone())
+}
+
+/// Generate storage key for cached delegated power mapping
+///
+/// # Arguments
+/// * `member` - The member address
+///
+/// # Returns
+/// * Tuple for storage key
+fn delegated_power_key(member: &Address) -> (Symbol, Address) {
+    (DELEGATED_POWER, member.clone())
+}
+
+/// Generate storage key for cached topic delegated power mapping
+///
+/// # Arguments
+/// * `member` - The member address
+/// * `topic` - The topic
+///
+/// # Returns
+/// * Tuple for storage key
+fn topic_power_key(member: &Address, topic: &Topic) -> (Symbol, Address, Topic) {
+    (TOPIC_POWER, member.clone(), topic.clone())
+}
+
+/// Get the cached delegated power of a member in a scope
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `member` - The member address
+/// * `scope` - The topic, or `None` for the default delegation
+///
+/// # Returns
+/// * `i128` - The delegated voting power (defaults to 0)
+pub fn get_scoped_delegated_power(env: &Env, member: &Address, scope: &Option<Topic>) -> i128 {
+    let power = match scope {
+        None => env.storage().persistent().get(&delegated_power_key(member)),
+        Some(topic) => env
+            .storage()
+            .persistent()
+            .get(&topic_power_key(member, topic)),
+    };
+
+    power.unwrap_or(0)
+}
@@ -0,0 +1,277 @@
+use crate::{
+    DELEGATE_POSITIONS, DELEGATE_PROFILES, DELEGATE_RANKS, DelegateProfile, DelegateSummary,
+    DelegationError, MAX_PAGE_SIZE, REGISTERED_DELEGATES, Topic, events, power,
+};
+use soroban_sdk::{Address, BytesN, Env, Symbol, Vec};
+
+/// Register a member as a delegate, or update their profile
+///
+/// New delegates join the ranking behind every delegate with at least as much received power.
+/// Syncs the delegate's base power, so it counts towards the total power.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegate` - The address registering as a delegate
+/// * `statement_hash` - Hash of the delegate's statement, published off-chain
+/// * `interests` - The topics the delegate focuses on
+/// * `accepting` - Whether the delegate accepts new delegations
+pub fn register(
+    env: Env,
+    delegate: Address,
+    statement_hash: BytesN<32>,
+    interests: Vec<Topic>,
+    accepting: bool,
+) {
+    let registered = get_profile(env.clone(), delegate.clone()).is_some();
+
+    let profile = DelegateProfile {
+        delegate: delegate.clone(),
+        statement_hash,
+        interests,
+        accepting,
+    };
+    env.storage()
+        .persistent()
+        .set(&profile_key(&delegate), &profile);
+    events::delegate_registered(&env, &profile);
+
+    if !registered {
+        let count = get_count(&env);
+        env.storage()
+            .persistent()
+            .set(&REGISTERED_DELEGATES, &(count + 1));
+        set_rank(&env, count, &delegate, count);
+        update_rank(&env, &delegate);
+    }
+    power::sync_base_power(env, delegate);
+}
+
+/// Open or close a registered delegate to new delegations
+///
+/// Existing delegations are kept; see `delegation::remove_delegator` to drop them.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegate` - The registered delegate
+/// * `accepting` - Whether the delegate accepts new delegations
+///
+/// # Returns
+/// * `Result<(), DelegationError>` - Success, or `NotRegistered`
+pub fn set_accepting(env: Env, delegate: Address, accepting: bool) -> Result<(), DelegationError> {
+    let mut profile =
+        get_profile(env.clone(), delegate.clone()).ok_or(DelegationError::NotRegistered)?;
+
+    profile.accepting = accepting;
+    env.storage()
+        .persistent()
+        .set(&profile_key(&delegate), &profile);
+    events::delegate_registered(&env, &profile);
+    Ok(())
+}
+
+/// Get the profile of a registered delegate
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegate` - The address to look up
+///
+/// # Returns
+/// * `Option<DelegateProfile>` - The profile, if the address is registered
+pub fn get_profile(env: Env, delegate: Address) -> Option<DelegateProfile> {
+    env.storage().persistent().get(&profile_key(&delegate))
+}
+
+/// Check whether an address is a registered delegate accepting new delegations
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegate` - The address to check
+///
+/// # Returns
+/// * `bool` - True if new delegations to the address are allowed
+pub fn is_accepting(env: &Env, delegate: &Address) -> bool {
+    get_profile(env.clone(), delegate.clone()).is_some_and(|profile| profile.accepting)
+}
+
+/// List registered delegates by received voting power, highest first
+///
+/// Delegates with equal power keep their registration order. Reads only the requested page of
+/// the ranking maintained by `update_rank`, which follows the cached power; the reported power
+/// leaves out expired delegations that were not cleared yet.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `start` - The index of the first delegate to return
+/// * `limit` - The most delegates to return, capped at `MAX_PAGE_SIZE`
+///
+/// # Returns
+/// * `Vec<DelegateSummary>` - The delegates on the page with their received power
+pub fn list_delegates(env: Env, start: u32, limit: u32) -> Vec<DelegateSummary> {
+    let end = start
+        .saturating_add(limit.min(MAX_PAGE_SIZE))
+        .min(get_count(&env));
+    let mut page = Vec::new(&env);
+
+    for position in start..end {
+        let (delegate, _) = delegate_at(&env, position);
+        page.push_back(DelegateSummary {
+            received_power: power::get_delegated_power(env.clone(), delegate.clone()),
+            profile: get_profile(env.clone(), delegate).unwrap(),
+        });
+    }
+
+    page
+}
+
+/// Move a registered delegate to its place in the ranking after its received power changed
+///
+/// The delegate swaps places with its neighbours until the ranking is ordered again, so the cost
+/// grows with the number of delegates it passes rather than with the size of the registry.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegate` - The address whose received power changed
+pub fn update_rank(env: &Env, delegate: &Address) {
+    let Some((mut position, registration)) = get_position(env, delegate) else {
+        return;
+    };
+    let rank = (
+        power::get_scoped_delegated_power(env, delegate, &None),
+        registration,
+    );
+    let start = position;
+
+    while position > 0 {
+        let (previous, previous_rank) = ranked_at(env, position - 1);
+        if !ranks_before(rank, previous_rank) {
+            break;
+        }
+        set_rank(env, position, &previous, previous_rank.1);
+        position -= 1;
+    }
+
+    let moved_up = position != start;
+    let count = get_count(env);
+    while !moved_up && position + 1 < count {
+        let (next, next_rank) = ranked_at(env, position + 1);
+        if !ranks_before(next_rank, rank) {
+            break;
+        }
+        set_rank(env, position, &next, next_rank.1);
+        position += 1;
+    }
+
+    if position != start {
+        set_rank(env, position, delegate, registration);
+    }
+}
+
+/// Helper function to get the number of registered delegates
+///
+/// # Arguments
+/// * `env` - The contract environment
+///
+/// # Returns
+/// * `u32` - The number of registered delegates
+fn get_count(env: &Env) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&REGISTERED_DELEGATES)
+        .unwrap_or(0)
+}
+
+/// Helper function to get the delegate at a position of the ranking
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `position` - The position, which must be below the number of registered delegates
+///
+/// # Returns
+/// * `(Address, u32)` - The delegate and its registration number
+fn delegate_at(env: &Env, position: u32) -> (Address, u32) {
+    env.storage().persistent().get(&rank_key(position)).unwrap()
+}
+
+/// Helper function to get the delegate at a position of the ranking with what it is ranked by
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `position` - The position, which must be below the number of registered delegates
+///
+/// # Returns
+/// * `(Address, (i128, u32))` - The delegate with its received power and registration number
+fn ranked_at(env: &Env, position: u32) -> (Address, (i128, u32)) {
+    let (delegate, registration) = delegate_at(env, position);
+    let power = power::get_scoped_delegated_power(env, &delegate, &None);
+    (delegate, (power, registration))
+}
+
+/// Helper function to get the position of a delegate in the ranking
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `delegate` - The delegate address
+///
+/// # Returns
+/// * `Option<(u32, u32)>` - The position and registration number, if the address is registered
+fn get_position(env: &Env, delegate: &Address) -> Option<(u32, u32)> {
+    env.storage().persistent().get(&position_key(delegate))
+}
+
+/// Helper function to place a delegate at a position of the ranking
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `position` - The position
+/// * `delegate` - The delegate address
+/// * `registration` - The delegate's registration number
+fn set_rank(env: &Env, position: u32, delegate: &Address, registration: u32) {
+    let storage = env.storage().persistent();
+    storage.set(&rank_key(position), &(delegate.clone(), registration));
+    storage.set(&position_key(delegate), &(position, registration));
+}
+
+/// Helper function to order delegates by received power, highest first, then by registration
+///
+/// # Arguments
+/// * `rank` - The received power and registration number of one delegate
+/// * `other` - The received power and registration number of another delegate
+///
+/// # Returns
+/// * `bool` - True if the first delegate is listed before the other
+fn ranks_before(rank: (i128, u32), other: (i128, u32)) -> bool {
+    rank.0 > other.0 || (rank.0 == other.0 && rank.1 < other.1)
+}
+
+/// Generate storage key for delegate profile mapping
+///
+/// # Arguments
+/// * `delegate` - The delegate address
+///
+/// # Returns
+/// * Tuple for storage key
+fn profile_key(delegate: &Address) -> (Symbol, Address) {
+    (DELEGATE_PROFILES, delegate.clone())
+}
+
+/// Generate storage key for delegate ranking mapping
+///
+/// # Arguments
+/// * `position` - The position in the ranking
+///
+/// # Returns
+/// * Tuple for storage key
+fn rank_key(position: u32) -> (Symbol, u32) {
+    (DELEGATE_RANKS, position)
+}
+
+/// Generate storage key for delegate ranking position mapping
+///
+/// # Arguments
+/// * `delegate` - The delegate address
+///
+/// # Returns
+/// * Tuple for storage key
+fn position_key(delegate: &Address) -> (Symbol, Address) {
+    (DELEGATE_POSITIONS, delegate.clone())
+}
@@ -0,0 +1,179 @@
+use crate::{DelegationError, NftPowerConfig, POWER_SOURCE, PowerSource, VotingNFT, events};
+use soroban_sdk::{Address, Env, IntoVal, InvokeError, Symbol, Val, Vec, vec};
+
+/// Get where base voting power comes from
+///
+/// # Arguments
+/// * `env` - The contract environment
+///
+/// # Returns
+/// * `PowerSource` - The configured source (defaults to `Manual`)
+pub fn get_source(env: &Env) -> PowerSource {
+    env.storage()
+        .persistent()
+        .get(&POWER_SOURCE)
+        .unwrap_or(PowerSource::Manual)
+}
+
+/// Set where base voting power comes from
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `source` - The new source
+///
+/// # Returns
+/// * `Result<(), DelegationError>` - Success, or `InvalidPowerSource` for negative NFT weights
+pub fn set_source(env: &Env, source: &PowerSource) -> Result<(), DelegationError> {
+    if let PowerSource::VotingNft(config) = source
+        && (config.default_weight < 0 || config.weights.iter().any(|weight| weight.weight < 0))
+    {
+        return Err(DelegationError::InvalidPowerSource);
+    }
+
+    env.storage().persistent().set(&POWER_SOURCE, source);
+    events::power_source_set(env, source);
+    Ok(())
+}
+
+/// Read the base voting power of a member from the configured external source
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `member` - The address to read base power for
+///
+/// # Returns
+/// * `Option<i128>` - The base power, 0 for non-members, or `None` in manual mode
+pub fn read_base_power(env: &Env, member: &Address) -> Option<i128> {
+    match get_source(env) {
+        PowerSource::Manual => None,
+        PowerSource::MemberRegistry(registry) => Some(registry_power(env, &registry, member)),
+        PowerSource::VotingNft(config) => Some(nft_power(env, &config, member)),
+    }
+}
+
+/// Read the total base voting power of all members from the configured external source
+///
+/// # Arguments
+/// * `env` - The contract environment
+///
+/// # Returns
+/// * `Option<i128>` - The total power, or `None` in manual mode or if the source cannot report it
+pub fn read_total_power(env: &Env) -> Option<i128> {
+    match get_source(env) {
+        PowerSource::Manual => None,
+        PowerSource::MemberRegistry(registry) => {
+            read_count(env, &registry, "get_total_voting_power", vec![env]).map(i128::from)
+        }
+        PowerSource::VotingNft(config) => nft_total_power(env, &config),
+    }
+}
+
+/// Helper function to read a member's voting power from the member registry
+///
+/// The registry fails for unknown addresses, which count as non-members.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `registry` - The member registry contract
+/// * `member` - The address to look up
+///
+/// # Returns
+/// * `i128` - The voting power, 0 for non-members
+fn registry_power(env: &Env, registry: &Address, member: &Address) -> i128 {
+    let args: Vec<Val> = vec![env, member.into_val(env)];
+    read_count(env, registry, "get_voting_power", args).map_or(0, i128::from)
+}
+
+/// Helper function to weigh the Voting NFTs held by a member
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `config` - The NFT contract and category weights
+/// * `member` - The address to look up
+///
+/// # Returns
+/// * `i128` - The sum of the weights of the member's NFTs
+fn nft_power(env: &Env, config: &NftPowerConfig, member: &Address) -> i128 {
+    let args: Vec<Val> = vec![env, member.into_val(env)];
+    let nfts = match env.try_invoke_contract::<Vec<VotingNFT>, InvokeError>(
+        &config.contract,
+        &Symbol::new(env, "get_nfts_by_owner"),
+        args,
+    ) {
+        Ok(Ok(nfts)) => nfts,
+        _ => return 0,
+    };
+
+    let mut power = 0i128;
+    for nft in nfts.iter() {
+        power += config
+            .weights
+            .iter()
+            .find(|weight| weight.category == nft.category)
+            .map_or(config.default_weight, |weight| weight.weight);
+    }
+
+    power
+}
+
+/// Helper function to weigh every Voting NFT minted, by category
+///
+/// Categories without a configured weight count with the default weight.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `config` - The NFT contract and category weights
+///
+/// # Returns
+/// * `Option<i128>` - The sum of the weights of all NFTs, or `None` if the contract cannot
+///   report its supply
+fn nft_total_power(env: &Env, config: &NftPowerConfig) -> Option<i128> {
+    let mut unweighted = i128::from(read_count(
+        env,
+        &config.contract,
+        "get_total_supply",
+        vec![env],
+    )?);
+    let mut power = 0i128;
+
+    for (index, weight) in config.weights.iter().enumerate() {
+        // Only the first weight of a category applies, as in `nft_power`
+        if config
+            .weights
+            .iter()
+            .take(index)
+            .any(|earlier| earlier.category == weight.category)
+        {
+            continue;
+        }
+
+        let args: Vec<Val> = vec![env, weight.category.into_val(env)];
+        let supply = i128::from(read_count(
+            env,
+            &config.contract,
+            "get_category_supply",
+            args,
+        )?);
+        power += supply * weight.weight;
+        unweighted -= supply;
+    }
+
+    Some(power + unweighted * config.default_weight)
+}
+
+/// Helper function to read a count from a source contract
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `contract` - The source contract
+/// * `function` - The function to call
+/// * `args` - The call arguments
+///
+/// # Returns
+/// * `Option<u32>` - The count, or `None` if the call fails
+fn read_count(env: &Env, contract: &Address, function: &str, args: Vec<Val>) -> Option<u32> {
+    match env.try_invoke_contract::<u32, InvokeError>(contract, &Symbol::new(env, function), args) {
+        Ok(Ok(count)) => Some(count),
+        _ => None,
+    }
+}
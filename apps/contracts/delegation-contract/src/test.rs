@@ -4,8 +4,16 @@
 
 #![cfg(test)]
 
-use crate::{DelegationContract, DelegationContractClient};
-use soroban_sdk::{Address, Env, Vec, testutils::Address as _};
+use crate::{
+    BPS_DENOMINATOR, CapPolicy, CategoryWeight, DEFAULT_MAX_CHAIN_DEPTH, DELEGATED_POWER,
+    Delegation, DelegationContract, DelegationContractClient, DelegationError, DelegationScope,
+    DelegationShare, MAX_CHECKPOINTS, MAX_DELEGATES, NftPowerConfig, PowerCap, PowerCapConfig,
+    PowerSource, Topic, VotingNFT,
+};
+use soroban_sdk::{
+    Address, BytesN, Env, IntoVal, Symbol, Vec, contract, contractimpl, symbol_short,
+    testutils::{Address as _, Events, Ledger},
+};
 
 fn create_test_env<'a>() -> (
     Env,
@@ -28,9 +36,18 @@ fn create_test_env<'a>() -> (
 
     env.mock_all_auths();
 
+    // Every member accepts delegations
+    for member in [&member_1, &member_2, &member_3, &member_4] {
+        client.register_delegate(member, &statement(&env), &Vec::new(&env), &true);
+    }
+
     (env, client, admin, member_1, member_2, member_3, member_4)
 }
 
+fn statement(env: &Env) -> BytesN<32> {
+    BytesN::from_array(env, &[7; 32])
+}
+
 #[test]
 fn test_basic_delegation() {
     let (_env, client, _admin, member_1, member_2, _member_3, _member_4) = create_test_env();
@@ -46,8 +63,8 @@ fn test_basic_delegation() {
     // member_1 delegates to member_2
     client.delegate_votes(&member_1, &member_2);
 
-    // member_1's power should remain 10, member_2's should be 5 + 10 = 15
-    assert_eq!(client.get_voting_power(&member_1), 10);
+    // member_1's power moves to member_2: 5 + 10 = 15, leaving member_1 with 0
+    assert_eq!(client.get_voting_power(&member_1), 0);
     assert_eq!(client.get_voting_power(&member_2), 15);
 
     // Check delegation relationship
@@ -160,7 +177,7 @@ fn test_zero_base_power() {
     client.delegate_votes(&member_1, &member_2);
 
     // member_2 should have: 10 (base) + 1 (member_1's default) = 11
-    assert_eq!(client.get_voting_power(&member_1), 1);
+    assert_eq!(client.get_voting_power(&member_1), 0);
     assert_eq!(client.get_voting_power(&member_2), 11);
 }
 
@@ -169,21 +186,21 @@ fn test_has_delegation_check() {
     let (_env, client, _admin, member_1, member_2, member_3, _member_4) = create_test_env();
 
     // Initially, no one has delegations
-    assert_eq!(client.has_delegation(&member_1), false);
-    assert_eq!(client.has_delegation(&member_2), false);
+    assert!(!client.has_delegation(&member_1));
+    assert!(!client.has_delegation(&member_2));
 
     // member_1 delegates to member_2
     client.delegate_votes(&member_1, &member_2);
 
     // Now both member_1 (as delegator) and member_2 (as delegate) have delegations
-    assert_eq!(client.has_delegation(&member_1), true);
-    assert_eq!(client.has_delegation(&member_2), true);
-    assert_eq!(client.has_delegation(&member_3), false);
+    assert!(client.has_delegation(&member_1));
+    assert!(client.has_delegation(&member_2));
+    assert!(!client.has_delegation(&member_3));
 
     // After undelegation, neither should have delegations
     client.undelegate(&member_1);
-    assert_eq!(client.has_delegation(&member_1), false);
-    assert_eq!(client.has_delegation(&member_2), false);
+    assert!(!client.has_delegation(&member_1));
+    assert!(!client.has_delegation(&member_2));
 }
 
 #[test]
@@ -226,9 +243,1171 @@ fn test_circular_delegation_prevention() {
     assert_eq!(member_3_delegators.len(), 1);
     assert!(member_3_delegators.contains(&member_2));
 
-    // Test that member_3 can still delegate (circular delegation is not prevented at the contract level)
+    // Closing the loop back to member_1 must be rejected
+    assert_eq!(
+        client.try_delegate_votes(&member_3, &member_1),
+        Err(Ok(DelegationError::CircularDelegation))
+    );
+    assert_eq!(client.get_delegate(&member_3), None);
+
+    // Voting power is still computable along the chain
+    assert_eq!(client.get_voting_power(&member_3), 18);
+}
+
+#[test]
+fn test_two_way_delegation_prevention() {
+    let (_env, client, _admin, member_1, member_2, _member_3, _member_4) = create_test_env();
+
+    client.delegate_votes(&member_1, &member_2);
+
+    // B -> A after A -> B is a direct cycle
+    assert_eq!(
+        client.try_delegate_votes(&member_2, &member_1),
+        Err(Ok(DelegationError::CircularDelegation))
+    );
+
+    // Once A undelegates, B may delegate to A
+    client.undelegate(&member_1);
+    client.delegate_votes(&member_2, &member_1);
+    assert_eq!(client.get_delegate(&member_2), Some(member_1));
+}
+
+#[test]
+fn test_multi_hop_cycle_prevention() {
+    let (_env, client, _admin, member_1, member_2, member_3, member_4) = create_test_env();
+
+    // Chain of four: member_1 -> member_2 -> member_3 -> member_4
+    client.delegate_votes(&member_1, &member_2);
+    client.delegate_votes(&member_2, &member_3);
+    client.delegate_votes(&member_3, &member_4);
+
+    // Any delegation from the end of the chain back into it would close a cycle
+    for target in [&member_1, &member_2, &member_3] {
+        assert_eq!(
+            client.try_delegate_votes(&member_4, target),
+            Err(Ok(DelegationError::CircularDelegation))
+        );
+    }
+
+    // Redelegating within the chain also must not create a cycle
+    assert_eq!(
+        client.try_delegate_votes(&member_2, &member_1),
+        Err(Ok(DelegationError::CircularDelegation))
+    );
+
+    // Shortening the chain is fine: member_1 now delegates directly to member_4
+    client.delegate_votes(&member_1, &member_4);
+    assert_eq!(client.get_voting_power(&member_4), 4);
+    assert_eq!(client.get_voting_power(&member_2), 0);
+}
+
+#[test]
+fn test_max_chain_depth() {
+    let (env, client, _admin, member_1, member_2, member_3, member_4) = create_test_env();
+
+    assert_eq!(client.get_max_chain_depth(), DEFAULT_MAX_CHAIN_DEPTH);
+    assert_eq!(
+        client.try_set_max_chain_depth(&0),
+        Err(Ok(DelegationError::InvalidChainDepth))
+    );
+
+    client.set_max_chain_depth(&2);
+    assert_eq!(client.get_max_chain_depth(), 2);
+
+    // member_1 -> member_2 -> member_3 uses both hops
+    client.delegate_votes(&member_1, &member_2);
+    client.delegate_votes(&member_2, &member_3);
+
+    // Extending the chain at either end goes past the limit
+    assert_eq!(
+        client.try_delegate_votes(&member_3, &member_4),
+        Err(Ok(DelegationError::ChainTooDeep))
+    );
+    assert_eq!(
+        client.try_delegate_votes(&member_4, &member_1),
+        Err(Ok(DelegationError::ChainTooDeep))
+    );
+
+    // Joining two separate chains in the middle is measured end to end
+    let member_5 = Address::generate(&env);
+    client.register_delegate(&member_5, &statement(&env), &Vec::new(&env), &true);
+    client.undelegate(&member_2);
+    client.delegate_votes(&member_4, &member_5);
+    assert_eq!(
+        client.try_delegate_votes(&member_2, &member_4),
+        Err(Ok(DelegationError::ChainTooDeep))
+    );
+
+    // Delegating directly to the end of a one-hop chain stays within the limit
+    client.delegate_votes(&member_3, &member_4);
+    assert_eq!(client.get_voting_power(&member_5), 3);
+}
+
+#[test]
+fn test_cached_power_propagates_along_chain() {
+    let (env, client, _admin, member_1, member_2, member_3, member_4) = create_test_env();
+
+    client.set_base_power(&member_1, &10);
+    client.set_base_power(&member_2, &5);
+    client.set_base_power(&member_3, &3);
+
+    // member_1 -> member_2 -> member_3
+    client.delegate_votes(&member_1, &member_2);
+    client.delegate_votes(&member_2, &member_3);
+    assert_eq!(client.get_voting_power(&member_3), 18);
+
+    // A base power change at the start of the chain reaches every delegate
+    client.set_base_power(&member_1, &20);
+    assert_eq!(client.get_voting_power(&member_2), 0);
+    assert_eq!(client.get_voting_power(&member_3), 28);
+
+    // Moving member_2's subtree moves all of its power
+    client.delegate_votes(&member_2, &member_4);
+    assert_eq!(client.get_voting_power(&member_3), 3);
+    assert_eq!(client.get_voting_power(&member_4), 26);
+
+    client.undelegate(&member_1);
+    assert_eq!(client.get_voting_power(&member_1), 20);
+    assert_eq!(client.get_voting_power(&member_4), 6);
+
+    let mut members = Vec::new(&env);
+    for member in [&member_1, &member_2, &member_3, &member_4] {
+        members.push_back(member.clone());
+    }
+    assert_eq!(client.check_power_consistency(&members).len(), 0);
+
+    // A corrupted cache entry is reported
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&(DELEGATED_POWER, member_2.clone()), &99i128);
+    });
+    let inconsistent = client.check_power_consistency(&members);
+    assert_eq!(inconsistent.len(), 1);
+    assert_eq!(inconsistent.get(0).unwrap(), member_2);
+}
+
+fn shares(env: &Env, shares: &[(&Address, u32)]) -> Vec<DelegationShare> {
+    let mut result = Vec::new(env);
+    for (delegate, share_bps) in shares {
+        result.push_back(DelegationShare {
+            delegate: (*delegate).clone(),
+            share_bps: *share_bps,
+            expires_at: None,
+        });
+    }
+    result
+}
+
+#[test]
+fn test_split_delegation() {
+    let (env, client, _admin, member_1, member_2, member_3, member_4) = create_test_env();
+
+    client.set_base_power(&member_1, &100);
+    client.set_base_power(&member_2, &5);
+    client.set_base_power(&member_3, &3);
+
+    // member_1 splits 60% / 30% and keeps the rest undelegated: 10 + 65 + 33 = 100 + 5 + 3
+    client.delegate_shares(
+        &member_1,
+        &shares(&env, &[(&member_2, 6_000), (&member_3, 3_000)]),
+    );
+    assert_eq!(client.get_voting_power(&member_1), 10);
+    assert_eq!(client.get_voting_power(&member_2), 65);
+    assert_eq!(client.get_voting_power(&member_3), 33);
+
+    let delegations = client.get_delegations(&member_1);
+    assert_eq!(delegations.len(), 2);
+    assert_eq!(delegations.get(1).unwrap().share_bps, 3_000);
+    assert_eq!(client.get_delegate(&member_1), Some(member_2.clone()));
+
+    let received = client.get_received_delegations(&member_3);
+    assert_eq!(received.len(), 1);
+    assert_eq!(received.get(0).unwrap().delegator, member_1);
+    assert_eq!(received.get(0).unwrap().share_bps, 3_000);
+
+    // Shares of a delegate's power pass further down the chain
+    client.delegate_shares(&member_2, &shares(&env, &[(&member_4, 5_000)]));
+    assert_eq!(client.get_voting_power(&member_4), 33);
+    client.set_base_power(&member_1, &200);
+    assert_eq!(client.get_voting_power(&member_2), 63); // 125 held, half delegated
+    assert_eq!(client.get_voting_power(&member_4), 63);
+
+    // A full delegation replaces the split: 0 + 3 + 203 + 3 = 200 + 5 + 3 + 1
+    client.delegate_votes(&member_1, &member_3);
+    assert_eq!(client.get_voting_power(&member_1), 0);
+    assert_eq!(client.get_voting_power(&member_2), 3);
+    assert_eq!(client.get_voting_power(&member_3), 203);
+    assert_eq!(client.get_voting_power(&member_4), 3);
+    assert_eq!(client.get_delegators(&member_2).len(), 0);
+
+    client.undelegate(&member_1);
+    assert_eq!(client.get_voting_power(&member_3), 3);
+    assert_eq!(client.get_delegations(&member_1).len(), 0);
+
+    let mut members = Vec::new(&env);
+    for member in [&member_1, &member_2, &member_3, &member_4] {
+        members.push_back(member.clone());
+    }
+    assert_eq!(client.check_power_consistency(&members).len(), 0);
+}
+
+#[test]
+fn test_split_delegation_validation() {
+    let (env, client, _admin, member_1, member_2, member_3, _member_4) = create_test_env();
+
+    let over_full = shares(&env, &[(&member_2, 6_000), (&member_3, 4_001)]);
+    let duplicate = shares(&env, &[(&member_2, 1_000), (&member_2, 1_000)]);
+    let zero = shares(&env, &[(&member_2, 0)]);
+    let own = shares(&env, &[(&member_2, 1_000), (&member_1, 1_000)]);
+
+    for (requested, error) in [
+        (over_full, DelegationError::InvalidShares),
+        (duplicate, DelegationError::InvalidShares),
+        (zero, DelegationError::InvalidShares),
+        (Vec::new(&env), DelegationError::InvalidShares),
+        (own, DelegationError::SelfDelegation),
+    ] {
+        assert_eq!(
+            client.try_delegate_shares(&member_1, &requested),
+            Err(Ok(error))
+        );
+    }
+
+    let mut too_many = Vec::new(&env);
+    for _ in 0..=MAX_DELEGATES {
+        too_many.push_back(DelegationShare {
+            delegate: Address::generate(&env),
+            share_bps: 100,
+            expires_at: None,
+        });
+    }
+    assert_eq!(
+        client.try_delegate_shares(&member_1, &too_many),
+        Err(Ok(DelegationError::TooManyDelegates))
+    );
+
+    // A cycle through any of the delegates is rejected
     client.delegate_votes(&member_3, &member_1);
-    assert_eq!(client.get_delegate(&member_3), Some(member_1.clone()));
+    assert_eq!(
+        client.try_delegate_shares(
+            &member_1,
+            &shares(&env, &[(&member_2, 5_000), (&member_3, 5_000)])
+        ),
+        Err(Ok(DelegationError::CircularDelegation))
+    );
+    assert!(!client.has_delegation(&member_2));
+}
+
+#[test]
+fn test_topic_delegation() {
+    let (env, client, _admin, member_1, member_2, member_3, member_4) = create_test_env();
+
+    client.set_base_power(&member_1, &10);
+    client.set_base_power(&member_2, &5);
+    client.set_base_power(&member_3, &3);
+
+    // member_1 trusts member_2 by default and member_3 on treasury proposals
+    client.delegate_votes(&member_1, &member_2);
+    client.delegate_topic(&member_1, &Topic::Treasury, &member_3);
+
+    assert_eq!(client.get_voting_power(&member_2), 15);
+    assert_eq!(
+        client.get_voting_power_for_topic(&member_2, &Topic::Treasury),
+        5
+    );
+    assert_eq!(
+        client.get_voting_power_for_topic(&member_2, &Topic::Technical),
+        15
+    );
+    assert_eq!(client.get_voting_power(&member_3), 3);
+    assert_eq!(
+        client.get_voting_power_for_topic(&member_3, &Topic::Treasury),
+        13
+    );
+
+    let topic_delegations = client.get_topic_delegations(&member_1, &Topic::Treasury);
+    assert_eq!(topic_delegations.get(0).unwrap().delegate, member_3);
+    assert_eq!(
+        client
+            .get_topic_delegations(&member_1, &Topic::Technical)
+            .len(),
+        0
+    );
+
+    // Changes to the default delegation only reach topics that follow it
+    client.delegate_votes(&member_1, &member_4);
+    assert_eq!(
+        client.get_voting_power_for_topic(&member_2, &Topic::Governance),
+        5
+    );
+    assert_eq!(
+        client.get_voting_power_for_topic(&member_4, &Topic::Governance),
+        11
+    );
+    assert_eq!(
+        client.get_voting_power_for_topic(&member_3, &Topic::Treasury),
+        13
+    );
+
+    // Base power changes reach every topic
+    client.set_base_power(&member_1, &20);
+    assert_eq!(
+        client.get_voting_power_for_topic(&member_3, &Topic::Treasury),
+        23
+    );
+    assert_eq!(
+        client.get_voting_power_for_topic(&member_4, &Topic::Community),
+        21
+    );
+
+    // Removing the topic delegation falls back to the default delegate
+    client.undelegate_topic(&member_1, &Topic::Treasury);
+    assert_eq!(
+        client.get_voting_power_for_topic(&member_3, &Topic::Treasury),
+        3
+    );
+    assert_eq!(
+        client.get_voting_power_for_topic(&member_4, &Topic::Treasury),
+        21
+    );
+    assert_eq!(
+        client.try_undelegate_topic(&member_1, &Topic::Treasury),
+        Err(Ok(DelegationError::NoDelegation))
+    );
+
+    let mut members = Vec::new(&env);
+    for member in [&member_1, &member_2, &member_3, &member_4] {
+        members.push_back(member.clone());
+    }
+    assert_eq!(client.check_power_consistency(&members).len(), 0);
+}
+
+#[test]
+fn test_topic_delegation_cycles() {
+    let (_env, client, _admin, member_1, member_2, member_3, _member_4) = create_test_env();
+
+    // member_1 follows its default delegation on technical proposals, so this closes a cycle
+    client.delegate_votes(&member_1, &member_2);
+    assert_eq!(
+        client.try_delegate_topic(&member_2, &Topic::Technical, &member_1),
+        Err(Ok(DelegationError::CircularDelegation))
+    );
+
+    // Once member_1 delegates the topic elsewhere, the reverse topic delegation is fine
+    client.delegate_topic(&member_1, &Topic::Technical, &member_3);
+    client.delegate_topic(&member_2, &Topic::Technical, &member_1);
+    assert_eq!(
+        client.get_voting_power_for_topic(&member_3, &Topic::Technical),
+        3
+    );
+
+    // Falling back to the default delegation must not close a cycle
+    assert_eq!(
+        client.try_undelegate_topic(&member_1, &Topic::Technical),
+        Err(Ok(DelegationError::CircularDelegation))
+    );
+
+    // A default delegation is checked in every topic that follows it
+    client.delegate_topic(&member_2, &Topic::Community, &member_3);
+    assert_eq!(
+        client.try_delegate_votes(&member_3, &member_1),
+        Err(Ok(DelegationError::CircularDelegation))
+    );
+}
+
+fn expiring(env: &Env, delegate: &Address, expires_at: u64) -> Vec<DelegationShare> {
+    Vec::from_array(
+        env,
+        [DelegationShare {
+            delegate: delegate.clone(),
+            share_bps: BPS_DENOMINATOR,
+            expires_at: Some(expires_at),
+        }],
+    )
+}
+
+fn set_timestamp(env: &Env, timestamp: u64) {
+    env.ledger().with_mut(|li| li.timestamp = timestamp);
+}
+
+#[test]
+fn test_delegation_expiry() {
+    let (env, client, _admin, member_1, member_2, member_3, member_4) = create_test_env();
+    let members = Vec::from_array(
+        &env,
+        [
+            member_1.clone(),
+            member_2.clone(),
+            member_3.clone(),
+            member_4.clone(),
+        ],
+    );
+
+    set_timestamp(&env, 1_000);
+    client.set_base_power(&member_1, &10);
+    client.set_base_power(&member_2, &5);
+    client.set_base_power(&member_3, &3);
+
+    // member_3 -> member_1 -> member_2, where the second hop expires
+    client.delegate_votes(&member_3, &member_1);
+    client.delegate_shares(&member_1, &expiring(&env, &member_2, 2_000));
+    client.delegate_votes(&member_4, &member_2);
+    assert_eq!(client.get_voting_power(&member_2), 19); // 5 + 13 + 1
+    assert_eq!(
+        client.get_voting_power_for_topic(&member_2, &Topic::Treasury),
+        19
+    );
+    assert_eq!(client.get_voting_power(&member_1), 0);
+
+    // Expiries must be in the future
+    assert_eq!(
+        client.try_delegate_shares(&member_1, &expiring(&env, &member_2, 1_000)),
+        Err(Ok(DelegationError::InvalidExpiry))
+    );
+
+    // Once expired, the power returns to the delegator without any transaction
+    set_timestamp(&env, 2_000);
+    assert_eq!(client.get_voting_power(&member_2), 6);
+    assert_eq!(
+        client.get_voting_power_for_topic(&member_2, &Topic::Treasury),
+        6
+    );
+    assert_eq!(client.get_voting_power(&member_1), 13);
+    assert_eq!(client.get_delegate(&member_1), None);
+    assert_eq!(client.get_delegators(&member_2).len(), 1);
+    assert!(client.has_delegation(&member_1));
+    assert!(client.check_power_consistency(&members).is_empty());
+
+    // Clearing the expired delegation keeps the power and restores the cached reads
+    assert_eq!(client.clear_expired_delegations(&member_1), 1);
+    assert_eq!(client.clear_expired_delegations(&member_1), 0);
+    assert_eq!(client.get_voting_power(&member_2), 6);
+    assert!(client.check_power_consistency(&members).is_empty());
+
+    // Renewing the delegation moves the power again
+    client.delegate_shares(&member_1, &expiring(&env, &member_2, 3_000));
+    assert_eq!(client.get_voting_power(&member_2), 19);
+}
+
+#[test]
+fn test_topic_delegation_expiry() {
+    let (env, client, _admin, member_1, member_2, member_3, member_4) = create_test_env();
+
+    set_timestamp(&env, 1_000);
+    client.set_base_power(&member_1, &10);
+    client.delegate_votes(&member_1, &member_2);
+    client.delegate_topic_shares(
+        &member_1,
+        &Topic::Treasury,
+        &expiring(&env, &member_3, 2_000),
+    );
+    client.delegate_votes(&member_4, &member_3);
+
+    assert_eq!(
+        client.get_voting_power_for_topic(&member_2, &Topic::Treasury),
+        1
+    );
+    assert_eq!(
+        client.get_voting_power_for_topic(&member_3, &Topic::Treasury),
+        12
+    );
+
+    // The expired topic falls back to the default delegation
+    set_timestamp(&env, 2_000);
+    assert_eq!(
+        client.get_voting_power_for_topic(&member_2, &Topic::Treasury),
+        11
+    );
+    assert_eq!(
+        client.get_voting_power_for_topic(&member_3, &Topic::Treasury),
+        2
+    );
+    assert!(
+        client
+            .get_topic_delegations(&member_1, &Topic::Treasury)
+            .is_empty()
+    );
+
+    // The fallback is part of the cycle check while the topic delegation is in place
+    set_timestamp(&env, 1_000);
+    assert_eq!(
+        client.try_delegate_topic(&member_2, &Topic::Treasury, &member_1),
+        Err(Ok(DelegationError::CircularDelegation))
+    );
+}
+
+#[test]
+fn test_expiring_delegations() {
+    let (env, client, _admin, member_1, member_2, member_3, _member_4) = create_test_env();
+
+    set_timestamp(&env, 1_000);
+    client.delegate_shares(&member_1, &expiring(&env, &member_2, 1_500));
+    client.delegate_topic_shares(
+        &member_1,
+        &Topic::Technical,
+        &expiring(&env, &member_3, 5_000),
+    );
+    client.delegate_topic(&member_1, &Topic::Community, &member_3);
+
+    let expiring_soon = client.get_expiring_delegations(&member_1, &500);
+    assert_eq!(expiring_soon.len(), 1);
+    assert_eq!(
+        expiring_soon.get(0).unwrap().scope,
+        DelegationScope::Default
+    );
+    assert_eq!(expiring_soon.get(0).unwrap().delegation.delegate, member_2);
+
+    let expiring_later = client.get_expiring_delegations(&member_1, &4_000);
+    assert_eq!(expiring_later.len(), 2);
+    assert_eq!(
+        expiring_later.get(1).unwrap().scope,
+        DelegationScope::Topic(Topic::Technical)
+    );
+
+    // Expired delegations are no longer listed
+    set_timestamp(&env, 1_500);
+    assert_eq!(client.get_expiring_delegations(&member_1, &4_000).len(), 1);
+}
+
+#[test]
+fn test_delegate_registry() {
+    let (env, client, _admin, member_1, member_2, member_3, member_4) = create_test_env();
+
+    let interests = Vec::from_array(&env, [Topic::Treasury, Topic::Technical]);
+    client.register_delegate(&member_2, &statement(&env), &interests, &true);
+    let profile = client.get_delegate_profile(&member_2).unwrap();
+    assert_eq!(profile.interests, interests);
+    assert!(profile.accepting);
+
+    // Unregistered addresses cannot be made delegates
+    let outsider = Address::generate(&env);
+    assert_eq!(client.get_delegate_profile(&outsider), None);
+    assert_eq!(
+        client.try_delegate_votes(&member_1, &outsider),
+        Err(Ok(DelegationError::NotAccepting))
+    );
+    assert_eq!(
+        client.try_set_accepting_delegations(&outsider, &false),
+        Err(Ok(DelegationError::NotRegistered))
+    );
+
+    // A delegate that refuses delegations keeps the existing ones
+    client.delegate_votes(&member_1, &member_2);
+    client.set_accepting_delegations(&member_2, &false);
+    assert_eq!(
+        client.try_delegate_votes(&member_3, &member_2),
+        Err(Ok(DelegationError::NotAccepting))
+    );
+    assert_eq!(
+        client.try_delegate_topic(&member_3, &Topic::Treasury, &member_2),
+        Err(Ok(DelegationError::NotAccepting))
+    );
+    assert_eq!(client.get_voting_power(&member_2), 2);
+
+    // Kicking a delegator removes its delegations in every scope
+    client.set_accepting_delegations(&member_2, &true);
+    client.delegate_topic(&member_4, &Topic::Treasury, &member_2);
+    client.delegate_votes(&member_4, &member_2);
+    assert_eq!(client.remove_delegator(&member_2, &member_4), 2);
+    assert_eq!(client.get_voting_power(&member_2), 2);
+    assert_eq!(
+        client.get_voting_power_for_topic(&member_2, &Topic::Treasury),
+        2
+    );
+    assert_eq!(
+        client.try_remove_delegator(&member_2, &member_4),
+        Err(Ok(DelegationError::NoDelegation))
+    );
+}
+
+#[test]
+fn test_registered_delegates_listing() {
+    let (_env, client, _admin, member_1, member_2, member_3, member_4) = create_test_env();
+
+    client.set_base_power(&member_1, &10);
+    client.delegate_votes(&member_1, &member_3);
+    client.delegate_votes(&member_2, &member_4);
+
+    // Sorted by received power, with ties in registration order
+    let all = client.get_registered_delegates(&0, &10);
+    assert_eq!(all.len(), 4);
+    assert_eq!(all.get(0).unwrap().profile.delegate, member_3);
+    assert_eq!(all.get(0).unwrap().received_power, 10);
+    assert_eq!(all.get(1).unwrap().profile.delegate, member_4);
+    assert_eq!(all.get(2).unwrap().profile.delegate, member_1);
+    assert_eq!(all.get(3).unwrap().profile.delegate, member_2);
+
+    let page = client.get_registered_delegates(&1, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().profile.delegate, member_4);
+    assert_eq!(page.get(1).unwrap().profile.delegate, member_1);
+    assert!(client.get_registered_delegates(&4, &10).is_empty());
+
+    // The ranking follows later changes, returning to registration order on ties
+    client.undelegate(&member_1);
+    client.set_base_power(&member_2, &3);
+    let all = client.get_registered_delegates(&0, &10);
+    assert_eq!(all.get(0).unwrap().profile.delegate, member_4);
+    assert_eq!(all.get(0).unwrap().received_power, 3);
+    assert_eq!(all.get(1).unwrap().profile.delegate, member_1);
+    assert_eq!(all.get(2).unwrap().profile.delegate, member_2);
+    assert_eq!(all.get(3).unwrap().profile.delegate, member_3);
+    assert_eq!(all.get(3).unwrap().received_power, 0);
+}
+
+// Minimal member registry exposing `get_voting_power` like the member-registry contract
+#[contract]
+pub struct MockMemberRegistry;
+
+#[contractimpl]
+impl MockMemberRegistry {
+    pub fn set_power(env: Env, address: Address, power: u32) {
+        env.storage().persistent().set(&address, &power);
+    }
+
+    pub fn get_voting_power(env: Env, address: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&address)
+            .unwrap_or_else(|| panic!("Member not found"))
+    }
+
+    pub fn set_total_voting_power(env: Env, power: u32) {
+        env.storage()
+            .persistent()
+            .set(&symbol_short!("total"), &power);
+    }
+
+    pub fn get_total_voting_power(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&symbol_short!("total"))
+            .unwrap_or(0)
+    }
+}
+
+// Minimal voting NFT contract exposing `get_nfts_by_owner` and its supply
+#[contract]
+pub struct MockVotingNft;
+
+#[contractimpl]
+impl MockVotingNft {
+    pub fn mint(env: Env, to: Address, category: Symbol) {
+        let mut nfts = Self::get_nfts_by_owner(env.clone(), to.clone());
+        nfts.push_back(VotingNFT {
+            token_id: nfts.len() + 1,
+            category: category.clone(),
+            metadata: symbol_short!("meta"),
+            owner: to.clone(),
+            issued_at: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&to, &nfts);
+
+        let supply = Self::get_category_supply(env.clone(), category.clone());
+        env.storage().persistent().set(&category, &(supply + 1));
+        let total = Self::get_total_supply(env.clone());
+        env.storage()
+            .persistent()
+            .set(&symbol_short!("total"), &(total + 1));
+    }
+
+    pub fn get_category_supply(env: Env, category: Symbol) -> u32 {
+        env.storage().persistent().get(&category).unwrap_or(0)
+    }
+
+    pub fn get_total_supply(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&symbol_short!("total"))
+            .unwrap_or(0)
+    }
+
+    pub fn get_nfts_by_owner(env: Env, owner: Address) -> Vec<VotingNFT> {
+        env.storage()
+            .persistent()
+            .get(&owner)
+            .unwrap_or(Vec::new(&env))
+    }
+}
+
+#[test]
+fn test_member_registry_power_source() {
+    let (env, client, _admin, member_1, member_2, member_3, _member_4) = create_test_env();
+    let registry = MockMemberRegistryClient::new(&env, &env.register(MockMemberRegistry, ()));
+
+    client.delegate_votes(&member_1, &member_2);
+    assert_eq!(client.get_voting_power(&member_2), 2);
+
+    registry.set_power(&member_1, &10);
+    registry.set_power(&member_2, &5);
+    client.set_power_source(&PowerSource::MemberRegistry(registry.address.clone()));
+    assert_eq!(
+        client.get_power_source(),
+        PowerSource::MemberRegistry(registry.address.clone())
+    );
+
+    // Non-members have no power once synced, and manual base power is disabled
+    assert_eq!(client.get_base_power(&member_3), 0);
+    assert_eq!(client.get_voting_power(&member_3), 1);
+    client.sync_base_power(&member_3);
+    assert_eq!(client.get_voting_power(&member_3), 0);
+    assert_eq!(
+        client.try_set_base_power(&member_1, &3),
+        Err(Ok(DelegationError::ManualPowerDisabled))
+    );
+
+    // Voting power follows the registry once members are synced
+    assert_eq!(client.get_voting_power(&member_2), 2);
+    assert_eq!(client.sync_base_power(&member_1), 10);
+    assert_eq!(client.get_voting_power(&member_2), 11);
+    assert_eq!(client.get_voting_power(&member_1), 0);
+    assert_eq!(client.sync_base_power(&member_2), 5);
+    assert_eq!(client.get_voting_power(&member_2), 15);
+
+    // Delegating syncs the delegator
+    registry.set_power(&member_1, &20);
+    client.delegate_votes(&member_1, &member_2);
+    assert_eq!(client.get_voting_power(&member_2), 25);
+
+    let members = Vec::from_array(&env, [member_1.clone(), member_2.clone()]);
+    assert!(client.check_power_consistency(&members).is_empty());
+
+    // Switching back to manual restores the admin-set power after a sync
+    client.set_power_source(&PowerSource::Manual);
+    client.sync_base_power(&member_1);
+    assert_eq!(client.get_voting_power(&member_2), 6);
+    client.sync_base_power(&member_2);
+    assert_eq!(client.get_voting_power(&member_2), 2);
+}
+
+#[test]
+fn test_power_source_change_before_sync() {
+    let (env, client, _admin, member_1, member_2, _member_3, _member_4) = create_test_env();
+    let registry = MockMemberRegistryClient::new(&env, &env.register(MockMemberRegistry, ()));
+
+    registry.set_power(&member_1, &10);
+    registry.set_power(&member_2, &0);
+    client.set_power_source(&PowerSource::MemberRegistry(registry.address.clone()));
+    client.sync_base_power(&member_2);
+    client.delegate_shares(&member_1, &shares(&env, &[(&member_2, 5_000)]));
+    assert_eq!(client.get_voting_power(&member_1), 5);
+    assert_eq!(client.get_voting_power(&member_2), 5);
+
+    // Both sides of the delegation keep counting the synced power until the delegator is synced
+    registry.set_power(&member_1, &30);
+    assert_eq!(client.get_voting_power(&member_1), 5);
+    assert_eq!(client.get_voting_power(&member_2), 5);
+
+    client.sync_base_power(&member_1);
+    assert_eq!(client.get_voting_power(&member_1), 15);
+    assert_eq!(client.get_voting_power(&member_2), 15);
+}
+
+#[test]
+fn test_voting_nft_power_source() {
+    let (env, client, _admin, member_1, member_2, _member_3, _member_4) = create_test_env();
+    let nft = MockVotingNftClient::new(&env, &env.register(MockVotingNft, ()));
+
+    nft.mint(&member_1, &symbol_short!("council"));
+    nft.mint(&member_1, &symbol_short!("general"));
+    nft.mint(&member_1, &symbol_short!("general"));
+
+    // Count every NFT once
+    let mut config = NftPowerConfig {
+        contract: nft.address.clone(),
+        weights: Vec::new(&env),
+        default_weight: 1,
+    };
+    client.set_power_source(&PowerSource::VotingNft(config.clone()));
+    assert_eq!(client.get_base_power(&member_1), 3);
+    assert_eq!(client.get_base_power(&member_2), 0);
+
+    // Weigh NFTs by category
+    config.weights = Vec::from_array(
+        &env,
+        [CategoryWeight {
+            category: symbol_short!("council"),
+            weight: 5,
+        }],
+    );
+    config.default_weight = 2;
+    client.set_power_source(&PowerSource::VotingNft(config.clone()));
+    assert_eq!(client.get_base_power(&member_1), 9);
+
+    client.sync_base_power(&member_2);
+    client.delegate_votes(&member_1, &member_2);
+    assert_eq!(client.get_voting_power(&member_2), 9);
+
+    config.default_weight = -1;
+    assert_eq!(
+        client.try_set_power_source(&PowerSource::VotingNft(config)),
+        Err(Ok(DelegationError::InvalidPowerSource))
+    );
+}
+
+fn set_up_powers(client: &DelegationContractClient, members: [&Address; 4]) {
+    for (member, power) in members.into_iter().zip([10, 5, 3, 2]) {
+        client.set_base_power(member, &power);
+    }
+}
+
+#[test]
+fn test_power_cap_rejects_excess() {
+    let (_env, client, _admin, member_1, member_2, member_3, member_4) = create_test_env();
+    set_up_powers(&client, [&member_1, &member_2, &member_3, &member_4]);
+
+    for cap in [PowerCap::Absolute(0), PowerCap::Percentage(10_001)] {
+        let config = PowerCapConfig {
+            cap,
+            policy: CapPolicy::Reject,
+        };
+        assert_eq!(
+            client.try_set_power_cap(&config),
+            Err(Ok(DelegationError::InvalidPowerCap))
+        );
+    }
+
+    // Half of the total power of 20
+    client.set_power_cap(&PowerCapConfig {
+        cap: PowerCap::Percentage(5_000),
+        policy: CapPolicy::Reject,
+    });
+    assert_eq!(client.get_power_cap_limit(), Some(10));
+
+    client.delegate_votes(&member_1, &member_2);
+    assert_eq!(
+        client.try_delegate_votes(&member_3, &member_2),
+        Err(Ok(DelegationError::PowerCapExceeded))
+    );
+
+    // Delegates further down the chain are capped too
+    client.delegate_votes(&member_3, &member_4);
+    assert_eq!(
+        client.try_delegate_votes(&member_4, &member_2),
+        Err(Ok(DelegationError::PowerCapExceeded))
+    );
+
+    // Renewing a delegation that is already at the cap is fine
+    client.delegate_votes(&member_1, &member_2);
+    assert_eq!(client.get_voting_power(&member_2), 15);
+}
+
+#[test]
+fn test_power_cap_counts_excess_back() {
+    let (_env, client, _admin, member_1, member_2, member_3, member_4) = create_test_env();
+    set_up_powers(&client, [&member_1, &member_2, &member_3, &member_4]);
+
+    client.set_power_cap(&PowerCapConfig {
+        cap: PowerCap::Absolute(8),
+        policy: CapPolicy::CountBack,
+    });
+
+    // Only 80% of member_1's power fits under the cap
+    client.delegate_votes(&member_1, &member_2);
+    assert_eq!(
+        client.get_delegations(&member_1).get(0).unwrap().share_bps,
+        8_000
+    );
+    assert_eq!(client.get_voting_power(&member_2), 13);
+    assert_eq!(client.get_voting_power(&member_1), 2);
+
+    // A delegate at the cap has no room left
+    assert_eq!(
+        client.try_delegate_votes(&member_3, &member_2),
+        Err(Ok(DelegationError::PowerCapExceeded))
+    );
+
+    client.delegate_votes(&member_2, &member_4);
+    assert_eq!(client.get_voting_power(&member_4), 9); // 2 + 7 of member_2's 13
+}
+
+#[test]
+fn test_concentration_metrics() {
+    let (_env, client, _admin, member_1, member_2, member_3, member_4) = create_test_env();
+    set_up_powers(&client, [&member_1, &member_2, &member_3, &member_4]);
+
+    client.delegate_votes(&member_1, &member_3);
+    client.delegate_votes(&member_2, &member_3);
+    client.delegate_votes(&member_4, &member_2);
+
+    let top = client.get_concentration(&1);
+    assert_eq!(top.total_power, 20);
+    assert_eq!(top.top_power, 17); // 10 + 5 + 2 through member_2
+    assert_eq!(top.top_share_bps, 8_500);
+    assert_eq!(top.largest_share_bps, 8_500);
+
+    let top_two = client.get_concentration(&2);
+    assert_eq!(top_two.top_power, 19);
+    assert_eq!(top_two.top_share_bps, 9_500);
+    assert_eq!(top_two.largest_share_bps, 8_500);
+}
+
+#[test]
+fn test_power_cap_share_of_default_power() {
+    let (env, client, _admin, member_1, member_2, member_3, member_4) = create_test_env();
+
+    // The four registered delegates count with the default base power of 1
+    client.set_power_cap(&PowerCapConfig {
+        cap: PowerCap::Percentage(5_000),
+        policy: CapPolicy::Reject,
+    });
+    assert_eq!(client.get_power_cap_limit(), Some(2));
+
+    client.delegate_votes(&member_1, &member_2);
+    client.delegate_votes(&member_3, &member_2);
+    assert_eq!(
+        client.try_delegate_votes(&member_4, &member_2),
+        Err(Ok(DelegationError::PowerCapExceeded))
+    );
+
+    // Delegators that are not delegates count once they delegate
+    let member_5 = Address::generate(&env);
+    client.set_base_power(&member_5, &2);
+    client.delegate_votes(&member_5, &member_4);
+    assert_eq!(client.get_power_cap_limit(), Some(3));
+}
+
+#[test]
+fn test_power_cap_share_of_source_total() {
+    let (env, client, _admin, member_1, member_2, member_3, _member_4) = create_test_env();
+    let registry = MockMemberRegistryClient::new(&env, &env.register(MockMemberRegistry, ()));
+    client.set_power_cap(&PowerCapConfig {
+        cap: PowerCap::Percentage(5_000),
+        policy: CapPolicy::CountBack,
+    });
+
+    // Registry members count before they are synced
+    registry.set_power(&member_1, &10);
+    registry.set_total_voting_power(&40);
+    client.set_power_source(&PowerSource::MemberRegistry(registry.address.clone()));
+    assert_eq!(client.get_power_cap_limit(), Some(20));
+    client.delegate_votes(&member_1, &member_2);
+    assert_eq!(client.get_concentration(&1).total_power, 40);
+
+    // A delegator without power fits even once the delegate is over the cap
+    registry.set_total_voting_power(&10);
+    client.sync_base_power(&member_3);
+    client.delegate_votes(&member_3, &member_2);
+    assert_eq!(
+        client.get_delegations(&member_3).get(0).unwrap().share_bps,
+        10_000
+    );
+
+    // Every minted NFT counts with its category weight
+    let nft = MockVotingNftClient::new(&env, &env.register(MockVotingNft, ()));
+    nft.mint(&member_1, &symbol_short!("council"));
+    nft.mint(&member_2, &symbol_short!("general"));
+    nft.mint(&member_3, &symbol_short!("general"));
+    let config = NftPowerConfig {
+        contract: nft.address.clone(),
+        weights: Vec::from_array(
+            &env,
+            [CategoryWeight {
+                category: symbol_short!("council"),
+                weight: 6,
+            }],
+        ),
+        default_weight: 2,
+    };
+    client.set_power_source(&PowerSource::VotingNft(config));
+    assert_eq!(client.get_power_cap_limit(), Some(5));
+}
+
+#[test]
+fn test_clearing_expired_keeps_remaining_shares() {
+    let (env, client, _admin, member_1, member_2, member_3, member_4) = create_test_env();
+    set_up_powers(&client, [&member_1, &member_2, &member_3, &member_4]);
+    set_timestamp(&env, 100);
+
+    let shares = Vec::from_array(
+        &env,
+        [
+            DelegationShare {
+                delegate: member_2.clone(),
+                share_bps: 5_000,
+                expires_at: Some(200),
+            },
+            DelegationShare {
+                delegate: member_3.clone(),
+                share_bps: 5_000,
+                expires_at: None,
+            },
+        ],
+    );
+    client.delegate_shares(&member_1, &shares);
+    client.delegate_votes(&member_3, &member_4);
+
+    // Tighter limits set afterwards only apply to new delegations
+    client.set_max_chain_depth(&1);
+    client.set_power_cap(&PowerCapConfig {
+        cap: PowerCap::Absolute(4),
+        policy: CapPolicy::CountBack,
+    });
+
+    set_timestamp(&env, 200);
+    assert_eq!(client.clear_expired_delegations(&member_1), 1);
+
+    let delegations = client.get_delegations(&member_1);
+    assert_eq!(delegations.len(), 1);
+    assert_eq!(delegations.get(0).unwrap().share_bps, 5_000);
+    assert_eq!(client.get_voting_power(&member_2), 5);
+    assert_eq!(client.get_voting_power(&member_4), 10);
+
+    assert_eq!(client.remove_delegator(&member_4, &member_3), 1);
+    assert_eq!(client.get_voting_power(&member_4), 2);
+}
+
+fn set_ledger(env: &Env, sequence: u32) {
+    env.ledger().with_mut(|li| li.sequence_number = sequence);
+}
+
+#[test]
+fn test_past_voting_power() {
+    let (env, client, _admin, member_1, member_2, member_3, _member_4) = create_test_env();
+
+    set_ledger(&env, 10);
+    client.set_base_power(&member_1, &10);
+    client.set_base_power(&member_2, &5);
+
+    set_ledger(&env, 20);
+    client.delegate_votes(&member_1, &member_2);
+
+    // Several changes in one ledger keep only the final power
+    set_ledger(&env, 30);
+    client.set_base_power(&member_1, &40);
+    client.set_base_power(&member_1, &30);
+
+    set_ledger(&env, 40);
+    client.undelegate(&member_1);
+
+    set_ledger(&env, 50);
+    assert_eq!(client.get_past_voting_power(&member_2, &5), 1);
+    assert_eq!(client.get_past_voting_power(&member_2, &10), 5);
+    assert_eq!(client.get_past_voting_power(&member_2, &25), 15);
+    assert_eq!(client.get_past_voting_power(&member_2, &30), 35);
+    assert_eq!(client.get_past_voting_power(&member_2, &45), 5);
+    assert_eq!(client.get_past_voting_power(&member_1, &15), 10);
+    assert_eq!(client.get_past_voting_power(&member_1, &35), 0);
+    assert_eq!(client.get_past_voting_power(&member_1, &45), 30);
+
+    // Members whose power never changed report their current power
+    assert_eq!(client.get_past_voting_power(&member_3, &15), 1);
+
+    // Members never synced have no history to report
+    assert_eq!(
+        client.try_get_past_voting_power(&Address::generate(&env), &15),
+        Err(Ok(DelegationError::NotSynced))
+    );
+
+    assert_eq!(
+        client.try_get_past_voting_power(&member_2, &50),
+        Err(Ok(DelegationError::FutureLedger))
+    );
+}
+
+#[test]
+fn test_past_total_power() {
+    let (env, client, _admin, member_1, member_2, _member_3, _member_4) = create_test_env();
+
+    set_ledger(&env, 10);
+    client.set_base_power(&member_1, &10);
+
+    set_ledger(&env, 20);
+    client.set_base_power(&member_2, &5);
+    client.delegate_votes(&member_1, &member_2);
+
+    set_ledger(&env, 30);
+    client.set_base_power(&member_1, &0);
+
+    set_ledger(&env, 40);
+    // The four registered delegates start with the default base power of 1
+    assert_eq!(client.get_past_total_power(&5), 4);
+    assert_eq!(client.get_past_total_power(&10), 13);
+    assert_eq!(client.get_past_total_power(&25), 17);
+    // Clearing the base power reverts member_1 to the default
+    assert_eq!(client.get_past_total_power(&30), 8);
+    assert_eq!(
+        client.try_get_past_total_power(&40),
+        Err(Ok(DelegationError::FutureLedger))
+    );
+}
+
+#[test]
+fn test_expired_delegations_stop_counting() {
+    let (env, client, _admin, member_1, member_2, member_3, member_4) = create_test_env();
+    set_up_powers(&client, [&member_1, &member_2, &member_3, &member_4]);
+
+    set_ledger(&env, 10);
+    set_timestamp(&env, 1_000);
+    client.delegate_shares(&member_1, &expiring(&env, &member_2, 2_000));
+    client.delegate_votes(&member_3, &member_4);
+    client.set_power_cap(&PowerCapConfig {
+        cap: PowerCap::Absolute(10),
+        policy: CapPolicy::Reject,
+    });
+
+    set_ledger(&env, 20);
+    set_timestamp(&env, 1_500);
+    assert_eq!(client.get_past_voting_power(&member_2, &10), 15);
+    assert_eq!(
+        client.try_delegate_votes(&member_4, &member_2),
+        Err(Ok(DelegationError::PowerCapExceeded))
+    );
+
+    // The delegation expires without being cleared
+    set_ledger(&env, 30);
+    set_timestamp(&env, 2_000);
+    let delegates = client.get_registered_delegates(&0, &4);
+    assert_eq!(delegates.get(0).unwrap().profile.delegate, member_2);
+    assert_eq!(delegates.get(0).unwrap().received_power, 0);
+    assert_eq!(delegates.get(1).unwrap().received_power, 3);
+
+    let top_two = client.get_concentration(&2);
+    assert_eq!(top_two.top_power, 3);
+    assert_eq!(top_two.largest_share_bps, 1_500);
+
+    // Ledgers that may follow the expiry count the power for neither side
+    assert_eq!(client.get_past_voting_power(&member_2, &10), 5);
+    assert_eq!(client.get_past_voting_power(&member_2, &20), 5);
+    assert_eq!(client.get_past_voting_power(&member_1, &20), 0);
+    assert_eq!(client.get_past_voting_power(&member_4, &20), 5);
+    assert_eq!(client.get_past_total_power(&20), 20);
+
+    // The expired power leaves room under the cap
+    client.delegate_votes(&member_4, &member_2);
+    assert_eq!(client.get_voting_power(&member_2), 10);
+}
+
+#[test]
+fn test_checkpoints_are_bounded() {
+    let (env, client, _admin, member_1, _member_2, _member_3, _member_4) = create_test_env();
+
+    for ledger in 1..=MAX_CHECKPOINTS + 1 {
+        set_ledger(&env, ledger);
+        client.set_base_power(&member_1, &(ledger as i128 + 1));
+    }
+
+    set_ledger(&env, MAX_CHECKPOINTS + 2);
+    assert_eq!(
+        client.get_past_voting_power(&member_1, &(MAX_CHECKPOINTS + 1)),
+        MAX_CHECKPOINTS as i128 + 2
+    );
+    assert_eq!(client.get_past_voting_power(&member_1, &2), 3);
+
+    // History older than the retained checkpoints is no longer available
+    assert_eq!(
+        client.try_get_past_voting_power(&member_1, &1),
+        Err(Ok(DelegationError::HistoryUnavailable))
+    );
+
+    // The total power keeps its whole history
+    assert_eq!(client.get_past_total_power(&0), 4);
+    assert_eq!(client.get_past_total_power(&1), 5);
+    assert_eq!(
+        client.get_past_total_power(&(MAX_CHECKPOINTS + 1)),
+        MAX_CHECKPOINTS as i128 + 5
+    );
 }
 
 #[test]
@@ -244,7 +1423,7 @@ fn test_max_power_delegation() {
     client.delegate_votes(&member_1, &member_2);
 
     // Check that power calculation handles large numbers correctly
-    assert_eq!(client.get_voting_power(&member_1), max_power);
+    assert_eq!(client.get_voting_power(&member_1), 0);
     assert_eq!(client.get_voting_power(&member_2), max_power * 2);
 }
 
@@ -265,11 +1444,11 @@ fn test_concurrent_delegations() {
 
     // Verify final state after all delegations
     assert_eq!(client.get_voting_power(&member_2), 20); // 5 + 10 + 3 + 2
-    
+
     // Verify all delegations are tracked correctly
     let delegators = client.get_delegators(&member_2);
     assert_eq!(delegators.len(), 3);
-    
+
     // Undelegate all simultaneously
     client.undelegate(&member_1);
     client.undelegate(&member_3);
@@ -279,3 +1458,105 @@ fn test_concurrent_delegations() {
     assert_eq!(client.get_voting_power(&member_2), 5);
     assert_eq!(client.get_delegators(&member_2).len(), 0);
 }
+
+#[test]
+fn test_constructor_base_power() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let client =
+        DelegationContractClient::new(&env, &env.register(DelegationContract {}, (&admin, 3i128)));
+    env.mock_all_auths();
+
+    let member = Address::generate(&env);
+    assert_eq!(client.get_base_power(&member), 3);
+    assert_eq!(client.get_voting_power(&member), 3);
+
+    client.set_base_power(&member, &7);
+    assert_eq!(client.get_base_power(&member), 7);
+}
+
+fn auth_addresses(env: &Env) -> Vec<Address> {
+    let mut authorizers = Vec::new(env);
+    for (address, _) in env.auths() {
+        authorizers.push_back(address);
+    }
+    authorizers
+}
+
+#[test]
+fn test_admin_transfer() {
+    let (env, client, admin, member_1, _member_2, _member_3, _member_4) = create_test_env();
+    assert_eq!(client.get_admin(), admin);
+
+    let new_admin = Address::generate(&env);
+    client.transfer_admin(&new_admin);
+
+    // Both the current and the new admin authorized the transfer
+    let authorizers = auth_addresses(&env);
+    assert!(authorizers.contains(&admin));
+    assert!(authorizers.contains(&new_admin));
+    assert_eq!(client.get_admin(), new_admin);
+
+    client.set_base_power(&member_1, &4);
+    let authorizers = auth_addresses(&env);
+    assert_eq!(authorizers, Vec::from_array(&env, [new_admin]));
+}
+
+#[test]
+fn test_delegation_events() {
+    let (env, client, _admin, member_1, member_2, _member_3, _member_4) = create_test_env();
+
+    client.set_base_power(&member_1, &10);
+    assert_eq!(
+        env.events().all(),
+        soroban_sdk::vec![
+            &env,
+            (
+                client.address.clone(),
+                (Symbol::new(&env, "base_power_set"), member_1.clone()).into_val(&env),
+                10i128.into_val(&env),
+            ),
+        ]
+    );
+
+    client.delegate_topic(&member_1, &Topic::Treasury, &member_2);
+    let delegation = Delegation {
+        delegator: member_1.clone(),
+        delegate: member_2.clone(),
+        share_bps: BPS_DENOMINATOR,
+        expires_at: None,
+    };
+    assert_eq!(
+        env.events().all(),
+        soroban_sdk::vec![
+            &env,
+            (
+                client.address.clone(),
+                (Symbol::new(&env, "delegations_set"), member_1.clone()).into_val(&env),
+                (
+                    DelegationScope::Topic(Topic::Treasury),
+                    Vec::from_array(&env, [delegation]),
+                )
+                    .into_val(&env),
+            ),
+        ]
+    );
+
+    // Removals emit an empty list
+    client.undelegate_topic(&member_1, &Topic::Treasury);
+    assert_eq!(
+        env.events().all(),
+        soroban_sdk::vec![
+            &env,
+            (
+                client.address.clone(),
+                (Symbol::new(&env, "delegations_set"), member_1.clone()).into_val(&env),
+                (
+                    DelegationScope::Topic(Topic::Treasury),
+                    Vec::<Delegation>::new(&env),
+                )
+                    .into_val(&env),
+            ),
+        ]
+    );
+}
@@ -58,6 +58,7 @@ impl MemberRegistryContract {
         env.storage()
             .instance()
             .set(&DataKey::TotalMembers, &(total_members + 1));
+        Self::adjust_total_voting_power(&env, 0, member.voting_power);
 
         // Emit event
         env.events()
@@ -78,6 +79,14 @@ impl MemberRegistryContract {
             .instance()
             .get(&DataKey::Member(address.clone()))
             .unwrap_or_else(|| panic!("Member not found"));
+
+        // Only active members count towards the total voting power
+        match (member.is_active, status) {
+            (true, false) => Self::adjust_total_voting_power(&env, member.voting_power, 0),
+            (false, true) => Self::adjust_total_voting_power(&env, 0, member.voting_power),
+            _ => {}
+        }
+
         member.is_active = status;
         env.storage()
             .instance()
@@ -105,6 +114,7 @@ impl MemberRegistryContract {
         member.role = role.clone();
 
         // Bonus: Role-based power multipliers
+        let previous_power = member.voting_power;
         member.voting_power = if role == Symbol::new(&env, "admin") {
             3
         } else if role == Symbol::new(&env, "moderator") {
@@ -112,6 +122,9 @@ impl MemberRegistryContract {
         } else {
             1
         };
+        if member.is_active {
+            Self::adjust_total_voting_power(&env, previous_power, member.voting_power);
+        }
 
         env.storage()
             .instance()
@@ -151,10 +164,26 @@ impl MemberRegistryContract {
             .unwrap_or(0)
     }
 
+    // Get the sum of the voting power of active members
+    pub fn get_total_voting_power(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalVotingPower)
+            .unwrap_or(0)
+    }
+
     // Helper function to check if address is a member
     fn is_member(env: &Env, address: &Address) -> bool {
         env.storage()
             .instance()
             .has(&DataKey::Member(address.clone()))
     }
+
+    // Helper function to replace a member's share of the total voting power
+    fn adjust_total_voting_power(env: &Env, previous: u32, power: u32) {
+        let total = Self::get_total_voting_power(env.clone()) - previous + power;
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalVotingPower, &total);
+    }
 }
@@ -142,6 +142,32 @@ fn test_get_total_members() {
     assert_eq!(client.get_total_members(), 2);
 }
 
+#[test]
+fn test_get_total_voting_power() {
+    let env = create_test_env();
+    let client = create_contract_client(&env);
+
+    let admin = Address::generate(&env);
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+
+    client.initialize(&admin);
+    client.register_member(&admin, &member1, &Symbol::new(&env, "member"));
+    client.register_member(&admin, &member2, &Symbol::new(&env, "member"));
+    assert_eq!(client.get_total_voting_power(), 2);
+
+    client.update_role(&admin, &member1, &Symbol::new(&env, "admin"));
+    assert_eq!(client.get_total_voting_power(), 4);
+
+    // Inactive members no longer count, and role changes while inactive are kept for later
+    client.update_status(&admin, &member1, &false);
+    assert_eq!(client.get_total_voting_power(), 1);
+    client.update_role(&admin, &member1, &Symbol::new(&env, "moderator"));
+    assert_eq!(client.get_total_voting_power(), 1);
+    client.update_status(&admin, &member1, &true);
+    assert_eq!(client.get_total_voting_power(), 3);
+}
+
 #[test]
 #[should_panic(expected = "Only admin can update member status")]
 fn test_update_status_non_admin() {
@@ -4,6 +4,7 @@ use soroban_sdk::{contracttype, Address, Symbol};
 pub enum DataKey {
     Member(Address), // Stores member data for a given address
     TotalMembers,    // Tracks total number of members
+    TotalVotingPower, // Sum of the voting power of active members
     Admin,           // Stores admin address
 }
 
@@ -0,0 +1,79 @@
+use soroban_sdk::{symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env, Symbol};
+
+use crate::datatypes::SignedBallot;
+use crate::errors::ProposalError;
+
+/// Strkey version byte of an ed25519 account public key (`G...` addresses).
+const ACCOUNT_VERSION_BYTE: u8 = 6 << 3;
+
+/// Alphabet of the RFC 4648 base32 encoding used by strkeys.
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Checks the signature and nonce of a ballot and returns the voter's account address.
+/// Consumes the nonce so the same ballot cannot be submitted twice.
+/// Panics if the signature does not match the ballot's public key.
+pub fn verify(env: &Env, ballot: &SignedBallot) -> Result<Address, ProposalError> {
+    let voter = account_address(env, &ballot.public_key);
+    let nonce = nonce(env, &voter);
+
+    if ballot.nonce != nonce {
+        return Err(ProposalError::InvalidNonce);
+    }
+
+    env.crypto().ed25519_verify(
+        &ballot.public_key,
+        &message(env, ballot.proposal_id, &ballot.choice, ballot.nonce),
+        &ballot.signature,
+    );
+
+    env.storage().persistent().set(&nonce_key(&voter), &(nonce + 1));
+
+    Ok(voter)
+}
+
+/// The bytes a voter signs: the XDR encoding of `(contract id, proposal id, choice, nonce)`.
+pub fn message(env: &Env, proposal_id: u32, choice: &Symbol, nonce: u64) -> Bytes {
+    (env.current_contract_address(), proposal_id, choice.clone(), nonce).to_xdr(env)
+}
+
+/// Next nonce expected in a signed ballot from `voter`, starting at 0.
+pub fn nonce(env: &Env, voter: &Address) -> u64 {
+    env.storage().persistent().get(&nonce_key(voter)).unwrap_or(0)
+}
+
+/// Returns the Stellar account address (`G...`) of an ed25519 public key.
+fn account_address(env: &Env, public_key: &BytesN<32>) -> Address {
+    let mut payload = [0u8; 35];
+    payload[0] = ACCOUNT_VERSION_BYTE;
+    payload[1..33].copy_from_slice(&public_key.to_array());
+    let checksum = crc16_xmodem(&payload[..33]);
+    payload[33..].copy_from_slice(&checksum.to_le_bytes());
+
+    let mut strkey = [0u8; 56];
+    for (i, chunk) in payload.chunks(5).enumerate() {
+        let bits = chunk.iter().fold(0u64, |acc, byte| (acc << 8) | *byte as u64);
+        for j in 0..8 {
+            strkey[i * 8 + j] = BASE32_ALPHABET[((bits >> (35 - 5 * j)) & 0x1f) as usize];
+        }
+    }
+
+    Address::from_string_bytes(&Bytes::from_slice(env, &strkey))
+}
+
+/// CRC-16/XMODEM checksum used by strkeys.
+fn crc16_xmodem(data: &[u8]) -> u16 {
+    data.iter().fold(0u16, |crc, byte| {
+        (0..8).fold(crc ^ ((*byte as u16) << 8), |crc, _| {
+            if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+/// Generate storage key for the signed-ballot nonce of a voter
+fn nonce_key(voter: &Address) -> (Symbol, Address) {
+    (symbol_short!("nonce"), voter.clone())
+}
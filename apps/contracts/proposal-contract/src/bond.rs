@@ -0,0 +1,114 @@
+use soroban_sdk::{symbol_short, token, vec, Address, Env, IntoVal, Symbol};
+
+use crate::datatypes::{BondConfig, BondStatus, ProposalBond, ProposalType};
+use crate::errors::ProposalError;
+
+/// Returns the bond configuration for a proposal type, if bonds are required for it.
+pub fn config_for(env: &Env, proposal_type: &ProposalType) -> Option<BondConfig> {
+    env.storage().persistent().get(&config_key(proposal_type))
+}
+
+/// Stores the bond configuration for a proposal type.
+pub fn set_config(env: &Env, proposal_type: &ProposalType, config: &BondConfig) {
+    env.storage().persistent().set(&config_key(proposal_type), config);
+}
+
+/// Returns the treasury contract that receives slashed bonds.
+pub fn treasury(env: &Env) -> Option<Address> {
+    env.storage().persistent().get(&symbol_short!("treasury"))
+}
+
+/// Stores the treasury contract that receives slashed bonds.
+pub fn set_treasury(env: &Env, treasury: &Address) {
+    env.storage().persistent().set(&symbol_short!("treasury"), treasury);
+}
+
+/// Returns the bond held for a proposal, if one was deposited.
+pub fn get_bond(env: &Env, proposal_id: u32) -> Option<ProposalBond> {
+    env.storage().persistent().get(&bond_key(proposal_id))
+}
+
+/// Transfers the configured bond from the proposer into escrow held by this contract.
+/// Does nothing when the proposal type has no bond configured.
+pub fn escrow(env: &Env, proposal_id: u32, proposal_type: &ProposalType, depositor: &Address) {
+    let Some(config) = config_for(env, proposal_type) else {
+        return;
+    };
+
+    token::Client::new(env, &config.token).transfer(
+        depositor,
+        &env.current_contract_address(),
+        &config.amount,
+    );
+
+    let bond = ProposalBond {
+        token: config.token,
+        amount: config.amount,
+        depositor: depositor.clone(),
+        slash_threshold_bps: config.slash_threshold_bps,
+        status: BondStatus::Escrowed,
+    };
+    env.storage().persistent().set(&bond_key(proposal_id), &bond);
+    env.events().publish((Symbol::new(env, "bond_deposited"),), (proposal_id, bond.amount));
+}
+
+/// Returns an escrowed bond to its depositor. Does nothing if there is no escrowed bond.
+pub fn refund(env: &Env, proposal_id: u32) {
+    let Some(mut bond) = escrowed_bond(env, proposal_id) else {
+        return;
+    };
+
+    token::Client::new(env, &bond.token).transfer(
+        &env.current_contract_address(),
+        &bond.depositor,
+        &bond.amount,
+    );
+
+    bond.status = BondStatus::Refunded;
+    env.storage().persistent().set(&bond_key(proposal_id), &bond);
+    env.events().publish((Symbol::new(env, "bond_refunded"),), (proposal_id, bond.amount));
+}
+
+/// Sends an escrowed bond to the treasury and records it as a treasury deposit.
+/// Does nothing if there is no escrowed bond.
+pub fn slash(env: &Env, proposal_id: u32) -> Result<(), ProposalError> {
+    let Some(mut bond) = escrowed_bond(env, proposal_id) else {
+        return Ok(());
+    };
+
+    let treasury = treasury(env).ok_or(ProposalError::TreasuryNotSet)?;
+    let this = env.current_contract_address();
+
+    token::Client::new(env, &bond.token).transfer(&this, &treasury, &bond.amount);
+    env.invoke_contract::<()>(
+        &treasury,
+        &Symbol::new(env, "deposit"),
+        vec![
+            env,
+            bond.token.into_val(env),
+            this.into_val(env),
+            bond.amount.into_val(env),
+        ],
+    );
+
+    bond.status = BondStatus::Slashed;
+    env.storage().persistent().set(&bond_key(proposal_id), &bond);
+    env.events().publish((Symbol::new(env, "bond_slashed"),), (proposal_id, bond.amount));
+
+    Ok(())
+}
+
+/// Returns the bond for a proposal only while it is still held in escrow.
+fn escrowed_bond(env: &Env, proposal_id: u32) -> Option<ProposalBond> {
+    get_bond(env, proposal_id).filter(|bond| bond.status == BondStatus::Escrowed)
+}
+
+/// Generate storage key for a proposal type's bond configuration
+fn config_key(proposal_type: &ProposalType) -> (Symbol, ProposalType) {
+    (symbol_short!("bond_cfg"), proposal_type.clone())
+}
+
+/// Generate storage key for the bond held by a proposal
+fn bond_key(proposal_id: u32) -> (Symbol, u32) {
+    (symbol_short!("bond"), proposal_id)
+}
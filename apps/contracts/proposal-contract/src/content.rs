@@ -0,0 +1,47 @@
+use soroban_sdk::{symbol_short, Env, Symbol, Vec};
+
+use crate::datatypes::{AmendmentPolicy, ContentVersion, ProposalType};
+use crate::MAX_PAGE_SIZE;
+
+/// Returns the amendment policy of a proposal type, if configured.
+pub fn policy(env: &Env, proposal_type: &ProposalType) -> Option<AmendmentPolicy> {
+    env.storage().persistent().get(&policy_key(proposal_type))
+}
+
+/// Stores the amendment policy of a proposal type.
+pub fn set_policy(env: &Env, proposal_type: &ProposalType, policy: &AmendmentPolicy) {
+    env.storage().persistent().set(&policy_key(proposal_type), policy);
+}
+
+/// Stores a content version of a proposal.
+pub fn record(env: &Env, proposal_id: u32, version: &ContentVersion) {
+    env.storage()
+        .persistent()
+        .set(&(symbol_short!("version"), proposal_id, version.version), version);
+}
+
+/// Returns a single content version of a proposal, if it exists.
+pub fn get(env: &Env, proposal_id: u32, version: u32) -> Option<ContentVersion> {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("version"), proposal_id, version))
+}
+
+/// Content versions of a proposal from oldest to newest, starting at version `start + 1`.
+pub fn history(env: &Env, proposal_id: u32, latest: u32, start: u32, limit: u32) -> Vec<ContentVersion> {
+    let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(latest);
+    let mut versions = Vec::new(env);
+
+    for version in start + 1..=end {
+        if let Some(content) = get(env, proposal_id, version) {
+            versions.push_back(content);
+        }
+    }
+
+    versions
+}
+
+/// Generate storage key for the amendment policy of a proposal type
+fn policy_key(proposal_type: &ProposalType) -> (Symbol, ProposalType) {
+    (symbol_short!("amend"), proposal_type.clone())
+}
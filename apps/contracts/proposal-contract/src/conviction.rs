@@ -0,0 +1,132 @@
+use soroban_sdk::{symbol_short, vec, Address, Env, IntoVal, Symbol};
+
+use crate::datatypes::{ConvictionConfig, FundingProposal};
+use crate::BPS_DENOMINATOR;
+
+/// Fixed-point scale of conviction: a conviction of `CONVICTION_SCALE` equals one unit of staked voting power.
+pub const CONVICTION_SCALE: u128 = 1_000_000_000;
+
+/// `2^(-1/2^k)` for k = 1..=20, scaled by `CONVICTION_SCALE`.
+const FRACTIONAL_DECAY: [u128; 20] = [
+    707_106_781, 840_896_415, 917_004_043, 957_603_281, 978_572_062, 989_228_013, 994_599_423,
+    997_296_056, 998_647_113, 999_323_328, 999_661_606, 999_830_789, 999_915_391, 999_957_695,
+    999_978_847, 999_989_423, 999_994_712, 999_997_356, 999_998_678, 999_999_339,
+];
+
+/// Returns the conviction voting configuration, if set.
+pub fn config(env: &Env) -> Option<ConvictionConfig> {
+    env.storage().persistent().get(&symbol_short!("cv_cfg"))
+}
+
+/// Stores the conviction voting configuration.
+pub fn set_config(env: &Env, config: &ConvictionConfig) {
+    env.storage().persistent().set(&symbol_short!("cv_cfg"), config);
+}
+
+/// Loads a funding proposal.
+pub fn load(env: &Env, id: u32) -> Option<FundingProposal> {
+    env.storage().persistent().get(&(symbol_short!("funding"), id))
+}
+
+/// Writes a funding proposal back to storage.
+pub fn save(env: &Env, proposal: &FundingProposal) {
+    env.storage().persistent().set(&(symbol_short!("funding"), proposal.id), proposal);
+}
+
+/// Returns the next available funding proposal ID and reserves it, starting at 1.
+pub fn next_id(env: &Env) -> u32 {
+    let id = env
+        .storage()
+        .persistent()
+        .get(&symbol_short!("fund_id"))
+        .unwrap_or(1);
+    env.storage().persistent().set(&symbol_short!("fund_id"), &(id + 1));
+    id
+}
+
+/// Voting power `staker` has staked on the funding proposal.
+pub fn stake_of(env: &Env, id: u32, staker: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("cv_stake"), id, staker.clone()))
+        .unwrap_or(0)
+}
+
+/// Voting power `staker` has staked across all funding proposals.
+pub fn total_stake_of(env: &Env, staker: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("cv_total"), staker.clone()))
+        .unwrap_or(0)
+}
+
+/// Records the stake of `staker` on a proposal, keeping their total across proposals in sync.
+pub fn set_stake(env: &Env, id: u32, staker: &Address, amount: u32) {
+    let previous = stake_of(env, id, staker);
+    let total = total_stake_of(env, staker) - previous + amount;
+
+    let storage = env.storage().persistent();
+    storage.set(&(symbol_short!("cv_stake"), id, staker.clone()), &amount);
+    storage.set(&(symbol_short!("cv_total"), staker.clone()), &total);
+}
+
+/// Brings the proposal's conviction up to date. Conviction moves towards the staked power,
+/// closing half of the remaining gap every `half_life` seconds.
+pub fn accrue(env: &Env, config: &ConvictionConfig, proposal: &mut FundingProposal) {
+    let now = env.ledger().timestamp();
+    let target = proposal.staked as u128 * CONVICTION_SCALE;
+    let decay = decay_factor(now - proposal.updated_at, config.half_life);
+
+    proposal.conviction = if proposal.conviction >= target {
+        target + (proposal.conviction - target) * decay / CONVICTION_SCALE
+    } else {
+        target - (target - proposal.conviction) * decay / CONVICTION_SCALE
+    };
+    proposal.updated_at = now;
+}
+
+/// Conviction a proposal needs to pass. The threshold grows with the share of the treasury's
+/// balance it requests, as `total_power * weight / (max_ratio - share)^2`, and is unreachable
+/// once the share reaches `max_ratio`.
+pub fn threshold(config: &ConvictionConfig, total_power: u32, requested: i128, balance: i128) -> u128 {
+    if balance <= 0 {
+        return u128::MAX;
+    }
+
+    let share_bps = (requested as u128 * BPS_DENOMINATOR as u128).div_ceil(balance as u128);
+    if share_bps >= config.max_ratio_bps as u128 {
+        return u128::MAX;
+    }
+
+    let gap_bps = config.max_ratio_bps as u128 - share_bps;
+    total_power as u128 * CONVICTION_SCALE * config.weight_bps as u128 * BPS_DENOMINATOR as u128
+        / (gap_bps * gap_bps)
+}
+
+/// Reads the treasury's balance of `asset`.
+pub fn treasury_balance(env: &Env, treasury: &Address, asset: &Address) -> i128 {
+    env.invoke_contract(
+        treasury,
+        &Symbol::new(env, "get_balance"),
+        vec![env, asset.into_val(env)],
+    )
+}
+
+/// `2^(-elapsed / half_life)` scaled by `CONVICTION_SCALE`.
+fn decay_factor(elapsed: u64, half_life: u64) -> u128 {
+    let halvings = elapsed / half_life;
+    if halvings >= 64 {
+        return 0;
+    }
+
+    let mut factor = CONVICTION_SCALE >> halvings;
+    let fraction = (((elapsed % half_life) as u128) << FRACTIONAL_DECAY.len()) / half_life as u128;
+
+    for (k, root) in FRACTIONAL_DECAY.iter().enumerate() {
+        if fraction & (1 << (FRACTIONAL_DECAY.len() - 1 - k)) != 0 {
+            factor = factor * root / CONVICTION_SCALE;
+        }
+    }
+
+    factor
+}
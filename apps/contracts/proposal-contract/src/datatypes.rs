@@ -1,6 +1,6 @@
-use soroban_sdk::{contracttype, Address, String};
+use soroban_sdk::{contracttype, Address, BytesN, String, Symbol};
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 #[contracttype]
 pub enum ProposalType {
     Treasury,
@@ -9,21 +9,100 @@ pub enum ProposalType {
     Technical
 }
 
-#[derive(Clone, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 #[contracttype]
 pub enum ProposalStatus {
-    Open,
-    Closed,
-    Accepted,
-    Rejected,
+    Draft,
+    Pending,
+    Active,
+    Succeeded,
+    Defeated,
+    Queued,
+    Executed,
+    Cancelled,
+    Vetoed,
+    Expired,
+}
+
+impl ProposalStatus {
+    /// Returns true if the lifecycle allows moving from `self` to `next`.
+    pub fn can_transition_to(&self, next: &ProposalStatus) -> bool {
+        use ProposalStatus::*;
+        matches!(
+            (self, next),
+            (Draft, Pending)
+                | (Draft, Cancelled)
+                | (Pending, Active)
+                | (Pending, Cancelled)
+                | (Pending, Vetoed)
+                | (Active, Succeeded)
+                | (Active, Defeated)
+                | (Active, Vetoed)
+                | (Succeeded, Queued)
+                | (Succeeded, Vetoed)
+                | (Succeeded, Expired)
+                | (Queued, Executed)
+                | (Queued, Vetoed)
+                | (Queued, Expired)
+        )
+    }
+
+    /// Returns true once voting has been decided and tallies can no longer change.
+    pub fn is_finalized(&self) -> bool {
+        matches!(
+            self,
+            ProposalStatus::Succeeded
+                | ProposalStatus::Defeated
+                | ProposalStatus::Queued
+                | ProposalStatus::Executed
+                | ProposalStatus::Expired
+        )
+    }
+}
+
+/// How votes on a proposal are weighted.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub enum TallyMode {
+    /// Every eligible address casts a single vote of weight one.
+    OnePerAddress,
+    /// Voters spend credits across options; each option receives the square root of its credits.
+    Quadratic,
+}
+
+/// Whether a proposal needs a regular vote or passes unless challenged.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub enum ProposalKind {
+    /// Decided by quorum and approval rules at the deadline.
+    Standard,
+    /// Passes at the deadline unless Against votes exceed the veto threshold before it.
+    Optimistic,
+    /// Fast-tracked by an emergency proposer under stricter quorum and approval rules.
+    Emergency,
 }
 
-#[derive(Clone)]
+/// Why a proposal was finalized when it was.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub enum FinalizationReason {
+    /// Not finalized yet.
+    None,
+    /// Finalized once voting ended.
+    Deadline,
+    /// Finalized early: For can no longer fall below the approval threshold.
+    ApprovalLocked,
+    /// Finalized early: For can no longer reach the approval threshold.
+    RejectionLocked,
+    /// Finalized early: quorum can no longer be reached. Emergency proposals only.
+    QuorumUnreachable,
+}
+
+#[derive(Clone, Debug, PartialEq)]
 #[contracttype]
 pub struct Proposal {
     pub id: u32,
     pub title: String,
-    pub description: String,
     pub created_at: u64,
     pub deadline: u64,
     pub proposal_type: ProposalType,
@@ -33,4 +112,309 @@ pub struct Proposal {
     pub abstain_votes: u32,
     pub created_by: Address,
     pub quorum: Option<u32>,
+    pub voting_starts_at: u64,
+    pub executable_at: u64,
+    pub total_voting_power: u32,
+    pub submitted_at: u64,
+    /// Members registered in the member registry when the proposal was submitted. Only they can
+    /// vote on it, so it bounds the votes still to come.
+    pub electorate: Option<u32>,
+    /// Seconds after the deadline during which committed votes can be revealed. Zero for open voting.
+    pub reveal_period: u64,
+    pub tally_mode: TallyMode,
+    pub kind: ProposalKind,
+    /// Seconds the deadline has been pushed back by late outcome-changing votes.
+    pub deadline_extension: u64,
+    /// Hash of the current off-chain content, such as an IPFS CID.
+    pub content_hash: String,
+    /// Number of content versions recorded, starting at 1 for the content the proposal was created with.
+    pub version: u32,
+    /// Oldest content version whose votes are still counted; earlier votes were reset.
+    pub counted_from_version: u32,
+    pub finalization_reason: FinalizationReason,
+}
+
+impl Proposal {
+    /// Returns true if votes are cast as hidden commitments and revealed after the deadline.
+    pub fn is_commit_reveal(&self) -> bool {
+        self.reveal_period > 0
+    }
+
+    /// Time after which no more votes can be counted: the deadline, or the end of the reveal window.
+    /// The sum always fits, as the deadline and reveal period are only accepted when it does.
+    pub fn voting_ends_at(&self) -> u64 {
+        self.deadline + self.reveal_period
+    }
+}
+
+/// Timing rules applied to every proposal once it leaves the Draft state.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct LifecycleConfig {
+    /// Seconds between submission and the start of voting.
+    pub voting_delay: u64,
+    /// Seconds a queued proposal must wait before it can be executed.
+    pub execution_delay: u64,
+    /// Seconds a succeeded or queued proposal stays executable before it expires.
+    pub execution_window: u64,
+}
+
+/// Admin-configured voting rules for a single proposal type.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct GovernanceParams {
+    /// Shortest allowed time between the start of voting and the deadline, in seconds.
+    pub min_voting_period: u64,
+    /// Longest allowed time between the start of voting and the deadline, in seconds.
+    pub max_voting_period: u64,
+    /// Votes required for quorum, in basis points of the total voting power.
+    pub quorum_bps: u32,
+    /// Share of For votes among For and Against votes that must be exceeded, in basis points.
+    pub approval_threshold_bps: u32,
+    /// Whether Abstain votes count toward quorum.
+    pub abstain_counts_toward_quorum: bool,
+}
+
+/// Deposit required to submit a proposal of a given type.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct BondConfig {
+    /// Token the bond is paid in.
+    pub token: Address,
+    /// Amount transferred into escrow when the proposal is created.
+    pub amount: i128,
+    /// Defeated proposals that missed quorum with a For share below this value, in basis points, lose their bond.
+    pub slash_threshold_bps: u32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub enum BondStatus {
+    Escrowed,
+    Refunded,
+    Slashed,
+}
+
+/// Bond held for a single proposal.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct ProposalBond {
+    pub token: Address,
+    pub amount: i128,
+    pub depositor: Address,
+    pub slash_threshold_bps: u32,
+    pub status: BondStatus,
+}
+
+/// Membership requirements checked against the member registry before proposing or voting.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct EligibilityConfig {
+    /// Member registry contract that must list the address as an active member.
+    pub member_registry: Address,
+    /// Minimum registry voting power required, 0 to disable.
+    pub min_voting_power: u32,
+    /// Voting NFT contract used for the category check.
+    pub voting_nft: Option<Address>,
+    /// NFT category the address must hold, if any.
+    pub required_nft_category: Option<Symbol>,
+}
+
+/// Member record as returned by the member registry's `get_member`.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct Member {
+    pub address: Address,
+    pub role: Symbol,
+    pub is_active: bool,
+    pub voting_power: u32,
+    pub joined_at: u64,
+}
+
+/// Token record as returned by the voting NFT contract's `get_nfts_by_owner`.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct VotingNFT {
+    pub token_id: u32,
+    pub category: Symbol,
+    pub metadata: Symbol,
+    pub owner: Address,
+    pub issued_at: u64,
+}
+
+/// Record of a single vote, stored per proposal and voter.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct VoteReceipt {
+    pub voter: Address,
+    pub choice: Symbol,
+    /// Weight the vote added to the tally: 1 for regular votes, or the quadratic weight across all
+    /// options scaled by `quadratic::WEIGHT_SCALE`.
+    pub weight: u64,
+    pub timestamp: u64,
+    /// Content version the vote was cast on. Votes on an older version than the proposal's are flagged.
+    pub content_version: u32,
+}
+
+/// A recorded revision of a proposal's off-chain content.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct ContentVersion {
+    pub version: u32,
+    pub content_hash: String,
+    pub timestamp: u64,
+}
+
+/// What happens to votes already cast when a proposal is amended during voting.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub enum AmendedVotes {
+    /// Tallies are cleared and earlier voters may vote again.
+    Reset,
+    /// Votes stay counted and their receipts show the older content version.
+    Flag,
+}
+
+/// Per-type rules for amending proposals after submission.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct AmendmentPolicy {
+    /// Seconds after voting opens during which the author may still amend.
+    pub discussion_period: u64,
+    pub amended_votes: AmendedVotes,
+}
+
+/// Fast-track rules for emergency proposals.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct EmergencyConfig {
+    /// Role manager contract holding the emergency proposer role.
+    pub role_manager: Address,
+    /// Role required to create emergency proposals.
+    pub role: Symbol,
+    /// Longest allowed voting period of an emergency proposal, in seconds.
+    pub max_voting_period: u64,
+    /// Minimum quorum in basis points; the proposal type's quorum applies if higher.
+    pub quorum_bps: u32,
+    /// Minimum approval threshold in basis points; the proposal type's threshold applies if higher.
+    pub approval_threshold_bps: u32,
+}
+
+/// Deadline extension applied when a late vote changes the leading outcome.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct AntiSnipingConfig {
+    /// Final seconds before the deadline in which an outcome-changing vote extends it. Zero disables the rule.
+    pub window: u64,
+    /// Seconds added to the deadline per extension.
+    pub extension: u64,
+    /// Largest total extension of a single proposal, in seconds.
+    pub max_extension: u64,
+}
+
+/// Challenge rules for optimistic proposals.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct OptimisticConfig {
+    /// Against votes, in basis points of the total voting power, that must be exceeded to object.
+    pub veto_threshold_bps: u32,
+    /// Seconds the regular vote runs after a successful objection.
+    pub objection_voting_period: u64,
+}
+
+/// Rules for conviction voting on funding proposals.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct ConvictionConfig {
+    /// Seconds for conviction to close half the gap to the currently staked power.
+    pub half_life: u64,
+    /// Largest share of the treasury balance a funding proposal may request, in basis points.
+    pub max_ratio_bps: u32,
+    /// Scales the passing threshold, in basis points of the total voting power.
+    pub weight_bps: u32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub enum FundingStatus {
+    Open,
+    Passed,
+    Cancelled,
+}
+
+/// A request for treasury funds decided by conviction voting instead of a deadline.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct FundingProposal {
+    pub id: u32,
+    pub title: String,
+    pub asset: Address,
+    pub requested_amount: i128,
+    pub beneficiary: Address,
+    pub created_by: Address,
+    pub created_at: u64,
+    pub status: FundingStatus,
+    /// Voting power currently staked on the proposal.
+    pub staked: u32,
+    /// Conviction as of `updated_at`, scaled by 10^9 per unit of voting power.
+    pub conviction: u128,
+    pub updated_at: u64,
+}
+
+/// Credit allowance for quadratic voting.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct QuadraticConfig {
+    /// Credits each voter may spend per proposal, or per epoch when `epoch_length` is set.
+    pub credits_per_voter: u32,
+    /// Length of a credit epoch in seconds. Zero gives every proposal its own allowance.
+    pub epoch_length: u64,
+}
+
+/// Credits a voter spent on each option of a quadratic proposal.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct QuadraticVote {
+    pub for_credits: u32,
+    pub against_credits: u32,
+    pub abstain_credits: u32,
+}
+
+/// Quadratic vote weights per option, in fixed point with six decimals.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct WeightedTally {
+    pub for_weight: u64,
+    pub against_weight: u64,
+    pub abstain_weight: u64,
+}
+
+/// A vote signed off-chain by the voter and submitted by a relayer.
+/// The signature covers the XDR encoding of `(contract id, proposal_id, choice, nonce)`.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct SignedBallot {
+    /// Ed25519 public key of the voter's Stellar account.
+    pub public_key: BytesN<32>,
+    pub proposal_id: u32,
+    pub choice: Symbol,
+    /// Must equal the voter's next nonce, see `get_ballot_nonce`.
+    pub nonce: u64,
+    pub signature: BytesN<64>,
+}
+
+/// Criteria for listing proposals. Unset fields match every proposal.
+/// Deadline bounds without a status, type or creator must both be set and at most 31 days apart.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct ProposalFilter {
+    /// Status symbol such as `active` or `succeeded`.
+    pub status: Option<Symbol>,
+    /// Proposal type symbol such as `treasury` or `governance`.
+    pub proposal_type: Option<Symbol>,
+    pub creator: Option<Address>,
+    /// Earliest deadline to include, inclusive.
+    pub deadline_from: Option<u64>,
+    /// Latest deadline to include, inclusive.
+    pub deadline_to: Option<u64>,
 }
@@ -0,0 +1,105 @@
+use soroban_sdk::{symbol_short, vec, Address, Env, IntoVal, InvokeError, Symbol, Val, Vec};
+
+use crate::datatypes::{EligibilityConfig, Member, VotingNFT};
+use crate::errors::ProposalError;
+
+/// Returns the eligibility requirements, if any have been configured.
+pub fn config(env: &Env) -> Option<EligibilityConfig> {
+    env.storage().persistent().get(&symbol_short!("elig_cfg"))
+}
+
+/// Stores the eligibility requirements.
+pub fn set_config(env: &Env, config: &EligibilityConfig) {
+    env.storage().persistent().set(&symbol_short!("elig_cfg"), config);
+}
+
+/// Verifies that `address` may propose or vote under the configured requirements.
+/// Checks active membership in the member registry, then the minimum voting power and
+/// the required voting NFT category when those are set. Passes when nothing is configured.
+pub fn require_eligible(env: &Env, address: &Address) -> Result<(), ProposalError> {
+    eligible_member(env, address).map(|_| ())
+}
+
+/// Verifies that `address` may vote on a proposal submitted at `submitted_at`. Besides the
+/// requirements of `require_eligible`, the member must have joined the registry by then, as
+/// members registered later are not part of the proposal's electorate.
+pub fn require_voter(env: &Env, address: &Address, submitted_at: u64) -> Result<(), ProposalError> {
+    match eligible_member(env, address)? {
+        Some(member) if member.joined_at > submitted_at => Err(ProposalError::NotInElectorate),
+        _ => Ok(()),
+    }
+}
+
+/// Checks the configured requirements for `address` and returns its registry entry.
+/// Returns `None` when no requirements are configured.
+fn eligible_member(env: &Env, address: &Address) -> Result<Option<Member>, ProposalError> {
+    let Some(config) = config(env) else {
+        return Ok(None);
+    };
+
+    let member = fetch_member(env, &config.member_registry, address)
+        .ok_or(ProposalError::NotActiveMember)?;
+
+    if !member.is_active {
+        return Err(ProposalError::NotActiveMember);
+    }
+
+    if member.voting_power < config.min_voting_power {
+        return Err(ProposalError::InsufficientVotingPower);
+    }
+
+    if let (Some(voting_nft), Some(category)) = (&config.voting_nft, &config.required_nft_category) {
+        let nfts: Vec<VotingNFT> = env.invoke_contract(
+            voting_nft,
+            &Symbol::new(env, "get_nfts_by_owner"),
+            vec![env, address.into_val(env)],
+        );
+
+        if !nfts.iter().any(|nft| nft.category == *category) {
+            return Err(ProposalError::MissingRequiredNft);
+        }
+    }
+
+    Ok(Some(member))
+}
+
+/// Voting power of an active member according to the member registry.
+/// Returns `None` when no registry is configured.
+pub fn voting_power(env: &Env, address: &Address) -> Result<Option<u32>, ProposalError> {
+    let Some(config) = config(env) else {
+        return Ok(None);
+    };
+
+    match fetch_member(env, &config.member_registry, address) {
+        Some(member) if member.is_active => Ok(Some(member.voting_power)),
+        _ => Err(ProposalError::NotActiveMember),
+    }
+}
+
+/// Number of members ever registered in the member registry. Snapshotted at submission, it bounds
+/// the addresses that can vote on a proposal.
+/// Returns `None` when no registry is configured or it cannot report its size.
+pub fn electorate(env: &Env) -> Option<u32> {
+    let config = config(env)?;
+    match env.try_invoke_contract::<u32, InvokeError>(
+        &config.member_registry,
+        &Symbol::new(env, "get_total_members"),
+        vec![env],
+    ) {
+        Ok(Ok(total)) => Some(total),
+        _ => None,
+    }
+}
+
+/// Looks up a member in the registry, treating a failed lookup as "not a member".
+fn fetch_member(env: &Env, registry: &Address, address: &Address) -> Option<Member> {
+    let args: Vec<Val> = vec![env, address.into_val(env)];
+    match env.try_invoke_contract::<Member, InvokeError>(
+        registry,
+        &Symbol::new(env, "get_member"),
+        args,
+    ) {
+        Ok(Ok(member)) => Some(member),
+        _ => None,
+    }
+}
@@ -14,4 +14,43 @@ pub enum ProposalError {
   InvalidVoteChoice = 8,
   AlreadyFinalized = 9,
   DeadlineNotReached = 10,
+  Unauthorized = 11,
+  AlreadyInitialized = 12,
+  NotInitialized = 13,
+  InvalidStatusTransition = 14,
+  VotingNotStarted = 15,
+  ExecutionDelayNotMet = 16,
+  ExecutionWindowPassed = 17,
+  ExecutionWindowOpen = 18,
+  InvalidConfig = 19,
+  ProposalNotEditable = 20,
+  InvalidVotingPeriod = 21,
+  TreasuryNotSet = 22,
+  NotActiveMember = 23,
+  InsufficientVotingPower = 24,
+  MissingRequiredNft = 25,
+  InvalidFilter = 26,
+  CommitRevealRequired = 27,
+  NotCommitReveal = 28,
+  NoCommitment = 29,
+  InvalidReveal = 30,
+  RevealWindowNotOpen = 31,
+  RevealWindowClosed = 32,
+  InvalidNonce = 33,
+  InvalidBatch = 34,
+  QuadraticVoteRequired = 35,
+  NotQuadratic = 36,
+  InsufficientCredits = 37,
+  CreditsNotConfigured = 38,
+  OptimisticNotConfigured = 39,
+  ConvictionNotConfigured = 40,
+  FundingRequestTooLarge = 41,
+  InsufficientStake = 42,
+  InvalidAmount = 43,
+  EmergencyNotConfigured = 44,
+  MissingEmergencyRole = 45,
+  VersionNotFound = 46,
+  EligibilityNotConfigured = 47,
+  TotalPowerNotSet = 48,
+  NotInElectorate = 49,
 }
@@ -0,0 +1,105 @@
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol};
+
+use crate::datatypes::{Proposal, ProposalStatus, ProposalType};
+
+/// Width of a deadline bucket in seconds (one day).
+pub const DEADLINE_BUCKET: u64 = 86_400;
+
+/// Secondary indexes over proposal IDs used by the filtered listing.
+#[derive(Clone)]
+#[contracttype]
+pub enum ProposalIndex {
+    Status(ProposalStatus),
+    Type(ProposalType),
+    Creator(Address),
+    DeadlineDay(u64),
+}
+
+/// Listing criteria with status and type symbols resolved to their enum values.
+pub struct ProposalQuery {
+    pub status: Option<ProposalStatus>,
+    pub proposal_type: Option<ProposalType>,
+    pub creator: Option<Address>,
+    pub deadline_from: Option<u64>,
+    pub deadline_to: Option<u64>,
+}
+
+impl ProposalQuery {
+    /// Returns true if the proposal satisfies every set criterion.
+    pub fn matches(&self, proposal: &Proposal) -> bool {
+        self.status.as_ref().is_none_or(|status| *status == proposal.status)
+            && self.proposal_type.as_ref().is_none_or(|t| *t == proposal.proposal_type)
+            && self.creator.as_ref().is_none_or(|creator| *creator == proposal.created_by)
+            && self.deadline_from.is_none_or(|from| proposal.deadline >= from)
+            && self.deadline_to.is_none_or(|to| proposal.deadline <= to)
+    }
+}
+
+/// Returns the index bucket holding proposals whose deadline falls on the same day as `deadline`.
+pub fn deadline_index(deadline: u64) -> ProposalIndex {
+    ProposalIndex::DeadlineDay(deadline / DEADLINE_BUCKET)
+}
+
+/// Number of proposal IDs in an index.
+pub fn len(env: &Env, index: &ProposalIndex) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&len_key(index))
+        .unwrap_or(0)
+}
+
+/// Proposal ID stored at `position` in an index.
+pub fn get(env: &Env, index: &ProposalIndex, position: u32) -> Option<u32> {
+    env.storage().persistent().get(&entry_key(index, position))
+}
+
+/// Appends a proposal ID to an index.
+pub fn add(env: &Env, index: &ProposalIndex, proposal_id: u32) {
+    let position = len(env, index);
+    let storage = env.storage().persistent();
+
+    storage.set(&entry_key(index, position), &proposal_id);
+    storage.set(&position_key(index, proposal_id), &position);
+    storage.set(&len_key(index), &(position + 1));
+}
+
+/// Removes a proposal ID from an index, shifting the later entries down so that the order
+/// paginated listings walk stays the same. Does nothing if the ID is not in the index.
+pub fn remove(env: &Env, index: &ProposalIndex, proposal_id: u32) {
+    let storage = env.storage().persistent();
+    let Some(position) = storage.get::<_, u32>(&position_key(index, proposal_id)) else {
+        return;
+    };
+
+    let last = len(env, index) - 1;
+    for slot in position..last {
+        let moved: u32 = storage.get(&entry_key(index, slot + 1)).unwrap();
+        storage.set(&entry_key(index, slot), &moved);
+        storage.set(&position_key(index, moved), &slot);
+    }
+
+    storage.remove(&entry_key(index, last));
+    storage.remove(&position_key(index, proposal_id));
+    storage.set(&len_key(index), &last);
+}
+
+/// Moves a proposal ID from one index to another.
+pub fn reindex(env: &Env, from: &ProposalIndex, to: &ProposalIndex, proposal_id: u32) {
+    remove(env, from, proposal_id);
+    add(env, to, proposal_id);
+}
+
+/// Generate storage key for the length of an index
+fn len_key(index: &ProposalIndex) -> (Symbol, ProposalIndex) {
+    (symbol_short!("idx_len"), index.clone())
+}
+
+/// Generate storage key for the entry at a position of an index
+fn entry_key(index: &ProposalIndex, position: u32) -> (Symbol, ProposalIndex, u32) {
+    (symbol_short!("idx"), index.clone(), position)
+}
+
+/// Generate storage key for the position of a proposal ID within an index
+fn position_key(index: &ProposalIndex, proposal_id: u32) -> (Symbol, ProposalIndex, u32) {
+    (symbol_short!("idx_pos"), index.clone(), proposal_id)
+}
@@ -1,34 +1,456 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, String, Symbol, Vec};
+use core::cmp::Ordering;
+
+use soroban_sdk::{
+    contract, contractimpl, symbol_short, vec, Address, Bytes, BytesN, Env, IntoVal, String, Symbol,
+    Vec,
+};
+
+mod ballot;
+mod bond;
+mod content;
+mod conviction;
 
 mod datatypes;
-use datatypes::{Proposal, ProposalStatus, ProposalType};
+use datatypes::{
+    AmendedVotes, AmendmentPolicy, AntiSnipingConfig, BondConfig, ContentVersion, ConvictionConfig, EligibilityConfig, EmergencyConfig, FinalizationReason, FundingProposal, FundingStatus,
+    GovernanceParams, LifecycleConfig, OptimisticConfig, Proposal, ProposalBond, ProposalFilter, ProposalKind, ProposalStatus, ProposalType, QuadraticConfig,
+    QuadraticVote, SignedBallot, TallyMode, VoteReceipt, WeightedTally,
+};
+
+mod eligibility;
+mod index;
+use index::{ProposalIndex, ProposalQuery};
+mod quadratic;
+mod votes;
 
 mod errors;
 use errors::ProposalError;
 
 mod test;
 
+/// Default number of seconds a succeeded proposal remains executable (7 days).
+const DEFAULT_EXECUTION_WINDOW: u64 = 604_800;
+
+/// Denominator for values expressed in basis points.
+const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Largest page returned by paginated queries.
+const MAX_PAGE_SIZE: u32 = 100;
+
+/// Largest number of ballots accepted in one `submit_signed_votes` call.
+const MAX_BALLOT_BATCH: u32 = 50;
+
+/// Widest deadline window, in daily buckets, served from the deadline index.
+const MAX_DEADLINE_BUCKETS: u64 = 31;
+
+/// Quorum and approval rules resolved for one proposal.
+struct TallyRules {
+    /// Votes counted towards quorum that must be reached.
+    quorum: u64,
+    approval_threshold_bps: u32,
+    abstain_counts: bool,
+}
+
 #[contract]
 pub struct ProposalContract;
 
 #[contractimpl]
 impl ProposalContract {
-    /// Creates a new proposal and stores it in persistent storage.
-    /// Requires authentication of the user creating the proposal.
-    /// Validates the deadline, title length, and proposal type.
+    /// Sets the admin address when the contract is deployed, so no one can claim it afterwards.
+    /// The admin configures lifecycle timings and the veto guardian.
+    pub fn __constructor(env: Env, admin: Address) {
+        env.storage().persistent().set(&symbol_short!("admin"), &admin);
+        env.events().publish((symbol_short!("init"),), admin);
+    }
+
+    /// Sets the guardian allowed to veto proposals. Admin only.
+    pub fn set_guardian(env: Env, caller: Address, guardian: Address) -> Result<(), ProposalError> {
+        Self::require_admin(&env, &caller)?;
+
+        env.storage().persistent().set(&symbol_short!("guardian"), &guardian);
+        env.events().publish((Symbol::new(&env, "guardian_set"),), guardian);
+
+        Ok(())
+    }
+
+    /// Updates the voting delay, execution delay and execution window. Admin only.
+    /// The execution window must be non-zero so that succeeded proposals can be executed.
+    pub fn set_lifecycle_config(
+        env: Env,
+        caller: Address,
+        config: LifecycleConfig,
+    ) -> Result<(), ProposalError> {
+        Self::require_admin(&env, &caller)?;
+
+        if config.execution_window == 0 {
+            return Err(ProposalError::InvalidConfig);
+        }
+
+        env.storage().persistent().set(&symbol_short!("lifecycle"), &config);
+        env.events().publish(
+            (Symbol::new(&env, "lifecycle_set"),),
+            (config.voting_delay, config.execution_delay, config.execution_window),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the lifecycle timings, falling back to no delays and a 7 day execution window.
+    pub fn get_lifecycle_config(env: Env) -> LifecycleConfig {
+        env.storage()
+            .persistent()
+            .get(&symbol_short!("lifecycle"))
+            .unwrap_or(LifecycleConfig {
+                voting_delay: 0,
+                execution_delay: 0,
+                execution_window: DEFAULT_EXECUTION_WINDOW,
+            })
+    }
+
+    /// Sets the voting rules for a proposal type. Admin only.
+    /// Voting periods must form a non-empty range, quorum is capped at 100%
+    /// and the approval threshold must be at least a simple majority.
+    /// A quorum percentage needs the total voting power to be set first.
+    pub fn set_governance_params(
+        env: Env,
+        caller: Address,
+        proposal_type_symbol: Symbol,
+        params: GovernanceParams,
+    ) -> Result<(), ProposalError> {
+        Self::require_admin(&env, &caller)?;
+
+        let proposal_type = Self::parse_proposal_type(&env, &proposal_type_symbol)?;
+
+        if params.max_voting_period == 0
+            || params.min_voting_period > params.max_voting_period
+            || params.quorum_bps as u64 > BPS_DENOMINATOR
+            || params.approval_threshold_bps < 5_000
+            || params.approval_threshold_bps as u64 >= BPS_DENOMINATOR
+        {
+            return Err(ProposalError::InvalidConfig);
+        }
+
+        if params.quorum_bps > 0 && Self::get_total_voting_power(env.clone()) == 0 {
+            return Err(ProposalError::TotalPowerNotSet);
+        }
+
+        env.storage().persistent().set(&Self::params_key(&proposal_type), &params);
+        env.events().publish((Symbol::new(&env, "params_set"),), proposal_type_symbol);
+
+        Ok(())
+    }
+
+    /// Returns the voting rules configured for a proposal type, if any.
+    /// Types without configured rules use a simple majority and the proposer's optional quorum.
+    pub fn get_governance_params(
+        env: Env,
+        proposal_type_symbol: Symbol,
+    ) -> Result<Option<GovernanceParams>, ProposalError> {
+        let proposal_type = Self::parse_proposal_type(&env, &proposal_type_symbol)?;
+        Ok(env.storage().persistent().get(&Self::params_key(&proposal_type)))
+    }
+
+    /// Sets the total voting power that quorum percentages are measured against. Admin only.
+    /// Proposals snapshot this value when they are submitted.
+    pub fn set_total_voting_power(env: Env, caller: Address, power: u32) -> Result<(), ProposalError> {
+        Self::require_admin(&env, &caller)?;
+
+        env.storage().persistent().set(&symbol_short!("tot_power"), &power);
+        env.events().publish((Symbol::new(&env, "total_power_set"),), power);
+
+        Ok(())
+    }
+
+    /// Returns the total voting power used for quorum calculations.
+    pub fn get_total_voting_power(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&symbol_short!("tot_power"))
+            .unwrap_or(0)
+    }
+
+    /// Sets the treasury contract that receives slashed proposal bonds. Admin only.
+    pub fn set_treasury(env: Env, caller: Address, treasury: Address) -> Result<(), ProposalError> {
+        Self::require_admin(&env, &caller)?;
+
+        bond::set_treasury(&env, &treasury);
+        env.events().publish((Symbol::new(&env, "treasury_set"),), treasury);
+
+        Ok(())
+    }
+
+    /// Returns the treasury contract that receives slashed proposal bonds.
+    pub fn get_treasury(env: Env) -> Option<Address> {
+        bond::treasury(&env)
+    }
+
+    /// Requires a token bond for new proposals of the given type. Admin only.
+    /// The treasury must be set first so that slashed bonds have somewhere to go.
+    pub fn set_bond_config(
+        env: Env,
+        caller: Address,
+        proposal_type_symbol: Symbol,
+        config: BondConfig,
+    ) -> Result<(), ProposalError> {
+        Self::require_admin(&env, &caller)?;
+
+        let proposal_type = Self::parse_proposal_type(&env, &proposal_type_symbol)?;
+
+        if bond::treasury(&env).is_none() {
+            return Err(ProposalError::TreasuryNotSet);
+        }
+
+        if config.amount <= 0 || config.slash_threshold_bps as u64 > BPS_DENOMINATOR {
+            return Err(ProposalError::InvalidConfig);
+        }
+
+        bond::set_config(&env, &proposal_type, &config);
+        env.events().publish(
+            (Symbol::new(&env, "bond_config_set"),),
+            (proposal_type_symbol, config.amount),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the bond required for proposals of the given type, if any.
+    pub fn get_bond_config(
+        env: Env,
+        proposal_type_symbol: Symbol,
+    ) -> Result<Option<BondConfig>, ProposalError> {
+        let proposal_type = Self::parse_proposal_type(&env, &proposal_type_symbol)?;
+        Ok(bond::config_for(&env, &proposal_type))
+    }
+
+    /// Returns the bond deposited for a proposal and whether it is escrowed, refunded or slashed.
+    pub fn get_bond(env: Env, proposal_id: u32) -> Option<ProposalBond> {
+        bond::get_bond(&env, proposal_id)
+    }
+
+    /// Sets the member registry and optional voting power or NFT requirements
+    /// that proposers and voters must satisfy. Admin only.
+    pub fn set_eligibility_config(
+        env: Env,
+        caller: Address,
+        config: EligibilityConfig,
+    ) -> Result<(), ProposalError> {
+        Self::require_admin(&env, &caller)?;
+
+        if config.voting_nft.is_some() != config.required_nft_category.is_some() {
+            return Err(ProposalError::InvalidConfig);
+        }
+
+        eligibility::set_config(&env, &config);
+        env.events().publish(
+            (Symbol::new(&env, "eligibility_set"),),
+            (config.member_registry, config.min_voting_power),
+        );
+
+        Ok(())
+    }
+
+    /// Sets the credit allowance voters spend on quadratic proposals. Admin only.
+    pub fn set_quadratic_config(
+        env: Env,
+        caller: Address,
+        config: QuadraticConfig,
+    ) -> Result<(), ProposalError> {
+        Self::require_admin(&env, &caller)?;
+
+        if config.credits_per_voter == 0 {
+            return Err(ProposalError::InvalidConfig);
+        }
+
+        quadratic::set_config(&env, &config);
+        env.events().publish(
+            (Symbol::new(&env, "quadratic_config_set"),),
+            (config.credits_per_voter, config.epoch_length),
+        );
+
+        Ok(())
+    }
+
+    /// Sets how long proposals of a type stay amendable after voting opens, and whether votes
+    /// cast before an amendment are reset or flagged. Admin only.
+    pub fn set_amendment_policy(
+        env: Env,
+        caller: Address,
+        proposal_type: Symbol,
+        policy: AmendmentPolicy,
+    ) -> Result<(), ProposalError> {
+        Self::require_admin(&env, &caller)?;
+
+        let parsed_type = Self::parse_proposal_type(&env, &proposal_type)?;
+        content::set_policy(&env, &parsed_type, &policy);
+        env.events().publish(
+            (Symbol::new(&env, "amendment_policy_set"),),
+            (proposal_type, policy.discussion_period),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the amendment policy of a proposal type, if configured.
+    pub fn get_amendment_policy(
+        env: Env,
+        proposal_type: Symbol,
+    ) -> Result<Option<AmendmentPolicy>, ProposalError> {
+        let parsed_type = Self::parse_proposal_type(&env, &proposal_type)?;
+        Ok(content::policy(&env, &parsed_type))
+    }
+
+    /// Sets the role that may create emergency proposals and the fast-track rules they follow. Admin only.
+    pub fn set_emergency_config(
+        env: Env,
+        caller: Address,
+        config: EmergencyConfig,
+    ) -> Result<(), ProposalError> {
+        Self::require_admin(&env, &caller)?;
+
+        if config.max_voting_period == 0
+            || config.quorum_bps == 0
+            || config.quorum_bps as u64 > BPS_DENOMINATOR
+            || config.approval_threshold_bps < 5_000
+            || config.approval_threshold_bps as u64 >= BPS_DENOMINATOR
+        {
+            return Err(ProposalError::InvalidConfig);
+        }
+
+        env.storage().persistent().set(&symbol_short!("emrg_cfg"), &config);
+        env.events().publish(
+            (Symbol::new(&env, "emergency_config_set"),),
+            (config.role, config.max_voting_period, config.quorum_bps, config.approval_threshold_bps),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the emergency proposal rules, if configured.
+    pub fn get_emergency_config(env: Env) -> Option<EmergencyConfig> {
+        env.storage().persistent().get(&symbol_short!("emrg_cfg"))
+    }
+
+    /// Sets the rule that extends a proposal's deadline when a vote in its final seconds changes
+    /// the leading outcome. A zero window disables the rule; otherwise both the extension and the
+    /// maximum total extension must be non-zero. Admin only.
+    pub fn set_anti_sniping_config(
+        env: Env,
+        caller: Address,
+        config: AntiSnipingConfig,
+    ) -> Result<(), ProposalError> {
+        Self::require_admin(&env, &caller)?;
+
+        if config.window > 0 && (config.extension == 0 || config.max_extension == 0) {
+            return Err(ProposalError::InvalidConfig);
+        }
+
+        env.storage().persistent().set(&symbol_short!("snipe_cfg"), &config);
+        env.events().publish(
+            (Symbol::new(&env, "anti_sniping_set"),),
+            (config.window, config.extension, config.max_extension),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the deadline extension rule, if configured.
+    pub fn get_anti_sniping_config(env: Env) -> Option<AntiSnipingConfig> {
+        env.storage().persistent().get(&symbol_short!("snipe_cfg"))
+    }
+
+    /// Sets the veto threshold and objection vote length for optimistic proposals. Admin only.
+    pub fn set_optimistic_config(
+        env: Env,
+        caller: Address,
+        config: OptimisticConfig,
+    ) -> Result<(), ProposalError> {
+        Self::require_admin(&env, &caller)?;
+
+        if config.veto_threshold_bps as u64 > BPS_DENOMINATOR || config.objection_voting_period == 0 {
+            return Err(ProposalError::InvalidConfig);
+        }
+
+        env.storage().persistent().set(&symbol_short!("opt_cfg"), &config);
+        env.events().publish(
+            (Symbol::new(&env, "optimistic_config_set"),),
+            (config.veto_threshold_bps, config.objection_voting_period),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the challenge rules for optimistic proposals, if configured.
+    pub fn get_optimistic_config(env: Env) -> Option<OptimisticConfig> {
+        env.storage().persistent().get(&symbol_short!("opt_cfg"))
+    }
+
+    /// Sets the half-life and threshold parameters of conviction voting. Admin only.
+    pub fn set_conviction_config(
+        env: Env,
+        caller: Address,
+        config: ConvictionConfig,
+    ) -> Result<(), ProposalError> {
+        Self::require_admin(&env, &caller)?;
+
+        if config.half_life == 0
+            || config.max_ratio_bps == 0
+            || config.max_ratio_bps as u64 > BPS_DENOMINATOR
+            || config.weight_bps == 0
+        {
+            return Err(ProposalError::InvalidConfig);
+        }
+
+        conviction::set_config(&env, &config);
+        env.events().publish(
+            (Symbol::new(&env, "conviction_config_set"),),
+            (config.half_life, config.max_ratio_bps, config.weight_bps),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the conviction voting parameters, if configured.
+    pub fn get_conviction_config(env: Env) -> Option<ConvictionConfig> {
+        conviction::config(&env)
+    }
+
+    /// Returns the quadratic voting credit allowance, if configured.
+    pub fn get_quadratic_config(env: Env) -> Option<QuadraticConfig> {
+        quadratic::config(&env)
+    }
+
+    /// Returns the membership requirements for proposers and voters, if configured.
+    pub fn get_eligibility_config(env: Env) -> Option<EligibilityConfig> {
+        eligibility::config(&env)
+    }
+
+    /// Creates a new proposal in the Draft state and stores it in persistent storage.
+    /// The proposal's content lives off-chain and is referenced by `content_hash`, such as an IPFS CID,
+    /// which is recorded as content version 1.
+    /// Requires authentication of the user creating the proposal, who must meet the eligibility requirements.
+    /// Validates the deadline, title length, content hash and proposal type.
+    /// Emergency proposals require the configured emergency proposer role.
+    /// Transfers the proposal type's bond, if configured, from the user into escrow.
     /// Emits a `proposal_created` event with the generated proposal ID.
+    #[allow(clippy::too_many_arguments)]
     pub fn create_proposal(
         env: Env,
         user: Address,
         title: String,
-        description: String,
+        content_hash: String,
         deadline: u64,
         proposal_type_symbol: Symbol,
         quorum: Option<u32>,
+        emergency: bool,
     ) -> Result<(), ProposalError> {
         user.require_auth();
+        eligibility::require_eligible(&env, &user)?;
+
+        if emergency {
+            Self::require_emergency_proposer(&env, &user)?;
+        }
 
         let now = env.ledger().timestamp();
 
@@ -40,189 +462,1716 @@ impl ProposalContract {
             return Err(ProposalError::InvalidTitleLength);
         }
 
-        let proposal_type = match proposal_type_symbol {
-            s if s == Symbol::new(&env, "treasury") => ProposalType::Treasury,
-            s if s == Symbol::new(&env, "governance") => ProposalType::Governance,
-            s if s == Symbol::new(&env, "community") => ProposalType::Community,
-            s if s == Symbol::new(&env, "technical") => ProposalType::Technical,
-            _ => return Err(ProposalError::InvalidProposalType),
-        };
+        if content_hash.is_empty() {
+            return Err(ProposalError::InvalidConfig);
+        }
+
+        let proposal_type = Self::parse_proposal_type(&env, &proposal_type_symbol)?;
 
         let id = Self::next_id(&env);
 
-        let proposal = Proposal {
+        let mut proposal = Proposal {
             id,
             title,
-            description,
             created_at: now,
             deadline,
             proposal_type,
-            status: ProposalStatus::Open,
+            status: ProposalStatus::Draft,
             for_votes: 0,
             against_votes: 0,
             abstain_votes: 0,
             created_by: user.clone(),
             quorum,
+            voting_starts_at: 0,
+            executable_at: 0,
+            total_voting_power: 0,
+            submitted_at: 0,
+            electorate: None,
+            reveal_period: 0,
+            tally_mode: TallyMode::OnePerAddress,
+            kind: if emergency { ProposalKind::Emergency } else { ProposalKind::Standard },
+            deadline_extension: 0,
+            content_hash: content_hash.clone(),
+            version: 0,
+            counted_from_version: 0,
+            finalization_reason: FinalizationReason::None,
         };
 
+        Self::record_content(&env, &mut proposal, &content_hash);
         env.storage().persistent().set(&Self::proposal_key(id), &proposal);
         Self::increment_id(&env);
+        index::add(&env, &ProposalIndex::Status(ProposalStatus::Draft), id);
+        index::add(&env, &ProposalIndex::Type(proposal.proposal_type.clone()), id);
+        index::add(&env, &ProposalIndex::Creator(user.clone()), id);
+        index::add(&env, &index::deadline_index(deadline), id);
         env.events().publish((Symbol::new(&env, "proposal_created"),), id);
 
+        bond::escrow(&env, id, &proposal.proposal_type, &user);
+
         Ok(())
     }
 
-    /// Allows a user to vote on a given proposal.
-    /// Prevents double voting and ensures the proposal is open and not expired.
-    /// Increments the appropriate vote count and stores the vote.
-    /// Emits a `vote_cast` event with the proposal ID, user, and vote type.
-    pub fn vote(
+    /// Edits the title, content hash and deadline of a Draft proposal.
+    /// Only the author can edit, and only while the proposal is still a Draft.
+    /// A new content hash is recorded as a new content version, as with `amend_proposal`.
+    /// Emits a `proposal_updated` event with the proposal ID.
+    pub fn update_proposal(
         env: Env,
         user: Address,
         proposal_id: u32,
-        vote_choice: Symbol,
+        title: String,
+        content_hash: String,
+        deadline: u64,
     ) -> Result<(), ProposalError> {
         user.require_auth();
 
-        let vote_key = (symbol_short!("vote"), proposal_id, user.clone());
-        if env.storage().persistent().has(&vote_key) {
-            return Err(ProposalError::AlreadyVoted);
+        let mut proposal = Self::load_proposal(&env, proposal_id)?;
+
+        if proposal.created_by != user {
+            return Err(ProposalError::Unauthorized);
         }
 
-        let mut proposal: Proposal = env
-            .storage()
-            .persistent()
-            .get(&Self::proposal_key(proposal_id))
-            .ok_or(ProposalError::ProposalNotFound)?;
+        if proposal.status != ProposalStatus::Draft {
+            return Err(ProposalError::ProposalNotEditable);
+        }
 
-        if proposal.status != ProposalStatus::Open {
-            return Err(ProposalError::ProposalNotOpen);
+        if deadline <= env.ledger().timestamp() || deadline.checked_add(proposal.reveal_period).is_none() {
+            return Err(ProposalError::InvalidDeadline);
         }
 
-        if env.ledger().timestamp() > proposal.deadline {
-            return Err(ProposalError::VotingClosed);
+        if title.is_empty() || title.len() > 100 {
+            return Err(ProposalError::InvalidTitleLength);
         }
 
-        let vote_choice_clone = vote_choice.clone();
+        if content_hash.is_empty() {
+            return Err(ProposalError::InvalidConfig);
+        }
 
-        match vote_choice {
-            s if s == symbol_short!("For") => proposal.for_votes += 1,
-            s if s == symbol_short!("Against") => proposal.against_votes += 1,
-            s if s == symbol_short!("Abstain") => proposal.abstain_votes += 1,
-            _ => return Err(ProposalError::InvalidVoteChoice),
+        index::reindex(
+            &env,
+            &index::deadline_index(proposal.deadline),
+            &index::deadline_index(deadline),
+            proposal_id,
+        );
+
+        proposal.title = title;
+        proposal.deadline = deadline;
+        if content_hash != proposal.content_hash {
+            Self::record_content(&env, &mut proposal, &content_hash);
         }
 
-        env.storage().persistent().set(&Self::proposal_key(proposal_id), &proposal);
-        env.storage().persistent().set(&vote_key, &vote_choice_clone);
-        env.events().publish((Symbol::new(&env, "vote_cast"),), (proposal_id, user, vote_choice_clone));
+        Self::save_proposal(&env, &proposal);
+        env.events().publish((Symbol::new(&env, "proposal_updated"),), proposal_id);
 
         Ok(())
     }
 
-    /// Finalizes a proposal by setting its status based on vote results and quorum (if defined).
-    /// Can only be called after the proposal deadline.
-    /// Emits a `proposal_finalized` event with the resulting status.
-    pub fn finalize(env: Env, proposal_id: u32) -> Result<(), ProposalError> {
-        let mut proposal: Proposal = env
-            .storage()
-            .persistent()
-            .get(&Self::proposal_key(proposal_id))
-            .ok_or(ProposalError::ProposalNotFound)?;
+    /// Records a new version of a proposal's off-chain content, identified by its hash (such as an IPFS CID).
+    /// Only the author can amend. Draft and Pending proposals can always be amended; Active ones only
+    /// during the type's discussion period, and only with open one-address-one-vote tallies. Votes
+    /// already cast are then reset or flagged according to the type's amendment policy.
+    /// Emits a `proposal_amended` event with the proposal ID, new version and content hash.
+    pub fn amend_proposal(
+        env: Env,
+        user: Address,
+        proposal_id: u32,
+        content_hash: String,
+    ) -> Result<(), ProposalError> {
+        user.require_auth();
 
-        if proposal.status != ProposalStatus::Open {
-            return Err(ProposalError::AlreadyFinalized);
+        let mut proposal = Self::load_proposal(&env, proposal_id)?;
+        Self::sync_status(&env, &mut proposal)?;
+
+        if proposal.created_by != user {
+            return Err(ProposalError::Unauthorized);
         }
 
-        if env.ledger().timestamp() < proposal.deadline {
-            return Err(ProposalError::DeadlineNotReached);
+        if content_hash.is_empty() {
+            return Err(ProposalError::InvalidConfig);
         }
 
-        let total_votes = proposal.for_votes + proposal.against_votes + proposal.abstain_votes;
-        let effective_votes = proposal.for_votes + proposal.against_votes;
+        match proposal.status {
+            ProposalStatus::Draft | ProposalStatus::Pending => {}
+            ProposalStatus::Active => {
+                let policy = content::policy(&env, &proposal.proposal_type)
+                    .ok_or(ProposalError::ProposalNotEditable)?;
 
-        proposal.status = if let Some(q) = proposal.quorum {
-            if total_votes < q {
-                ProposalStatus::Closed
-            } else if proposal.for_votes > proposal.against_votes && proposal.for_votes > 0 {
-                ProposalStatus::Accepted
-            } else {
-                ProposalStatus::Rejected
+                if env.ledger().timestamp() >= proposal.voting_starts_at.saturating_add(policy.discussion_period)
+                    || proposal.tally_mode == TallyMode::Quadratic
+                    || proposal.is_commit_reveal()
+                {
+                    return Err(ProposalError::ProposalNotEditable);
+                }
+
+                if policy.amended_votes == AmendedVotes::Reset {
+                    proposal.for_votes = 0;
+                    proposal.against_votes = 0;
+                    proposal.abstain_votes = 0;
+                    proposal.counted_from_version = proposal.version + 1;
+                }
             }
-        } else if effective_votes == 0 {
-            ProposalStatus::Rejected
-        } else if proposal.for_votes > proposal.against_votes && proposal.for_votes > 0 {
-            ProposalStatus::Accepted
-        } else {
-            ProposalStatus::Rejected
-        };
+            _ => return Err(ProposalError::ProposalNotEditable),
+        }
 
-        env.storage().persistent().set(&Self::proposal_key(proposal_id), &proposal);
-        env.events().publish((Symbol::new(&env, "proposal_finalized"),), (proposal_id, Self::status_to_symbol(&proposal.status)));
+        Self::record_content(&env, &mut proposal, &content_hash);
+        Self::save_proposal(&env, &proposal);
+        env.events().publish(
+            (Symbol::new(&env, "proposal_amended"),),
+            (proposal_id, proposal.version, content_hash),
+        );
 
         Ok(())
     }
 
-    /// Retrieves the vote counts for a given proposal.
-    /// Returns a tuple of (for_votes, against_votes, abstain_votes).
-    pub fn get_votes(env: Env, proposal_id: u32) -> (u32, u32, u32) {
-        let proposal: Proposal = env
-            .storage()
-            .persistent()
-            .get(&Self::proposal_key(proposal_id))
-            .unwrap_or_else(|| panic!("Proposal not found"));
-        (proposal.for_votes, proposal.against_votes, proposal.abstain_votes)
-    }
+    /// Switches a Draft proposal to commit-reveal voting, or back to open voting with a zero period.
+    /// Votes are then committed as `sha256(choice || salt)` before the deadline and revealed during
+    /// the `reveal_period` seconds that follow it. Only the author can change the mode.
+    /// Emits a `commit_reveal_set` event with the proposal ID and reveal period.
+    pub fn set_commit_reveal(
+        env: Env,
+        user: Address,
+        proposal_id: u32,
+        reveal_period: u64,
+    ) -> Result<(), ProposalError> {
+        user.require_auth();
 
-    /// Retrieves the full proposal object for the given ID.
-    pub fn get_proposal(env: Env, proposal_id: u32) -> Proposal {
-        env.storage()
-            .persistent()
-            .get(&Self::proposal_key(proposal_id))
-            .unwrap_or_else(|| panic!("Proposal not found"))
-    }
+        let mut proposal = Self::load_proposal(&env, proposal_id)?;
 
-    /// Returns all proposals stored in persistent storage.
-    /// Iterates from 1 to the current next_id (exclusive) and collects all existing proposals.
-    pub fn get_all_proposals(env: Env) -> Vec<Proposal> {
-        let mut proposals = Vec::new(&env);
-        let next_id = Self::next_id(&env);
+        if proposal.created_by != user {
+            return Err(ProposalError::Unauthorized);
+        }
 
-        for id in 1..next_id {
-            if let Some(proposal) = env.storage().persistent().get::<_, Proposal>(&Self::proposal_key(id)) {
-                proposals.push_back(proposal);
-            }
+        if proposal.status != ProposalStatus::Draft {
+            return Err(ProposalError::ProposalNotEditable);
         }
 
-        proposals
-    }
+        if reveal_period > 0
+            && (proposal.tally_mode == TallyMode::Quadratic || proposal.kind != ProposalKind::Standard)
+        {
+            return Err(ProposalError::InvalidConfig);
+        }
 
-    /// Returns the next available proposal ID from storage, starting at 1.
-    fn next_id(env: &Env) -> u32 {
-        env.storage()
-            .persistent()
-            .get(&symbol_short!("next_id"))
-            .unwrap_or(Some(1))
-            .unwrap()
+        if proposal.deadline.checked_add(reveal_period).is_none() {
+            return Err(ProposalError::InvalidConfig);
+        }
+
+        proposal.reveal_period = reveal_period;
+
+        Self::save_proposal(&env, &proposal);
+        env.events().publish((Symbol::new(&env, "commit_reveal_set"),), (proposal_id, reveal_period));
+
+        Ok(())
     }
 
-    /// Increments the proposal ID counter in persistent storage.
-    fn increment_id(env: &Env) {
-        let id = Self::next_id(env) + 1;
-        env.storage().persistent().set(&symbol_short!("next_id"), &id);
+    /// Selects how votes on a Draft proposal are tallied. Only the author can change the mode.
+    /// Quadratic proposals cannot also use commit-reveal voting.
+    /// Emits a `tally_mode_set` event with the proposal ID and mode.
+    pub fn set_tally_mode(
+        env: Env,
+        user: Address,
+        proposal_id: u32,
+        tally_mode: TallyMode,
+    ) -> Result<(), ProposalError> {
+        user.require_auth();
+
+        let mut proposal = Self::load_proposal(&env, proposal_id)?;
+
+        if proposal.created_by != user {
+            return Err(ProposalError::Unauthorized);
+        }
+
+        if proposal.status != ProposalStatus::Draft {
+            return Err(ProposalError::ProposalNotEditable);
+        }
+
+        if tally_mode == TallyMode::Quadratic && proposal.is_commit_reveal() {
+            return Err(ProposalError::InvalidConfig);
+        }
+
+        proposal.tally_mode = tally_mode.clone();
+
+        Self::save_proposal(&env, &proposal);
+        env.events().publish((Symbol::new(&env, "tally_mode_set"),), (proposal_id, tally_mode));
+
+        Ok(())
     }
 
-    /// Internal helper to create the key used to store/retrieve a proposal.
+    /// Makes a Draft proposal optimistic, or standard again. Only the author can change the kind.
+    /// Optimistic proposals pass at the deadline unless challenged, so they cannot hide votes with
+    /// commit-reveal voting and require the optimistic rules to be configured.
+    /// Emits a `proposal_kind_set` event with the proposal ID and kind.
+    pub fn set_proposal_kind(
+        env: Env,
+        user: Address,
+        proposal_id: u32,
+        kind: ProposalKind,
+    ) -> Result<(), ProposalError> {
+        user.require_auth();
+
+        let mut proposal = Self::load_proposal(&env, proposal_id)?;
+
+        if proposal.created_by != user {
+            return Err(ProposalError::Unauthorized);
+        }
+
+        if proposal.status != ProposalStatus::Draft {
+            return Err(ProposalError::ProposalNotEditable);
+        }
+
+        // Emergency status is granted at creation and cannot be toggled afterwards
+        if kind == ProposalKind::Emergency || proposal.kind == ProposalKind::Emergency {
+            return Err(ProposalError::InvalidConfig);
+        }
+
+        if kind == ProposalKind::Optimistic {
+            if Self::get_optimistic_config(env.clone()).is_none() {
+                return Err(ProposalError::OptimisticNotConfigured);
+            }
+            if proposal.is_commit_reveal() {
+                return Err(ProposalError::InvalidConfig);
+            }
+        }
+
+        proposal.kind = kind.clone();
+
+        Self::save_proposal(&env, &proposal);
+        env.events().publish((Symbol::new(&env, "proposal_kind_set"),), (proposal_id, kind));
+
+        Ok(())
+    }
+
+    /// Submits a Draft proposal for voting. Only the author can submit.
+    /// Voting opens after the configured voting delay; with no delay the proposal becomes Active immediately.
+    /// The voting period must respect the type's configured bounds. The total voting power and the
+    /// number of registered members are snapshotted; members who join later cannot vote on the proposal.
+    /// Emergency proposals skip the voting delay and only have to respect the emergency maximum period.
+    /// Proposals measured against a quorum percentage or veto threshold need a non-zero total voting power.
+    /// Emits `proposal_submitted`, and `proposal_activated` when voting opens right away.
+    pub fn submit_proposal(env: Env, user: Address, proposal_id: u32) -> Result<(), ProposalError> {
+        user.require_auth();
+
+        let mut proposal = Self::load_proposal(&env, proposal_id)?;
+
+        if proposal.created_by != user {
+            return Err(ProposalError::Unauthorized);
+        }
+
+        let emergency = proposal.kind == ProposalKind::Emergency;
+        let voting_delay = if emergency {
+            0
+        } else {
+            Self::get_lifecycle_config(env.clone()).voting_delay
+        };
+        let voting_starts_at = env
+            .ledger()
+            .timestamp()
+            .checked_add(voting_delay)
+            .ok_or(ProposalError::InvalidDeadline)?;
+
+        if proposal.deadline <= voting_starts_at {
+            return Err(ProposalError::InvalidDeadline);
+        }
+
+        if emergency {
+            let config = Self::get_emergency_config(env.clone()).ok_or(ProposalError::EmergencyNotConfigured)?;
+            if proposal.deadline - voting_starts_at > config.max_voting_period {
+                return Err(ProposalError::InvalidVotingPeriod);
+            }
+        } else if let Some(params) = Self::params_for(&env, &proposal.proposal_type) {
+            let voting_period = proposal.deadline - voting_starts_at;
+            if voting_period < params.min_voting_period || voting_period > params.max_voting_period {
+                return Err(ProposalError::InvalidVotingPeriod);
+            }
+        }
+
+        proposal.voting_starts_at = voting_starts_at;
+        proposal.total_voting_power = Self::get_total_voting_power(env.clone());
+        proposal.submitted_at = env.ledger().timestamp();
+        proposal.electorate = eligibility::electorate(&env);
+
+        // A quorum percentage of no voting power would let a single vote pass the proposal, and a
+        // veto threshold of no voting power would let a single objection challenge it
+        if proposal.total_voting_power == 0
+            && (proposal.kind == ProposalKind::Optimistic
+                || Self::governing_params(&env, &proposal).is_some_and(|params| params.quorum_bps > 0))
+        {
+            return Err(ProposalError::TotalPowerNotSet);
+        }
+
+        Self::transition(&env, &mut proposal, ProposalStatus::Pending)?;
+        Self::sync_status(&env, &mut proposal)?;
+        Self::save_proposal(&env, &proposal);
+
+        Ok(())
+    }
+
+    /// Cancels a Draft or Pending proposal. Only the author can cancel.
+    /// Any escrowed bond is slashed to the treasury.
+    /// Emits a `proposal_cancelled` event with the proposal ID.
+    pub fn cancel_proposal(env: Env, user: Address, proposal_id: u32) -> Result<(), ProposalError> {
+        user.require_auth();
+
+        let mut proposal = Self::load_proposal(&env, proposal_id)?;
+
+        if proposal.created_by != user {
+            return Err(ProposalError::Unauthorized);
+        }
+
+        Self::transition(&env, &mut proposal, ProposalStatus::Cancelled)?;
+        Self::save_proposal(&env, &proposal);
+        bond::slash(&env, proposal_id)?;
+
+        Ok(())
+    }
+
+    /// Opens a funding proposal decided by conviction voting, requesting `requested_amount` of `asset`
+    /// from the treasury for `beneficiary`. The request must stay below the configured maximum share
+    /// of the treasury's current balance, and the total voting power the threshold scales with must be set.
+    /// Emits a `funding_proposal_created` event with the funding proposal ID.
+    pub fn create_funding_proposal(
+        env: Env,
+        user: Address,
+        title: String,
+        asset: Address,
+        requested_amount: i128,
+        beneficiary: Address,
+    ) -> Result<(), ProposalError> {
+        user.require_auth();
+        eligibility::require_eligible(&env, &user)?;
+
+        let config = conviction::config(&env).ok_or(ProposalError::ConvictionNotConfigured)?;
+        let treasury = bond::treasury(&env).ok_or(ProposalError::TreasuryNotSet)?;
+
+        if title.is_empty() || title.len() > 100 {
+            return Err(ProposalError::InvalidTitleLength);
+        }
+
+        if requested_amount <= 0 {
+            return Err(ProposalError::InvalidAmount);
+        }
+
+        // The threshold scales with the total voting power, so without one any stake would pass
+        if Self::get_total_voting_power(env.clone()) == 0 {
+            return Err(ProposalError::TotalPowerNotSet);
+        }
+
+        let balance = conviction::treasury_balance(&env, &treasury, &asset);
+        if conviction::threshold(&config, 0, requested_amount, balance) == u128::MAX {
+            return Err(ProposalError::FundingRequestTooLarge);
+        }
+
+        let now = env.ledger().timestamp();
+        let proposal = FundingProposal {
+            id: conviction::next_id(&env),
+            title,
+            asset,
+            requested_amount,
+            beneficiary,
+            created_by: user,
+            created_at: now,
+            status: FundingStatus::Open,
+            staked: 0,
+            conviction: 0,
+            updated_at: now,
+        };
+
+        conviction::save(&env, &proposal);
+        env.events().publish((Symbol::new(&env, "funding_proposal_created"),), proposal.id);
+
+        Ok(())
+    }
+
+    /// Withdraws an open funding proposal. Only the author can cancel.
+    /// Stakes remain recorded until their owners unstake them.
+    /// Emits a `funding_proposal_cancelled` event with the funding proposal ID.
+    pub fn cancel_funding_proposal(env: Env, user: Address, funding_id: u32) -> Result<(), ProposalError> {
+        user.require_auth();
+
+        let mut proposal = conviction::load(&env, funding_id).ok_or(ProposalError::ProposalNotFound)?;
+
+        if proposal.created_by != user {
+            return Err(ProposalError::Unauthorized);
+        }
+
+        if proposal.status != FundingStatus::Open {
+            return Err(ProposalError::ProposalNotOpen);
+        }
+
+        proposal.status = FundingStatus::Cancelled;
+        conviction::save(&env, &proposal);
+        env.events().publish((Symbol::new(&env, "funding_proposal_cancelled"),), funding_id);
+
+        Ok(())
+    }
+
+    /// Stakes `amount` of the caller's voting power on an open funding proposal.
+    /// A member's stakes across all funding proposals cannot exceed their voting power in the member registry.
+    /// Fails with `EligibilityNotConfigured` when no member registry is set.
+    /// Emits a `conviction_staked` event with the funding proposal ID, user, and new stake,
+    /// and `funding_passed` if the proposal's conviction has already crossed its threshold.
+    pub fn stake_conviction(
+        env: Env,
+        user: Address,
+        funding_id: u32,
+        amount: u32,
+    ) -> Result<(), ProposalError> {
+        user.require_auth();
+        eligibility::require_eligible(&env, &user)?;
+
+        let power = eligibility::voting_power(&env, &user)?.ok_or(ProposalError::EligibilityNotConfigured)?;
+
+        if amount == 0 {
+            return Err(ProposalError::InvalidAmount);
+        }
+
+        if conviction::total_stake_of(&env, &user) as u64 + amount as u64 > power as u64 {
+            return Err(ProposalError::InsufficientVotingPower);
+        }
+
+        let mut proposal = conviction::load(&env, funding_id).ok_or(ProposalError::ProposalNotFound)?;
+
+        if proposal.status != FundingStatus::Open {
+            return Err(ProposalError::ProposalNotOpen);
+        }
+
+        Self::refresh_funding(&env, &mut proposal)?;
+
+        let stake = conviction::stake_of(&env, funding_id, &user) + amount;
+        conviction::set_stake(&env, funding_id, &user, stake);
+        proposal.staked += amount;
+        conviction::save(&env, &proposal);
+        env.events().publish((Symbol::new(&env, "conviction_staked"),), (funding_id, user, stake));
+
+        Ok(())
+    }
+
+    /// Withdraws `amount` of the caller's stake from a funding proposal, freeing that voting power.
+    /// Stakes can be withdrawn whatever the proposal's status.
+    /// Emits a `conviction_unstaked` event with the funding proposal ID, user, and remaining stake.
+    pub fn unstake_conviction(
+        env: Env,
+        user: Address,
+        funding_id: u32,
+        amount: u32,
+    ) -> Result<(), ProposalError> {
+        user.require_auth();
+
+        let stake = conviction::stake_of(&env, funding_id, &user);
+        if amount == 0 || amount > stake {
+            return Err(ProposalError::InsufficientStake);
+        }
+
+        let mut proposal = conviction::load(&env, funding_id).ok_or(ProposalError::ProposalNotFound)?;
+        Self::refresh_funding(&env, &mut proposal)?;
+
+        conviction::set_stake(&env, funding_id, &user, stake - amount);
+        proposal.staked -= amount;
+        conviction::save(&env, &proposal);
+        env.events().publish(
+            (Symbol::new(&env, "conviction_unstaked"),),
+            (funding_id, user, stake - amount),
+        );
+
+        Ok(())
+    }
+
+    /// Brings a funding proposal's conviction up to date and marks it Passed once it crosses its threshold.
+    /// Anyone can call this; staking and unstaking perform the same check.
+    /// Emits a `funding_passed` event with the funding proposal ID and conviction when it passes.
+    pub fn update_conviction(env: Env, funding_id: u32) -> Result<(), ProposalError> {
+        let mut proposal = conviction::load(&env, funding_id).ok_or(ProposalError::ProposalNotFound)?;
+        Self::refresh_funding(&env, &mut proposal)?;
+        conviction::save(&env, &proposal);
+
+        Ok(())
+    }
+
+    /// Moves a Pending proposal to Active once its voting delay has elapsed.
+    /// Voting and finalization perform this step automatically; this entrypoint lets anyone trigger it.
+    /// Emits a `proposal_activated` event with the proposal ID.
+    pub fn activate(env: Env, proposal_id: u32) -> Result<(), ProposalError> {
+        let mut proposal = Self::load_proposal(&env, proposal_id)?;
+
+        if proposal.status != ProposalStatus::Pending {
+            return Err(ProposalError::InvalidStatusTransition);
+        }
+
+        Self::sync_status(&env, &mut proposal)?;
+
+        if proposal.status == ProposalStatus::Pending {
+            return Err(ProposalError::VotingNotStarted);
+        }
+
+        Self::save_proposal(&env, &proposal);
+
+        Ok(())
+    }
+
+    /// Allows an eligible user to vote on a given proposal.
+    /// Prevents double voting and ensures the proposal is active and not expired.
+    /// Increments the appropriate vote count and stores a receipt with the choice, weight and timestamp.
+    /// Emits a `vote_cast` event with the proposal ID, user, and vote type.
+    pub fn vote(
+        env: Env,
+        user: Address,
+        proposal_id: u32,
+        vote_choice: Symbol,
+    ) -> Result<(), ProposalError> {
+        user.require_auth();
+        Self::cast_vote(&env, user, proposal_id, vote_choice)
+    }
+
+    /// Tallies a batch of ballots signed off-chain, so voters without funds can vote through a relayer.
+    /// Each ballot is signed with the voter's ed25519 account key over
+    /// `(contract id, proposal id, choice, nonce)` and must carry the voter's next nonce.
+    /// Ballots are counted exactly as if each voter had called `vote`; the batch is applied atomically.
+    /// Emits a `vote_cast` event per ballot.
+    pub fn submit_signed_votes(env: Env, ballots: Vec<SignedBallot>) -> Result<(), ProposalError> {
+        if ballots.is_empty() || ballots.len() > MAX_BALLOT_BATCH {
+            return Err(ProposalError::InvalidBatch);
+        }
+
+        for ballot in ballots.iter() {
+            let voter = ballot::verify(&env, &ballot)?;
+            Self::cast_vote(&env, voter, ballot.proposal_id, ballot.choice)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the nonce the next signed ballot of `voter` must carry.
+    pub fn get_ballot_nonce(env: Env, voter: Address) -> u64 {
+        ballot::nonce(&env, &voter)
+    }
+
+    /// Casts a quadratic vote, splitting credits from the voter's allowance across the options.
+    /// Each option receives a weight of the square root of the credits spent on it.
+    /// Emits a `quadratic_vote_cast` event with the proposal ID, user, and credits per option.
+    pub fn vote_quadratic(
+        env: Env,
+        user: Address,
+        proposal_id: u32,
+        vote: QuadraticVote,
+    ) -> Result<(), ProposalError> {
+        user.require_auth();
+
+        let mut proposal = Self::open_for_voting(&env, proposal_id)?;
+        eligibility::require_voter(&env, &user, proposal.submitted_at)?;
+
+        if votes::has_voted(&env, &proposal, &user) {
+            return Err(ProposalError::AlreadyVoted);
+        }
+
+        if proposal.tally_mode != TallyMode::Quadratic {
+            return Err(ProposalError::NotQuadratic);
+        }
+
+        let config = quadratic::config(&env).ok_or(ProposalError::CreditsNotConfigured)?;
+        let credits = vote.for_credits as u64 + vote.against_credits as u64 + vote.abstain_credits as u64;
+
+        if credits == 0 {
+            return Err(ProposalError::InvalidVoteChoice);
+        }
+
+        if credits > quadratic::remaining_credits(&env, &config, &user, proposal_id) as u64 {
+            return Err(ProposalError::InsufficientCredits);
+        }
+
+        let leading = Self::leading_outcome(&env, &proposal);
+
+        // Option counts track how many voters backed each option; weights decide the outcome
+        proposal.for_votes += (vote.for_credits > 0) as u32;
+        proposal.against_votes += (vote.against_credits > 0) as u32;
+        proposal.abstain_votes += (vote.abstain_credits > 0) as u32;
+
+        quadratic::spend(&env, &config, &user, proposal_id, credits as u32);
+        quadratic::record(&env, proposal_id, &user, &vote);
+        Self::check_sniping(&env, &mut proposal, leading)?;
+        Self::check_objection(&env, &mut proposal)?;
+        Self::save_proposal(&env, &proposal);
+        votes::record(
+            &env,
+            proposal_id,
+            &VoteReceipt {
+                voter: user.clone(),
+                choice: symbol_short!("Quadratic"),
+                weight: quadratic::vote_weight(&vote),
+                timestamp: env.ledger().timestamp(),
+                content_version: proposal.version,
+            },
+        );
+        env.events().publish(
+            (Symbol::new(&env, "quadratic_vote_cast"),),
+            (proposal_id, user, (vote.for_credits, vote.against_credits, vote.abstain_credits)),
+        );
+
+        Ok(())
+    }
+
+    /// Commits a hidden vote on a commit-reveal proposal before its deadline.
+    /// `commitment` is `sha256(choice || salt)`, where `choice` is the UTF-8 name of the vote choice
+    /// (`For`, `Against` or `Abstain`) and `salt` the 32 bytes later passed to `reveal_vote`.
+    /// Emits a `vote_committed` event with the proposal ID and user.
+    pub fn commit_vote(
+        env: Env,
+        user: Address,
+        proposal_id: u32,
+        commitment: BytesN<32>,
+    ) -> Result<(), ProposalError> {
+        user.require_auth();
+
+        if votes::get_commitment(&env, proposal_id, &user).is_some() {
+            return Err(ProposalError::AlreadyVoted);
+        }
+
+        let proposal = Self::open_for_voting(&env, proposal_id)?;
+        eligibility::require_voter(&env, &user, proposal.submitted_at)?;
+
+        if proposal.tally_mode == TallyMode::Quadratic {
+            return Err(ProposalError::QuadraticVoteRequired);
+        }
+
+        if !proposal.is_commit_reveal() {
+            return Err(ProposalError::NotCommitReveal);
+        }
+
+        Self::save_proposal(&env, &proposal);
+        votes::commit(&env, proposal_id, &user, &commitment);
+        env.events().publish((Symbol::new(&env, "vote_committed"),), (proposal_id, user));
+
+        Ok(())
+    }
+
+    /// Reveals a committed vote during the reveal window that follows the deadline.
+    /// The choice and salt must hash to the stored commitment; the vote is then counted and a receipt stored.
+    /// Emits a `vote_revealed` event with the proposal ID, user, and vote type.
+    pub fn reveal_vote(
+        env: Env,
+        user: Address,
+        proposal_id: u32,
+        vote_choice: Symbol,
+        salt: BytesN<32>,
+    ) -> Result<(), ProposalError> {
+        user.require_auth();
+
+        let mut proposal = Self::load_proposal(&env, proposal_id)?;
+
+        if !proposal.is_commit_reveal() {
+            return Err(ProposalError::NotCommitReveal);
+        }
+
+        if proposal.status != ProposalStatus::Active {
+            return Err(ProposalError::ProposalNotOpen);
+        }
+
+        let now = env.ledger().timestamp();
+        if now <= proposal.deadline {
+            return Err(ProposalError::RevealWindowNotOpen);
+        }
+        if now > proposal.voting_ends_at() {
+            return Err(ProposalError::RevealWindowClosed);
+        }
+
+        let commitment =
+            votes::get_commitment(&env, proposal_id, &user).ok_or(ProposalError::NoCommitment)?;
+
+        if votes::has_voted(&env, &proposal, &user) {
+            return Err(ProposalError::AlreadyVoted);
+        }
+
+        let mut preimage = Bytes::from_slice(&env, Self::choice_name(&vote_choice)?);
+        preimage.append(&salt.into());
+
+        if BytesN::from(env.crypto().sha256(&preimage)) != commitment {
+            return Err(ProposalError::InvalidReveal);
+        }
+
+        Self::count_vote(&env, &mut proposal, &user, &vote_choice)?;
+        env.events().publish((Symbol::new(&env, "vote_revealed"),), (proposal_id, user, vote_choice));
+
+        Ok(())
+    }
+
+    /// Finalizes an Active proposal as Succeeded or Defeated.
+    /// Applies the governance parameters of the proposal type when configured, otherwise a simple
+    /// majority with the proposer's optional quorum. Unchallenged optimistic proposals succeed.
+    /// Can be called after the proposal deadline, or after the reveal window for commit-reveal
+    /// proposals, where only revealed votes are counted.
+    /// When the member registry bounds the electorate, it can also be called before the deadline
+    /// once quorum is met and the registered members that have not voted yet can no longer change
+    /// the outcome. Emergency proposals may additionally be defeated early when quorum is out of
+    /// reach. Optimistic proposals always wait for the deadline.
+    /// The reason is recorded as `finalization_reason`.
+    /// Refunds the bond when quorum was reached; otherwise the bond is slashed if support fell
+    /// below the type's slash threshold.
+    /// Emits a `proposal_finalized` event with the resulting status, and for commit-reveal
+    /// proposals a `votes_unrevealed` event with the number of commitments that were never
+    /// revealed.
+    pub fn finalize(env: Env, proposal_id: u32) -> Result<(), ProposalError> {
+        let mut proposal = Self::load_proposal(&env, proposal_id)?;
+        Self::sync_status(&env, &mut proposal)?;
+
+        if proposal.status.is_finalized() {
+            return Err(ProposalError::AlreadyFinalized);
+        }
+
+        if proposal.status != ProposalStatus::Active {
+            return Err(ProposalError::InvalidStatusTransition);
+        }
+
+        let now = env.ledger().timestamp();
+        let voting_over = if proposal.is_commit_reveal() {
+            now > proposal.voting_ends_at()
+        } else {
+            now >= proposal.deadline
+        };
+
+        let (quorum_met, approved, reason) = if voting_over {
+            let (quorum_met, approved) = Self::evaluate_tally(&env, &proposal);
+            (quorum_met, approved, FinalizationReason::Deadline)
+        } else {
+            match Self::decided_outcome(&env, &proposal) {
+                Some((quorum_met, approved, reason))
+                    if quorum_met || proposal.kind == ProposalKind::Emergency =>
+                {
+                    (quorum_met, approved, reason)
+                }
+                _ => return Err(ProposalError::DeadlineNotReached),
+            }
+        };
+        proposal.finalization_reason = reason;
+
+        let next = if quorum_met && approved {
+            ProposalStatus::Succeeded
+        } else {
+            ProposalStatus::Defeated
+        };
+
+        Self::transition(&env, &mut proposal, next)?;
+        Self::save_proposal(&env, &proposal);
+
+        if proposal.is_commit_reveal() {
+            env.events().publish(
+                (Symbol::new(&env, "votes_unrevealed"),),
+                (proposal_id, Self::get_unrevealed_count(env.clone(), proposal_id)),
+            );
+        }
+
+        if quorum_met || !Self::below_slash_threshold(&env, &proposal) {
+            bond::refund(&env, proposal_id);
+        } else {
+            bond::slash(&env, proposal_id)?;
+        }
+
+        Ok(())
+    }
+
+    /// Queues a Succeeded proposal for execution after the configured execution delay.
+    /// Fails once the execution window following the end of voting has passed.
+    /// Emits a `proposal_queued` event with the proposal ID.
+    pub fn queue(env: Env, proposal_id: u32) -> Result<(), ProposalError> {
+        let mut proposal = Self::load_proposal(&env, proposal_id)?;
+        let config = Self::get_lifecycle_config(env.clone());
+        let now = env.ledger().timestamp();
+
+        if proposal.status == ProposalStatus::Succeeded
+            && now > Self::window_end(proposal.voting_ends_at(), config.execution_window)?
+        {
+            return Err(ProposalError::ExecutionWindowPassed);
+        }
+
+        proposal.executable_at = Self::window_end(now, config.execution_delay)?;
+        Self::transition(&env, &mut proposal, ProposalStatus::Queued)?;
+        Self::save_proposal(&env, &proposal);
+
+        Ok(())
+    }
+
+    /// Marks a Queued proposal as Executed once its execution delay has elapsed.
+    /// Fails if the execution window has already passed.
+    /// Emits a `proposal_executed` event with the proposal ID.
+    pub fn execute(env: Env, proposal_id: u32) -> Result<(), ProposalError> {
+        let mut proposal = Self::load_proposal(&env, proposal_id)?;
+        let config = Self::get_lifecycle_config(env.clone());
+        let now = env.ledger().timestamp();
+
+        if proposal.status == ProposalStatus::Queued {
+            if now < proposal.executable_at {
+                return Err(ProposalError::ExecutionDelayNotMet);
+            }
+            if now > Self::window_end(proposal.executable_at, config.execution_window)? {
+                return Err(ProposalError::ExecutionWindowPassed);
+            }
+        }
+
+        Self::transition(&env, &mut proposal, ProposalStatus::Executed)?;
+        Self::save_proposal(&env, &proposal);
+
+        Ok(())
+    }
+
+    /// Vetoes a proposal that has not yet been executed. Guardian only.
+    /// Any escrowed bond is returned to the proposer.
+    /// Emits a `proposal_vetoed` event with the proposal ID.
+    pub fn veto(env: Env, guardian: Address, proposal_id: u32) -> Result<(), ProposalError> {
+        guardian.require_auth();
+
+        let stored: Option<Address> = env.storage().persistent().get(&symbol_short!("guardian"));
+        if stored != Some(guardian) {
+            return Err(ProposalError::Unauthorized);
+        }
+
+        let mut proposal = Self::load_proposal(&env, proposal_id)?;
+        Self::transition(&env, &mut proposal, ProposalStatus::Vetoed)?;
+        Self::save_proposal(&env, &proposal);
+        bond::refund(&env, proposal_id);
+
+        Ok(())
+    }
+
+    /// Expires a Succeeded or Queued proposal whose execution window has passed.
+    /// Can be called by anyone.
+    /// Emits a `proposal_expired` event with the proposal ID.
+    pub fn expire(env: Env, proposal_id: u32) -> Result<(), ProposalError> {
+        let mut proposal = Self::load_proposal(&env, proposal_id)?;
+        let config = Self::get_lifecycle_config(env.clone());
+
+        let expires_at = match proposal.status {
+            ProposalStatus::Succeeded => Self::window_end(proposal.voting_ends_at(), config.execution_window)?,
+            ProposalStatus::Queued => Self::window_end(proposal.executable_at, config.execution_window)?,
+            _ => return Err(ProposalError::InvalidStatusTransition),
+        };
+
+        if env.ledger().timestamp() <= expires_at {
+            return Err(ProposalError::ExecutionWindowOpen);
+        }
+
+        Self::transition(&env, &mut proposal, ProposalStatus::Expired)?;
+        Self::save_proposal(&env, &proposal);
+
+        Ok(())
+    }
+
+    /// Returns a single content version of a proposal.
+    pub fn get_content_version(
+        env: Env,
+        proposal_id: u32,
+        version: u32,
+    ) -> Result<ContentVersion, ProposalError> {
+        Self::load_proposal(&env, proposal_id)?;
+        content::get(&env, proposal_id, version).ok_or(ProposalError::VersionNotFound)
+    }
+
+    /// Returns the content versions of a proposal from oldest to newest, skipping the first `start`.
+    pub fn get_content_history(
+        env: Env,
+        proposal_id: u32,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<ContentVersion>, ProposalError> {
+        let proposal = Self::load_proposal(&env, proposal_id)?;
+        Ok(content::history(&env, proposal_id, proposal.version, start, limit))
+    }
+
+    /// Returns a funding proposal with its conviction accrued to the current time.
+    pub fn get_funding_proposal(env: Env, funding_id: u32) -> Result<FundingProposal, ProposalError> {
+        let mut proposal = conviction::load(&env, funding_id).ok_or(ProposalError::ProposalNotFound)?;
+        if proposal.status == FundingStatus::Open {
+            let config = conviction::config(&env).ok_or(ProposalError::ConvictionNotConfigured)?;
+            conviction::accrue(&env, &config, &mut proposal);
+        }
+        Ok(proposal)
+    }
+
+    /// Returns the conviction a funding proposal needs to pass, given the treasury's current balance.
+    pub fn get_conviction_threshold(env: Env, funding_id: u32) -> Result<u128, ProposalError> {
+        let proposal = conviction::load(&env, funding_id).ok_or(ProposalError::ProposalNotFound)?;
+        Self::funding_threshold(&env, &proposal)
+    }
+
+    /// Returns the voting power `staker` has staked on a funding proposal.
+    pub fn get_conviction_stake(env: Env, funding_id: u32, staker: Address) -> u32 {
+        conviction::stake_of(&env, funding_id, &staker)
+    }
+
+    /// Returns the vote weights of a proposal in fixed point with six decimals.
+    /// One-address-one-vote proposals report each vote as a weight of one.
+    pub fn get_weighted_votes(env: Env, proposal_id: u32) -> Result<WeightedTally, ProposalError> {
+        let proposal = Self::load_proposal(&env, proposal_id)?;
+        Ok(Self::weighted_tally(&env, &proposal))
+    }
+
+    /// Returns how `voter` split their credits on a quadratic proposal, if they voted.
+    pub fn get_quadratic_vote(env: Env, proposal_id: u32, voter: Address) -> Option<QuadraticVote> {
+        quadratic::get_vote(&env, proposal_id, &voter)
+    }
+
+    /// Returns the credits `voter` can still spend on a proposal under the current allowance.
+    pub fn get_remaining_credits(
+        env: Env,
+        voter: Address,
+        proposal_id: u32,
+    ) -> Result<u32, ProposalError> {
+        let config = quadratic::config(&env).ok_or(ProposalError::CreditsNotConfigured)?;
+        Ok(quadratic::remaining_credits(&env, &config, &voter, proposal_id))
+    }
+
+    /// Returns the vote commitment of `voter` on a commit-reveal proposal, if they committed.
+    pub fn get_commitment(env: Env, proposal_id: u32, voter: Address) -> Option<BytesN<32>> {
+        votes::get_commitment(&env, proposal_id, &voter)
+    }
+
+    /// Returns the number of commitments on a proposal that have not been revealed.
+    pub fn get_unrevealed_count(env: Env, proposal_id: u32) -> u32 {
+        votes::commitment_count(&env, proposal_id).saturating_sub(votes::voter_count(&env, proposal_id))
+    }
+
+    /// Returns the vote receipt of `voter` on a proposal, if they voted.
+    pub fn get_vote(env: Env, proposal_id: u32, voter: Address) -> Option<VoteReceipt> {
+        votes::get_receipt(&env, proposal_id, &voter)
+    }
+
+    /// Returns up to `limit` voters of a proposal in voting order, starting at index `start`.
+    /// Pages are capped at 100 entries.
+    pub fn get_voters(env: Env, proposal_id: u32, start: u32, limit: u32) -> Vec<Address> {
+        votes::voters(&env, proposal_id, start, limit)
+    }
+
+    /// Returns the number of addresses that voted on a proposal.
+    pub fn get_voter_count(env: Env, proposal_id: u32) -> u32 {
+        votes::voter_count(&env, proposal_id)
+    }
+
+    /// Returns up to `limit` proposal IDs `voter` has voted on, starting at index `start`.
+    /// Pages are capped at 100 entries.
+    pub fn get_voted_proposals(env: Env, voter: Address, start: u32, limit: u32) -> Vec<u32> {
+        votes::voted_proposals(&env, &voter, start, limit)
+    }
+
+    /// Returns the number of proposals `voter` has voted on.
+    pub fn get_voted_proposal_count(env: Env, voter: Address) -> u32 {
+        votes::voted_count(&env, &voter)
+    }
+
+    /// Retrieves the vote counts for a given proposal.
+    /// Returns a tuple of (for_votes, against_votes, abstain_votes).
+    pub fn get_votes(env: Env, proposal_id: u32) -> Result<(u32, u32, u32), ProposalError> {
+        let proposal = Self::load_proposal(&env, proposal_id)?;
+        Ok((proposal.for_votes, proposal.against_votes, proposal.abstain_votes))
+    }
+
+    /// Retrieves the full proposal object for the given ID.
+    pub fn get_proposal(env: Env, proposal_id: u32) -> Result<Proposal, ProposalError> {
+        Self::load_proposal(&env, proposal_id)
+    }
+
+    /// Returns up to `limit` proposals matching `filter`, skipping the first `start` matches.
+    /// Candidates come from the smallest matching secondary index (status, type, creator or
+    /// deadline day) and the remaining criteria are checked per proposal. Pages are capped at 100 entries.
+    /// A deadline window without a status, type or creator must set both bounds and span at most
+    /// 31 days, otherwise `InvalidFilter` is returned instead of scanning every proposal.
+    pub fn list_proposals(
+        env: Env,
+        filter: ProposalFilter,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<Proposal>, ProposalError> {
+        let query = Self::parse_filter(&env, filter)?;
+        let source = Self::select_source(&env, &query);
+
+        // Only filters without any criterion may walk every proposal, since all of them match
+        if source.is_none() && (query.deadline_from.is_some() || query.deadline_to.is_some()) {
+            return Err(ProposalError::InvalidFilter);
+        }
+
+        let limit = limit.min(MAX_PAGE_SIZE);
+        let mut page = Vec::new(&env);
+        if limit == 0 {
+            return Ok(page);
+        }
+
+        let mut skipped = 0u32;
+        let mut visit = |id: u32| -> bool {
+            if let Some(proposal) = env.storage().persistent().get::<_, Proposal>(&Self::proposal_key(id)) {
+                if query.matches(&proposal) {
+                    if skipped < start {
+                        skipped += 1;
+                    } else {
+                        page.push_back(proposal);
+                    }
+                }
+            }
+            page.len() < limit
+        };
+
+        match source {
+            Some(indexes) => {
+                'outer: for idx in indexes.iter() {
+                    for position in 0..index::len(&env, &idx) {
+                        let id = index::get(&env, &idx, position).unwrap();
+                        if !visit(id) {
+                            break 'outer;
+                        }
+                    }
+                }
+            }
+            None => {
+                for id in 1..Self::next_id(&env) {
+                    if !visit(id) {
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(page)
+    }
+
+    /// Returns up to `limit` proposals in ID order, skipping the first `start`.
+    /// Pages are capped at 100 entries.
+    pub fn get_all_proposals(env: Env, start: u32, limit: u32) -> Vec<Proposal> {
+        let mut page = Vec::new(&env);
+        let end = start.saturating_add(limit.min(MAX_PAGE_SIZE));
+
+        for id in start.saturating_add(1)..Self::next_id(&env).min(end.saturating_add(1)) {
+            if let Some(proposal) = env.storage().persistent().get::<_, Proposal>(&Self::proposal_key(id)) {
+                page.push_back(proposal);
+            }
+        }
+
+        page
+    }
+
+    /// Counts a vote from an authenticated or signature-verified user on an open-voting proposal.
+    fn cast_vote(
+        env: &Env,
+        user: Address,
+        proposal_id: u32,
+        vote_choice: Symbol,
+    ) -> Result<(), ProposalError> {
+        let mut proposal = Self::open_for_voting(env, proposal_id)?;
+        eligibility::require_voter(env, &user, proposal.submitted_at)?;
+
+        if votes::has_voted(env, &proposal, &user) {
+            return Err(ProposalError::AlreadyVoted);
+        }
+
+        if proposal.tally_mode == TallyMode::Quadratic {
+            return Err(ProposalError::QuadraticVoteRequired);
+        }
+
+        if proposal.is_commit_reveal() {
+            return Err(ProposalError::CommitRevealRequired);
+        }
+
+        Self::count_vote(env, &mut proposal, &user, &vote_choice)?;
+        env.events().publish((Symbol::new(env, "vote_cast"),), (proposal_id, user, vote_choice));
+
+        Ok(())
+    }
+
+    /// Accrues the conviction of an open funding proposal and marks it Passed once it reaches its threshold.
+    fn refresh_funding(env: &Env, proposal: &mut FundingProposal) -> Result<(), ProposalError> {
+        if proposal.status != FundingStatus::Open {
+            return Ok(());
+        }
+
+        let config = conviction::config(env).ok_or(ProposalError::ConvictionNotConfigured)?;
+        conviction::accrue(env, &config, proposal);
+
+        let threshold = Self::funding_threshold(env, proposal)?;
+        if threshold > 0 && proposal.conviction >= threshold {
+            proposal.status = FundingStatus::Passed;
+            env.events().publish(
+                (Symbol::new(env, "funding_passed"),),
+                (proposal.id, proposal.conviction),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Conviction threshold of a funding proposal against the treasury's current balance.
+    fn funding_threshold(env: &Env, proposal: &FundingProposal) -> Result<u128, ProposalError> {
+        let config = conviction::config(env).ok_or(ProposalError::ConvictionNotConfigured)?;
+        let treasury = bond::treasury(env).ok_or(ProposalError::TreasuryNotSet)?;
+        let balance = conviction::treasury_balance(env, &treasury, &proposal.asset);
+
+        let total_power = Self::get_total_voting_power(env.clone());
+        if total_power == 0 {
+            return Err(ProposalError::TotalPowerNotSet);
+        }
+
+        Ok(conviction::threshold(&config, total_power, proposal.requested_amount, balance))
+    }
+
+    /// Loads a proposal that is accepting votes, activating it first if its voting delay has elapsed.
+    fn open_for_voting(env: &Env, proposal_id: u32) -> Result<Proposal, ProposalError> {
+        let mut proposal = Self::load_proposal(env, proposal_id)?;
+        Self::sync_status(env, &mut proposal)?;
+
+        if proposal.status == ProposalStatus::Pending {
+            return Err(ProposalError::VotingNotStarted);
+        }
+
+        if proposal.status != ProposalStatus::Active {
+            return Err(ProposalError::ProposalNotOpen);
+        }
+
+        if env.ledger().timestamp() > proposal.deadline {
+            return Err(ProposalError::VotingClosed);
+        }
+
+        Ok(proposal)
+    }
+
+    /// Adds a vote to the proposal's tally, saves it, and stores the voter's receipt.
+    fn count_vote(
+        env: &Env,
+        proposal: &mut Proposal,
+        user: &Address,
+        vote_choice: &Symbol,
+    ) -> Result<(), ProposalError> {
+        let leading = Self::leading_outcome(env, proposal);
+
+        match vote_choice.clone() {
+            s if s == symbol_short!("For") => proposal.for_votes += 1,
+            s if s == symbol_short!("Against") => proposal.against_votes += 1,
+            s if s == symbol_short!("Abstain") => proposal.abstain_votes += 1,
+            _ => return Err(ProposalError::InvalidVoteChoice),
+        }
+
+        Self::check_sniping(env, proposal, leading)?;
+        Self::check_objection(env, proposal)?;
+        Self::save_proposal(env, proposal);
+        votes::record(
+            env,
+            proposal.id,
+            &VoteReceipt {
+                voter: user.clone(),
+                choice: vote_choice.clone(),
+                weight: 1,
+                timestamp: env.ledger().timestamp(),
+                content_version: proposal.version,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Which side currently leads: For ahead (`Greater`), Against ahead (`Less`) or a tie.
+    fn leading_outcome(env: &Env, proposal: &Proposal) -> Ordering {
+        let tally = Self::weighted_tally(env, proposal);
+        tally.for_weight.cmp(&tally.against_weight)
+    }
+
+    /// Extends the deadline when a vote cast in the final seconds changed the leading outcome,
+    /// up to the configured maximum total extension.
+    /// Reveals on commit-reveal proposals happen after the deadline and never extend it.
+    /// Emits a `deadline_extended` event with the proposal ID and new deadline.
+    fn check_sniping(env: &Env, proposal: &mut Proposal, leading_before: Ordering) -> Result<(), ProposalError> {
+        let Some(config) = Self::get_anti_sniping_config(env.clone()) else {
+            return Ok(());
+        };
+
+        let now = env.ledger().timestamp();
+        if config.window == 0
+            || proposal.is_commit_reveal()
+            || now > proposal.deadline
+            || now.saturating_add(config.window) < proposal.deadline
+            || Self::leading_outcome(env, proposal) == leading_before
+        {
+            return Ok(());
+        }
+
+        let extension = config
+            .extension
+            .min(config.max_extension.saturating_sub(proposal.deadline_extension));
+        if extension == 0 {
+            return Ok(());
+        }
+
+        let deadline = proposal
+            .deadline
+            .checked_add(extension)
+            .ok_or(ProposalError::InvalidDeadline)?;
+        index::reindex(
+            env,
+            &index::deadline_index(proposal.deadline),
+            &index::deadline_index(deadline),
+            proposal.id,
+        );
+        proposal.deadline = deadline;
+        proposal.deadline_extension += extension;
+
+        env.events().publish((Symbol::new(env, "deadline_extended"),), (proposal.id, deadline));
+
+        Ok(())
+    }
+
+    /// Converts an optimistic proposal into a regular vote once its Against weight exceeds the
+    /// veto threshold. The votes already cast carry over and the deadline moves to the end of the
+    /// objection voting period, unless the original deadline is later.
+    /// Emits a `proposal_challenged` event with the proposal ID and new deadline.
+    fn check_objection(env: &Env, proposal: &mut Proposal) -> Result<(), ProposalError> {
+        if proposal.kind != ProposalKind::Optimistic {
+            return Ok(());
+        }
+
+        let Some(config) = Self::get_optimistic_config(env.clone()) else {
+            return Ok(());
+        };
+
+        let against_weight = Self::weighted_tally(env, proposal).against_weight as u128;
+        let veto_weight = proposal.total_voting_power as u128
+            * config.veto_threshold_bps as u128
+            * quadratic::WEIGHT_SCALE as u128
+            / BPS_DENOMINATOR as u128;
+
+        if against_weight <= veto_weight {
+            return Ok(());
+        }
+
+        let deadline = env
+            .ledger()
+            .timestamp()
+            .checked_add(config.objection_voting_period)
+            .ok_or(ProposalError::InvalidDeadline)?
+            .max(proposal.deadline);
+        index::reindex(
+            env,
+            &index::deadline_index(proposal.deadline),
+            &index::deadline_index(deadline),
+            proposal.id,
+        );
+        proposal.kind = ProposalKind::Standard;
+        proposal.deadline = deadline;
+
+        env.events().publish((Symbol::new(env, "proposal_challenged"),), (proposal.id, deadline));
+
+        Ok(())
+    }
+
+    /// Verifies through the role manager that `user` holds the emergency proposer role.
+    fn require_emergency_proposer(env: &Env, user: &Address) -> Result<(), ProposalError> {
+        let config = Self::get_emergency_config(env.clone()).ok_or(ProposalError::EmergencyNotConfigured)?;
+
+        let has_role: bool = env.invoke_contract(
+            &config.role_manager,
+            &Symbol::new(env, "has_role"),
+            vec![env, user.into_val(env), config.role.into_val(env)],
+        );
+
+        if !has_role {
+            return Err(ProposalError::MissingEmergencyRole);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the UTF-8 name of a vote choice as hashed into commitments.
+    fn choice_name(vote_choice: &Symbol) -> Result<&'static [u8], ProposalError> {
+        match vote_choice.clone() {
+            s if s == symbol_short!("For") => Ok(b"For"),
+            s if s == symbol_short!("Against") => Ok(b"Against"),
+            s if s == symbol_short!("Abstain") => Ok(b"Abstain"),
+            _ => Err(ProposalError::InvalidVoteChoice),
+        }
+    }
+
+    /// Applies quorum and approval rules to the current tally of a proposal.
+    /// Returns whether quorum was met and whether the votes cast approve the proposal.
+    /// Quadratic proposals are approved on weights, while quorum counts the addresses that voted.
+    /// Optimistic proposals that were never challenged need neither quorum nor approval.
+    fn evaluate_tally(env: &Env, proposal: &Proposal) -> (bool, bool) {
+        if proposal.kind == ProposalKind::Optimistic {
+            return (true, true);
+        }
+
+        let rules = Self::tally_rules(env, proposal);
+        let tally = Self::weighted_tally(env, proposal);
+
+        (
+            Self::turnout(env, proposal, rules.abstain_counts) >= rules.quorum,
+            Self::exceeds_threshold(tally.for_weight as u128, tally.against_weight as u128, &rules),
+        )
+    }
+
+    /// Returns the final `(quorum_met, approved, reason)` of an open proposal if no distribution of
+    /// the votes still possible could change it, or `None` while the outcome is open.
+    /// Only members registered at submission can vote once eligibility is configured, so every one of
+    /// them that has not voted yet is assumed to cast one full-weight vote. Without eligibility any
+    /// address can vote and the outcome stays open until the deadline.
+    fn decided_outcome(env: &Env, proposal: &Proposal) -> Option<(bool, bool, FinalizationReason)> {
+        if proposal.kind == ProposalKind::Optimistic {
+            return None;
+        }
+
+        eligibility::config(env)?;
+        let electorate = proposal.electorate?;
+
+        let rules = Self::tally_rules(env, proposal);
+        let tally = Self::weighted_tally(env, proposal);
+
+        let max_weight = match proposal.tally_mode {
+            TallyMode::OnePerAddress => quadratic::WEIGHT_SCALE,
+            TallyMode::Quadratic => quadratic::weight(quadratic::config(env)?.credits_per_voter),
+        };
+        let voted = match proposal.tally_mode {
+            TallyMode::OnePerAddress => {
+                (proposal.for_votes + proposal.against_votes + proposal.abstain_votes) as u64
+            }
+            TallyMode::Quadratic => votes::voter_count(env, proposal.id) as u64,
+        };
+        let remaining = (electorate as u64).saturating_sub(voted);
+        let remaining_weight = remaining as u128 * max_weight as u128;
+
+        let (for_weight, against_weight) = (tally.for_weight as u128, tally.against_weight as u128);
+        let turnout = Self::turnout(env, proposal, rules.abstain_counts);
+        let quorum_met = turnout >= rules.quorum;
+
+        // Passes even if all remaining members vote Against
+        if quorum_met && Self::exceeds_threshold(for_weight, against_weight + remaining_weight, &rules) {
+            return Some((true, true, FinalizationReason::ApprovalLocked));
+        }
+
+        // Fails even if all remaining members vote For
+        if !Self::exceeds_threshold(for_weight + remaining_weight, against_weight, &rules) {
+            return Some((quorum_met, false, FinalizationReason::RejectionLocked));
+        }
+
+        if turnout + remaining < rules.quorum {
+            return Some((false, false, FinalizationReason::QuorumUnreachable));
+        }
+
+        None
+    }
+
+    /// Governance parameters a proposal is tallied under: the type's parameters, tightened by the
+    /// emergency rules for emergency proposals.
+    fn governing_params(env: &Env, proposal: &Proposal) -> Option<GovernanceParams> {
+        let mut params = Self::params_for(env, &proposal.proposal_type);
+
+        if proposal.kind == ProposalKind::Emergency {
+            if let Some(emergency) = Self::get_emergency_config(env.clone()) {
+                params = Some(GovernanceParams {
+                    min_voting_period: 0,
+                    max_voting_period: emergency.max_voting_period,
+                    quorum_bps: emergency
+                        .quorum_bps
+                        .max(params.as_ref().map_or(0, |p| p.quorum_bps)),
+                    approval_threshold_bps: emergency
+                        .approval_threshold_bps
+                        .max(params.as_ref().map_or(0, |p| p.approval_threshold_bps)),
+                    abstain_counts_toward_quorum: params.is_some_and(|p| p.abstain_counts_toward_quorum),
+                });
+            }
+        }
+
+        params
+    }
+
+    /// Quorum and approval rules of a proposal under its governing parameters. Without parameters,
+    /// a simple majority applies with the proposer's quorum counting all votes, or at least one
+    /// decisive vote when none was set.
+    fn tally_rules(env: &Env, proposal: &Proposal) -> TallyRules {
+        match Self::governing_params(env, proposal) {
+            // The proposer may ask for a stricter quorum but never a looser one
+            Some(params) => TallyRules {
+                quorum: Self::required_quorum(proposal.total_voting_power, params.quorum_bps)
+                    .max(proposal.quorum.unwrap_or(0) as u64),
+                approval_threshold_bps: params.approval_threshold_bps,
+                abstain_counts: params.abstain_counts_toward_quorum,
+            },
+            None => TallyRules {
+                quorum: proposal.quorum.unwrap_or(1) as u64,
+                approval_threshold_bps: 5_000,
+                abstain_counts: proposal.quorum.is_some(),
+            },
+        }
+    }
+
+    /// Votes counted towards quorum: addresses that voted on quadratic proposals, otherwise For and
+    /// Against votes plus Abstain votes when `include_abstain` is set.
+    fn turnout(env: &Env, proposal: &Proposal, include_abstain: bool) -> u64 {
+        match proposal.tally_mode {
+            TallyMode::Quadratic => votes::voter_count(env, proposal.id) as u64,
+            TallyMode::OnePerAddress if include_abstain => {
+                (proposal.for_votes + proposal.against_votes + proposal.abstain_votes) as u64
+            }
+            TallyMode::OnePerAddress => (proposal.for_votes + proposal.against_votes) as u64,
+        }
+    }
+
+    /// Returns true if the For weight exceeds the approval threshold of the decisive weight.
+    fn exceeds_threshold(for_weight: u128, against_weight: u128, rules: &TallyRules) -> bool {
+        for_weight > 0
+            && for_weight * BPS_DENOMINATOR as u128
+                > rules.approval_threshold_bps as u128 * (for_weight + against_weight)
+    }
+
+    /// Returns true if the For share of decisive votes is below the slash threshold recorded with the bond.
+    /// Proposals without decisive votes count as having no support.
+    fn below_slash_threshold(env: &Env, proposal: &Proposal) -> bool {
+        let Some(bond) = bond::get_bond(env, proposal.id) else {
+            return false;
+        };
+
+        let tally = Self::weighted_tally(env, proposal);
+        let decisive_weight = tally.for_weight as u128 + tally.against_weight as u128;
+        if decisive_weight == 0 {
+            return bond.slash_threshold_bps > 0;
+        }
+
+        (tally.for_weight as u128) * (BPS_DENOMINATOR as u128)
+            < bond.slash_threshold_bps as u128 * decisive_weight
+    }
+
+    /// Vote weights of a proposal: quadratic weights, or one weight unit per vote otherwise.
+    fn weighted_tally(env: &Env, proposal: &Proposal) -> WeightedTally {
+        match proposal.tally_mode {
+            TallyMode::Quadratic => quadratic::tally(env, proposal.id),
+            TallyMode::OnePerAddress => WeightedTally {
+                for_weight: proposal.for_votes as u64 * quadratic::WEIGHT_SCALE,
+                against_weight: proposal.against_votes as u64 * quadratic::WEIGHT_SCALE,
+                abstain_weight: proposal.abstain_votes as u64 * quadratic::WEIGHT_SCALE,
+            },
+        }
+    }
+
+    /// Number of votes needed to reach `quorum_bps` of `total_power`, rounded up.
+    fn required_quorum(total_power: u32, quorum_bps: u32) -> u64 {
+        (total_power as u64 * quorum_bps as u64).div_ceil(BPS_DENOMINATOR)
+    }
+
+    /// Maps a proposal type symbol to its `ProposalType`.
+    fn parse_proposal_type(env: &Env, symbol: &Symbol) -> Result<ProposalType, ProposalError> {
+        match symbol.clone() {
+            s if s == Symbol::new(env, "treasury") => Ok(ProposalType::Treasury),
+            s if s == Symbol::new(env, "governance") => Ok(ProposalType::Governance),
+            s if s == Symbol::new(env, "community") => Ok(ProposalType::Community),
+            s if s == Symbol::new(env, "technical") => Ok(ProposalType::Technical),
+            _ => Err(ProposalError::InvalidProposalType),
+        }
+    }
+
+    /// Returns the configured governance parameters for a proposal type.
+    fn params_for(env: &Env, proposal_type: &ProposalType) -> Option<GovernanceParams> {
+        env.storage().persistent().get(&Self::params_key(proposal_type))
+    }
+
+    /// Resolves the status and type symbols of a filter and validates its deadline window.
+    fn parse_filter(env: &Env, filter: ProposalFilter) -> Result<ProposalQuery, ProposalError> {
+        if let (Some(from), Some(to)) = (filter.deadline_from, filter.deadline_to) {
+            if from > to {
+                return Err(ProposalError::InvalidFilter);
+            }
+        }
+
+        let status = match filter.status {
+            Some(symbol) => Some(Self::symbol_to_status(&symbol).ok_or(ProposalError::InvalidFilter)?),
+            None => None,
+        };
+
+        let proposal_type = match filter.proposal_type {
+            Some(symbol) => Some(Self::parse_proposal_type(env, &symbol)?),
+            None => None,
+        };
+
+        Ok(ProposalQuery {
+            status,
+            proposal_type,
+            creator: filter.creator,
+            deadline_from: filter.deadline_from,
+            deadline_to: filter.deadline_to,
+        })
+    }
+
+    /// Picks the secondary indexes with the fewest candidates for a filter.
+    /// Returns `None` when no criterion is indexed and every proposal has to be scanned; deadline windows
+    /// are only indexed when both bounds are set and span at most `MAX_DEADLINE_BUCKETS` days.
+    fn select_source(env: &Env, filter: &ProposalQuery) -> Option<Vec<ProposalIndex>> {
+        let mut best: Option<(u32, Vec<ProposalIndex>)> = None;
+        let mut consider = |indexes: Vec<ProposalIndex>| {
+            let size = indexes.iter().map(|idx| index::len(env, &idx)).sum::<u32>();
+            if best.as_ref().is_none_or(|(best_size, _)| size < *best_size) {
+                best = Some((size, indexes));
+            }
+        };
+
+        if let Some(status) = &filter.status {
+            consider(vec![env, ProposalIndex::Status(status.clone())]);
+        }
+        if let Some(proposal_type) = &filter.proposal_type {
+            consider(vec![env, ProposalIndex::Type(proposal_type.clone())]);
+        }
+        if let Some(creator) = &filter.creator {
+            consider(vec![env, ProposalIndex::Creator(creator.clone())]);
+        }
+        if let (Some(from), Some(to)) = (filter.deadline_from, filter.deadline_to) {
+            let (first, last) = (from / index::DEADLINE_BUCKET, to / index::DEADLINE_BUCKET);
+            if last - first < MAX_DEADLINE_BUCKETS {
+                let mut days = Vec::new(env);
+                for day in first..=last {
+                    days.push_back(ProposalIndex::DeadlineDay(day));
+                }
+                consider(days);
+            }
+        }
+
+        best.map(|(_, indexes)| indexes)
+    }
+
+    /// Moves a proposal to a new content version identified by `content_hash` and records it in the
+    /// version history.
+    fn record_content(env: &Env, proposal: &mut Proposal, content_hash: &String) {
+        proposal.version += 1;
+        proposal.content_hash = content_hash.clone();
+        content::record(
+            env,
+            proposal.id,
+            &ContentVersion {
+                version: proposal.version,
+                content_hash: content_hash.clone(),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    /// Returns the time `length` seconds after `start`, failing when it does not fit in a timestamp.
+    fn window_end(start: u64, length: u64) -> Result<u64, ProposalError> {
+        start.checked_add(length).ok_or(ProposalError::InvalidConfig)
+    }
+
+    /// Checks that the caller is the stored admin and has authorized the call.
+    fn require_admin(env: &Env, caller: &Address) -> Result<(), ProposalError> {
+        caller.require_auth();
+
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&symbol_short!("admin"))
+            .ok_or(ProposalError::NotInitialized)?;
+
+        if admin != *caller {
+            return Err(ProposalError::Unauthorized);
+        }
+
+        Ok(())
+    }
+
+    /// Loads a proposal or returns `ProposalNotFound`.
+    fn load_proposal(env: &Env, proposal_id: u32) -> Result<Proposal, ProposalError> {
+        env.storage()
+            .persistent()
+            .get(&Self::proposal_key(proposal_id))
+            .ok_or(ProposalError::ProposalNotFound)
+    }
+
+    /// Writes a proposal back to persistent storage.
+    fn save_proposal(env: &Env, proposal: &Proposal) {
+        env.storage().persistent().set(&Self::proposal_key(proposal.id), proposal);
+    }
+
+    /// Activates a Pending proposal in memory once its voting start time has been reached.
+    fn sync_status(env: &Env, proposal: &mut Proposal) -> Result<(), ProposalError> {
+        if proposal.status == ProposalStatus::Pending
+            && env.ledger().timestamp() >= proposal.voting_starts_at
+        {
+            Self::transition(env, proposal, ProposalStatus::Active)?;
+        }
+        Ok(())
+    }
+
+    /// Validates and applies a status change, emitting the event that matches the new status.
+    /// Finalization events carry the resulting status; every other transition carries the proposal ID.
+    fn transition(
+        env: &Env,
+        proposal: &mut Proposal,
+        next: ProposalStatus,
+    ) -> Result<(), ProposalError> {
+        if !proposal.status.can_transition_to(&next) {
+            return Err(ProposalError::InvalidStatusTransition);
+        }
+
+        index::reindex(
+            env,
+            &ProposalIndex::Status(proposal.status.clone()),
+            &ProposalIndex::Status(next.clone()),
+            proposal.id,
+        );
+        proposal.status = next;
+
+        let topic = match proposal.status {
+            ProposalStatus::Pending => "proposal_submitted",
+            ProposalStatus::Active => "proposal_activated",
+            ProposalStatus::Succeeded | ProposalStatus::Defeated => "proposal_finalized",
+            ProposalStatus::Queued => "proposal_queued",
+            ProposalStatus::Executed => "proposal_executed",
+            ProposalStatus::Cancelled => "proposal_cancelled",
+            ProposalStatus::Vetoed => "proposal_vetoed",
+            ProposalStatus::Expired => "proposal_expired",
+            ProposalStatus::Draft => "proposal_created",
+        };
+
+        if matches!(proposal.status, ProposalStatus::Succeeded | ProposalStatus::Defeated) {
+            env.events().publish(
+                (Symbol::new(env, topic),),
+                (proposal.id, Self::status_to_symbol(&proposal.status)),
+            );
+        } else {
+            env.events().publish((Symbol::new(env, topic),), proposal.id);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the next available proposal ID from storage, starting at 1.
+    fn next_id(env: &Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&symbol_short!("next_id"))
+            .unwrap_or(Some(1))
+            .unwrap()
+    }
+
+    /// Increments the proposal ID counter in persistent storage.
+    fn increment_id(env: &Env) {
+        let id = Self::next_id(env) + 1;
+        env.storage().persistent().set(&symbol_short!("next_id"), &id);
+    }
+
+    /// Internal helper to create the key used to store/retrieve a proposal.
     fn proposal_key(id: u32) -> (Symbol, u32) {
         (symbol_short!("proposal"), id)
     }
 
+    /// Internal helper to create the key used to store/retrieve governance parameters.
+    fn params_key(proposal_type: &ProposalType) -> (Symbol, ProposalType) {
+        (symbol_short!("params"), proposal_type.clone())
+    }
+
+    /// Converts a status Symbol back into its ProposalStatus, if it names one.
+    fn symbol_to_status(symbol: &Symbol) -> Option<ProposalStatus> {
+        use ProposalStatus::*;
+        [Draft, Pending, Active, Succeeded, Defeated, Queued, Executed, Cancelled, Vetoed, Expired]
+            .into_iter()
+            .find(|status| Self::status_to_symbol(status) == *symbol)
+    }
+
     /// Converts a ProposalStatus enum to its corresponding Symbol for event emission.
     fn status_to_symbol(status: &ProposalStatus) -> Symbol {
         match status {
-            ProposalStatus::Open => symbol_short!("open"),
-            ProposalStatus::Closed => symbol_short!("closed"),
-            ProposalStatus::Accepted => symbol_short!("accepted"),
-            ProposalStatus::Rejected => symbol_short!("rejected"),
+            ProposalStatus::Draft => symbol_short!("draft"),
+            ProposalStatus::Pending => symbol_short!("pending"),
+            ProposalStatus::Active => symbol_short!("active"),
+            ProposalStatus::Succeeded => symbol_short!("succeeded"),
+            ProposalStatus::Defeated => symbol_short!("defeated"),
+            ProposalStatus::Queued => symbol_short!("queued"),
+            ProposalStatus::Executed => symbol_short!("executed"),
+            ProposalStatus::Cancelled => symbol_short!("cancelled"),
+            ProposalStatus::Vetoed => symbol_short!("vetoed"),
+            ProposalStatus::Expired => symbol_short!("expired"),
         }
     }
 }
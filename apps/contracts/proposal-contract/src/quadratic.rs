@@ -0,0 +1,97 @@
+use soroban_sdk::{symbol_short, Address, Env, Symbol};
+
+use crate::datatypes::{QuadraticConfig, QuadraticVote, WeightedTally};
+
+/// Fixed-point scale of quadratic weights: a weight of `WEIGHT_SCALE` equals one whole vote.
+pub const WEIGHT_SCALE: u64 = 1_000_000;
+
+/// Returns the credit allowance configuration, if set.
+pub fn config(env: &Env) -> Option<QuadraticConfig> {
+    env.storage().persistent().get(&symbol_short!("qv_cfg"))
+}
+
+/// Stores the credit allowance configuration.
+pub fn set_config(env: &Env, config: &QuadraticConfig) {
+    env.storage().persistent().set(&symbol_short!("qv_cfg"), config);
+}
+
+/// Weight of spending `credits` on one option: `sqrt(credits)` scaled by `WEIGHT_SCALE`, rounded down.
+pub fn weight(credits: u32) -> u64 {
+    isqrt(credits as u128 * (WEIGHT_SCALE as u128) * (WEIGHT_SCALE as u128)) as u64
+}
+
+/// Total weight a quadratic vote adds across all options, scaled by `WEIGHT_SCALE`.
+pub fn vote_weight(vote: &QuadraticVote) -> u64 {
+    weight(vote.for_credits) + weight(vote.against_credits) + weight(vote.abstain_credits)
+}
+
+/// Credits `voter` has left for the proposal under the current allowance.
+pub fn remaining_credits(env: &Env, config: &QuadraticConfig, voter: &Address, proposal_id: u32) -> u32 {
+    let spent: u32 = env
+        .storage()
+        .persistent()
+        .get(&spent_key(env, config, voter, proposal_id))
+        .unwrap_or(0);
+    config.credits_per_voter.saturating_sub(spent)
+}
+
+/// Charges `credits` against the voter's allowance for the proposal.
+pub fn spend(env: &Env, config: &QuadraticConfig, voter: &Address, proposal_id: u32, credits: u32) {
+    let key = spent_key(env, config, voter, proposal_id);
+    let spent: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+    env.storage().persistent().set(&key, &(spent + credits));
+}
+
+/// Weighted tally of a quadratic proposal.
+pub fn tally(env: &Env, proposal_id: u32) -> WeightedTally {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("qv_tally"), proposal_id))
+        .unwrap_or(WeightedTally {
+            for_weight: 0,
+            against_weight: 0,
+            abstain_weight: 0,
+        })
+}
+
+/// Adds the weights of a quadratic vote to the proposal's tally and stores the voter's allocation.
+pub fn record(env: &Env, proposal_id: u32, voter: &Address, vote: &QuadraticVote) {
+    let mut tally = tally(env, proposal_id);
+    tally.for_weight += weight(vote.for_credits);
+    tally.against_weight += weight(vote.against_credits);
+    tally.abstain_weight += weight(vote.abstain_credits);
+
+    let storage = env.storage().persistent();
+    storage.set(&(symbol_short!("qv_tally"), proposal_id), &tally);
+    storage.set(&(symbol_short!("qv_vote"), proposal_id, voter.clone()), vote);
+}
+
+/// Returns how `voter` split their credits on the proposal, if they voted.
+pub fn get_vote(env: &Env, proposal_id: u32, voter: &Address) -> Option<QuadraticVote> {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("qv_vote"), proposal_id, voter.clone()))
+}
+
+/// Integer square root, rounded down.
+fn isqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+
+    let mut x = value;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+/// Generate storage key for the credits a voter spent, per proposal or per epoch
+fn spent_key(env: &Env, config: &QuadraticConfig, voter: &Address, proposal_id: u32) -> (Symbol, Address, u64) {
+    match env.ledger().timestamp().checked_div(config.epoch_length) {
+        Some(epoch) => (symbol_short!("qv_epoch"), voter.clone(), epoch),
+        None => (symbol_short!("qv_prop"), voter.clone(), proposal_id as u64),
+    }
+}
@@ -1,11 +1,22 @@
 #![cfg(test)]
+extern crate std;
 
+use ed25519_dalek::{Signer, SigningKey};
 use soroban_sdk::{
+    contract, contractimpl,
     testutils::{Address as _, Ledger, Events},
-    Address, Env, Symbol, symbol_short, String, IntoVal, vec,
+    token::{StellarAssetClient, TokenClient},
+    xdr::ToXdr,
+    Address, Bytes, BytesN, Env, Symbol, symbol_short, String, IntoVal, Vec, vec,
 };
 use crate::{ProposalContract, ProposalContractClient};
-use crate::datatypes::ProposalStatus;
+use crate::datatypes::{
+    AmendedVotes, AmendmentPolicy, AntiSnipingConfig, BondConfig, BondStatus, ConvictionConfig, EligibilityConfig, EmergencyConfig,
+    FinalizationReason, FundingStatus, GovernanceParams, LifecycleConfig, Member, OptimisticConfig, ProposalFilter,
+    ProposalKind, ProposalStatus, QuadraticConfig, QuadraticVote, SignedBallot, TallyMode, VotingNFT,
+    WeightedTally,
+};
+use crate::errors::ProposalError;
 
 // Macro to simplify event assertion across tests
 macro_rules! assert_event {
@@ -27,17 +38,23 @@ macro_rules! assert_event {
 // Converts a ProposalStatus into a short Symbol and compares with expected label
 fn status_eq(a: &ProposalStatus, s: &str, env: &Env) -> bool {
     match a {
-        ProposalStatus::Open => symbol_short!("open") == Symbol::new(env, s),
-        ProposalStatus::Closed => symbol_short!("closed") == Symbol::new(env, s),
-        ProposalStatus::Accepted => symbol_short!("accepted") == Symbol::new(env, s),
-        ProposalStatus::Rejected => symbol_short!("rejected") == Symbol::new(env, s),
+        ProposalStatus::Draft => symbol_short!("draft") == Symbol::new(env, s),
+        ProposalStatus::Pending => symbol_short!("pending") == Symbol::new(env, s),
+        ProposalStatus::Active => symbol_short!("active") == Symbol::new(env, s),
+        ProposalStatus::Succeeded => symbol_short!("succeeded") == Symbol::new(env, s),
+        ProposalStatus::Defeated => symbol_short!("defeated") == Symbol::new(env, s),
+        ProposalStatus::Queued => symbol_short!("queued") == Symbol::new(env, s),
+        ProposalStatus::Executed => symbol_short!("executed") == Symbol::new(env, s),
+        ProposalStatus::Cancelled => symbol_short!("cancelled") == Symbol::new(env, s),
+        ProposalStatus::Vetoed => symbol_short!("vetoed") == Symbol::new(env, s),
+        ProposalStatus::Expired => symbol_short!("expired") == Symbol::new(env, s),
     }
 }
 
 // Common setup function to initialize Env, Contract, a test user, and client
 fn setup<'a>() -> (Env, Address, Address, ProposalContractClient<'a>) {
     let env = Env::default();
-    let contract_id = env.register(ProposalContract, ());
+    let contract_id = env.register(ProposalContract, (Address::generate(&env),));
     let client = ProposalContractClient::new(&env, &contract_id);
     let user = Address::generate(&env);
     env.mock_all_auths();
@@ -49,19 +66,34 @@ fn setup<'a>() -> (Env, Address, Address, ProposalContractClient<'a>) {
 fn test_create_and_get_proposal() {
     let (env, user, contract_id, client) = setup();
 
+    let content_hash = String::from_str(&env, "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi");
     client.create_proposal(
         &user,
         &String::from_str(&env, "Title"),
-        &String::from_str(&env, "Description"),
+        &content_hash,
         &(env.ledger().timestamp() + 100),
         &Symbol::new(&env, "governance"),
         &Some(3),
+        &false,
     );
     assert_event!(env, contract_id, "proposal_created", 1u32);
 
     let proposal = client.get_proposal(&1);
     assert_eq!(proposal.title, String::from_str(&env, "Title"));
-    assert!(status_eq(&proposal.status, "open", &env));
+    assert!(status_eq(&proposal.status, "draft", &env));
+    assert_eq!((proposal.version, proposal.content_hash), (1, content_hash.clone()));
+    assert_eq!(client.get_content_version(&1, &1).content_hash, content_hash);
+
+    let result = client.try_create_proposal(
+        &user,
+        &String::from_str(&env, "Title"),
+        &String::from_str(&env, ""),
+        &(env.ledger().timestamp() + 100),
+        &Symbol::new(&env, "governance"),
+        &None,
+        &false,
+    );
+    assert_eq!(result, Err(Ok(ProposalError::InvalidConfig)));
 }
 
 // Ensures a user cannot vote more than once on the same proposal
@@ -77,15 +109,17 @@ fn test_prevent_double_vote() {
         &(env.ledger().timestamp() + 100),
         &Symbol::new(&env, "governance"),
         &None,
+        &false,
     );
     assert_event!(env, contract_id, "proposal_created", 1u32);
+    client.submit_proposal(&user, &1);
 
     client.vote(&user, &1, &symbol_short!("For"));
     assert_event!(env, contract_id, "vote_cast", (1u32, user.clone(), symbol_short!("For")));
     client.vote(&user, &1, &symbol_short!("For"));
 }
 
-// Verifies that a proposal is accepted if quorum is met and majority votes are in favor
+// Verifies that a proposal succeeds if quorum is met and majority votes are in favor
 #[test]
 fn test_finalize_accepted_when_quorum_met() {
     let (env, user1, contract_id, client) = setup();
@@ -101,8 +135,10 @@ fn test_finalize_accepted_when_quorum_met() {
         &(now + 10),
         &Symbol::new(&env, "community"),
         &Some(3),
+        &false,
     );
     assert_event!(env, contract_id, "proposal_created", 1u32);    
+    client.submit_proposal(&user1, &1);
 
     client.vote(&user1, &1, &symbol_short!("For"));
     assert_event!(env, contract_id, "vote_cast", (1u32, user1.clone(), symbol_short!("For")));
@@ -113,13 +149,13 @@ fn test_finalize_accepted_when_quorum_met() {
 
     env.ledger().set_timestamp(now + 20);
     client.finalize(&1);
-    assert_event!(env, contract_id, "proposal_finalized", (1u32, symbol_short!("accepted")));
+    assert_event!(env, contract_id, "proposal_finalized", (1u32, symbol_short!("succeeded")));
 
     let proposal = client.get_proposal(&1);
-    assert!(status_eq(&proposal.status, "accepted", &env));
+    assert!(status_eq(&proposal.status, "succeeded", &env));
 }
 
-// Verifies that a proposal is closed if quorum is not met
+// Verifies that a proposal is defeated if quorum is not met
 #[test]
 fn test_finalize_closed_when_quorum_not_met() {
     let (env, user, contract_id, client) = setup();
@@ -131,21 +167,23 @@ fn test_finalize_closed_when_quorum_not_met() {
         &(env.ledger().timestamp() + 5),
         &Symbol::new(&env, "governance"),
         &Some(5),
+        &false,
     );
     assert_event!(env, contract_id, "proposal_created", 1u32);
+    client.submit_proposal(&user, &1);
 
     client.vote(&user, &1, &symbol_short!("For"));
     assert_event!(env, contract_id, "vote_cast", (1u32, user.clone(), symbol_short!("For")));
 
     env.ledger().set_timestamp(env.ledger().timestamp() + 10);
     client.finalize(&1);
-    assert_event!(env, contract_id, "proposal_finalized", (1u32, symbol_short!("closed")));
+    assert_event!(env, contract_id, "proposal_finalized", (1u32, symbol_short!("defeated")));
 
     let proposal = client.get_proposal(&1);
-    assert!(status_eq(&proposal.status, "closed", &env));
+    assert!(status_eq(&proposal.status, "defeated", &env));
 }
 
-// Checks rejection when majority of effective votes are against, and no quorum is required
+// Checks defeat when majority of effective votes are against, and no quorum is required
 #[test]
 fn test_finalize_without_quorum_majority_against() {
     let (env, user1, contract_id, client) = setup();
@@ -160,8 +198,10 @@ fn test_finalize_without_quorum_majority_against() {
         &deadline,
         &Symbol::new(&env, "treasury"),
         &None,
+        &false,
     );
     assert_event!(env, contract_id, "proposal_created", 1u32);
+    client.submit_proposal(&user1, &1);
 
     client.vote(&user1, &1, &symbol_short!("Against"));
     assert_event!(env, contract_id, "vote_cast", (1u32, user1.clone(), symbol_short!("Against")));
@@ -171,13 +211,13 @@ fn test_finalize_without_quorum_majority_against() {
 
     env.ledger().set_timestamp(deadline + 5);
     client.finalize(&1);
-    assert_event!(env, contract_id, "proposal_finalized", (1u32, symbol_short!("rejected")));
+    assert_event!(env, contract_id, "proposal_finalized", (1u32, symbol_short!("defeated")));
 
     let proposal = client.get_proposal(&1);
-    assert!(status_eq(&proposal.status, "rejected", &env));
+    assert!(status_eq(&proposal.status, "defeated", &env));
 }
 
-// Validates tie results in rejection (equal For and Against votes)
+// Validates tie results in defeat (equal For and Against votes)
 #[test]
 fn test_finalize_rejected_on_for_against_tie() {
     let (env, user1, contract_id, client) = setup();
@@ -190,8 +230,10 @@ fn test_finalize_rejected_on_for_against_tie() {
         &(env.ledger().timestamp() + 10),
         &Symbol::new(&env, "technical"),
         &None,
+        &false,
     );
     assert_event!(env, contract_id, "proposal_created", 1u32);
+    client.submit_proposal(&user1, &1);
 
     client.vote(&user1, &1, &symbol_short!("For"));
     assert_event!(env, contract_id, "vote_cast", (1u32, user1.clone(), symbol_short!("For")));
@@ -201,13 +243,13 @@ fn test_finalize_rejected_on_for_against_tie() {
 
     env.ledger().set_timestamp(env.ledger().timestamp() + 20);
     client.finalize(&1);
-    assert_event!(env, contract_id, "proposal_finalized", (1u32, symbol_short!("rejected")));
+    assert_event!(env, contract_id, "proposal_finalized", (1u32, symbol_short!("defeated")));
 
     let proposal = client.get_proposal(&1);
-    assert!(status_eq(&proposal.status, "rejected", &env));
+    assert!(status_eq(&proposal.status, "defeated", &env));
 }
 
-// Ensures that proposals with only Abstain votes are rejected
+// Ensures that proposals with only Abstain votes are defeated
 #[test]
 fn test_finalize_rejected_when_only_abstain() {
     let (env, user1, contract_id, client) = setup();
@@ -222,8 +264,10 @@ fn test_finalize_rejected_when_only_abstain() {
         &deadline,
         &Symbol::new(&env, "treasury"),
         &None,
+        &false,
     );
     assert_event!(env, contract_id, "proposal_created", 1u32);
+    client.submit_proposal(&user1, &1);
 
     client.vote(&user1, &1, &symbol_short!("Abstain"));
     assert_event!(env, contract_id, "vote_cast", (1u32, user1.clone(), symbol_short!("Abstain")));
@@ -233,10 +277,10 @@ fn test_finalize_rejected_when_only_abstain() {
 
     env.ledger().set_timestamp(deadline + 1);
     client.finalize(&1);
-    assert_event!(env, contract_id, "proposal_finalized", (1u32, symbol_short!("rejected")));
+    assert_event!(env, contract_id, "proposal_finalized", (1u32, symbol_short!("defeated")));
 
     let proposal = client.get_proposal(&1);
-    assert!(status_eq(&proposal.status, "rejected", &env));
+    assert!(status_eq(&proposal.status, "defeated", &env));
 }
 
 // Confirms panic if trying to finalize before deadline
@@ -252,8 +296,10 @@ fn test_finalize_panics_before_deadline() {
         &(env.ledger().timestamp() + 100),
         &Symbol::new(&env, "governance"),
         &None,
+        &false,
     );
     assert_event!(env, contract_id, "proposal_created", 1u32);
+    client.submit_proposal(&user, &1);
 
     client.finalize(&1);
 }
@@ -278,6 +324,7 @@ fn test_invalid_proposal_type() {
         &(env.ledger().timestamp() + 50),
         &Symbol::new(&env, "invalid_type"),
         &None,
+        &false,
     );
 }
 
@@ -294,8 +341,10 @@ fn test_vote_after_deadline() {
         &deadline,
         &Symbol::new(&env, "treasury"),
         &None,
+        &false,
     );
     assert_event!(env, contract_id, "proposal_created", 1u32);
+    client.submit_proposal(&user, &1);
     env.ledger().set_timestamp(deadline + 5);
     client.vote(&user, &1, &symbol_short!("For"));
 }
@@ -312,11 +361,13 @@ fn test_vote_on_closed_proposal() {
         &(env.ledger().timestamp() + 10),
         &Symbol::new(&env, "community"),
         &None,
+        &false,
     );
     assert_event!(env, contract_id, "proposal_created", 1u32);
+    client.submit_proposal(&user, &1);
     env.ledger().set_timestamp(env.ledger().timestamp() + 20);
     client.finalize(&1);
-    assert_event!(env, contract_id, "proposal_finalized", (1u32, symbol_short!("rejected")));
+    assert_event!(env, contract_id, "proposal_finalized", (1u32, symbol_short!("defeated")));
     client.vote(&user, &1, &symbol_short!("For"));
 }
 
@@ -332,12 +383,14 @@ fn test_invalid_vote_choice() {
         &(env.ledger().timestamp() + 100),
         &Symbol::new(&env, "governance"),
         &None,
+        &false,
     );
     assert_event!(env, contract_id, "proposal_created", 1u32);
+    client.submit_proposal(&user, &1);
     client.vote(&user, &1, &Symbol::new(&env, "Bad"));
 }
 
-// Validates proposal is closed (not accepted) when quorum is required but no votes were cast
+// Validates proposal is defeated when quorum is required but no votes were cast
 #[test]
 fn test_finalize_closed_with_quorum_and_no_votes() {
     let (env, user, contract_id, client) = setup();
@@ -351,18 +404,20 @@ fn test_finalize_closed_with_quorum_and_no_votes() {
         &deadline,
         &Symbol::new(&env, "treasury"),
         &Some(3),
+        &false,
     );
     assert_event!(env, contract_id, "proposal_created", 1u32);
+    client.submit_proposal(&user, &1);
 
     env.ledger().set_timestamp(deadline + 1);
     client.finalize(&1);
-    assert_event!(env, contract_id, "proposal_finalized", (1u32, symbol_short!("closed")));
+    assert_event!(env, contract_id, "proposal_finalized", (1u32, symbol_short!("defeated")));
 
     let proposal = client.get_proposal(&1);
-    assert!(status_eq(&proposal.status, "closed", &env));
+    assert!(status_eq(&proposal.status, "defeated", &env));
 }
 
-// Validates rejection when no quorum is set and no votes are cast
+// Validates defeat when no quorum is set and no votes are cast
 #[test]
 fn test_finalize_rejected_without_quorum_and_no_votes() {
     let (env, user, contract_id, client) = setup();
@@ -376,15 +431,17 @@ fn test_finalize_rejected_without_quorum_and_no_votes() {
         &deadline,
         &Symbol::new(&env, "treasury"),
         &None,
+        &false,
     );
     assert_event!(env, contract_id, "proposal_created", 1u32);
+    client.submit_proposal(&user, &1);
 
     env.ledger().set_timestamp(deadline + 1);
     client.finalize(&1);
-    assert_event!(env, contract_id, "proposal_finalized", (1u32, symbol_short!("rejected")));
+    assert_event!(env, contract_id, "proposal_finalized", (1u32, symbol_short!("defeated")));
 
     let proposal = client.get_proposal(&1);
-    assert!(status_eq(&proposal.status, "rejected", &env));
+    assert!(status_eq(&proposal.status, "defeated", &env));
 }
 
 // Confirms proposals cannot be created without proper authorization
@@ -404,7 +461,40 @@ fn test_create_proposal_without_auth_should_fail() {
         &(env.ledger().timestamp() + 100),
         &Symbol::new(&env, "governance"),
         &None,
+        &false,
+    );
+}
+
+// Verifies that `list_proposals` without criteria returns all created proposals in order
+#[test]
+fn test_list_proposals_returns_all() {
+    let (env, user, _contract_id, client) = setup();
+
+    client.create_proposal(
+        &user,
+        &String::from_str(&env, "Proposal 1"),
+        &String::from_str(&env, "Description 1"),
+        &(env.ledger().timestamp() + 100),
+        &Symbol::new(&env, "governance"),
+        &Some(2),
+        &false,
+    );
+
+    client.create_proposal(
+        &user,
+        &String::from_str(&env, "Proposal 2"),
+        &String::from_str(&env, "Description 2"),
+        &(env.ledger().timestamp() + 200),
+        &Symbol::new(&env, "technical"),
+        &None,
+        &false,
     );
+
+    let proposals = client.list_proposals(&no_filter(), &0, &10);
+
+    assert_eq!(proposals.len(), 2);
+    assert_eq!(proposals.get(0).unwrap().title, String::from_str(&env, "Proposal 1"));
+    assert_eq!(proposals.get(1).unwrap().title, String::from_str(&env, "Proposal 2"));
 }
 
 // Verifies that `get_all_proposals` returns all created proposals in order
@@ -419,6 +509,7 @@ fn test_get_all_proposals_returns_all() {
         &(env.ledger().timestamp() + 100),
         &Symbol::new(&env, "governance"),
         &Some(2),
+        &false,
     );
 
     client.create_proposal(
@@ -428,11 +519,1899 @@ fn test_get_all_proposals_returns_all() {
         &(env.ledger().timestamp() + 200),
         &Symbol::new(&env, "technical"),
         &None,
+        &false,
     );
 
-    let proposals = client.get_all_proposals();
+    let proposals = client.get_all_proposals(&0, &10);
 
     assert_eq!(proposals.len(), 2);
     assert_eq!(proposals.get(0).unwrap().title, String::from_str(&env, "Proposal 1"));
     assert_eq!(proposals.get(1).unwrap().title, String::from_str(&env, "Proposal 2"));
+
+    assert_eq!(client.get_all_proposals(&1, &10).len(), 1);
+    assert_eq!(client.get_all_proposals(&1, &10).get(0).unwrap().title, String::from_str(&env, "Proposal 2"));
+}
+
+// Filter that matches every proposal
+fn no_filter() -> ProposalFilter {
+    ProposalFilter {
+        status: None,
+        proposal_type: None,
+        creator: None,
+        deadline_from: None,
+        deadline_to: None,
+    }
+}
+
+// Returns the admin the contract was deployed with
+fn init_admin(env: &Env, client: &ProposalContractClient) -> Address {
+    env.as_contract(&client.address, || {
+        env.storage().persistent().get(&symbol_short!("admin")).unwrap()
+    })
+}
+
+// Creates a governance draft proposal owned by `user` with the given deadline
+fn create_draft(env: &Env, client: &ProposalContractClient, user: &Address, deadline: u64) {
+    client.create_proposal(
+        user,
+        &String::from_str(env, "Lifecycle"),
+        &String::from_str(env, "bafy-lifecycle"),
+        &deadline,
+        &Symbol::new(env, "governance"),
+        &None,
+        &false,
+    );
+}
+
+// Checks that the author can edit a draft and that edits are stored
+#[test]
+fn test_update_draft_proposal() {
+    let (env, user, contract_id, client) = setup();
+    create_draft(&env, &client, &user, env.ledger().timestamp() + 100);
+
+    client.update_proposal(
+        &user,
+        &1,
+        &String::from_str(&env, "Edited"),
+        &String::from_str(&env, "bafy-edited"),
+        &(env.ledger().timestamp() + 200),
+    );
+    assert_event!(env, contract_id, "proposal_updated", 1u32);
+
+    let proposal = client.get_proposal(&1);
+    assert_eq!(proposal.title, String::from_str(&env, "Edited"));
+    assert_eq!(proposal.deadline, env.ledger().timestamp() + 200);
+    assert!(status_eq(&proposal.status, "draft", &env));
+    assert_eq!((proposal.version, proposal.content_hash), (2, String::from_str(&env, "bafy-edited")));
+    assert_eq!(client.get_content_version(&1, &1).content_hash, String::from_str(&env, "bafy-lifecycle"));
+}
+
+// Ensures only the author can edit a draft
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #11)")]
+fn test_update_draft_by_non_author_fails() {
+    let (env, user, _, client) = setup();
+    let other = Address::generate(&env);
+    create_draft(&env, &client, &user, env.ledger().timestamp() + 100);
+
+    client.update_proposal(
+        &other,
+        &1,
+        &String::from_str(&env, "Hijack"),
+        &String::from_str(&env, "Hijack"),
+        &(env.ledger().timestamp() + 100),
+    );
+}
+
+// Ensures a submitted proposal can no longer be edited
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #20)")]
+fn test_update_after_submit_fails() {
+    let (env, user, _, client) = setup();
+    create_draft(&env, &client, &user, env.ledger().timestamp() + 100);
+    client.submit_proposal(&user, &1);
+
+    client.update_proposal(
+        &user,
+        &1,
+        &String::from_str(&env, "Too Late"),
+        &String::from_str(&env, "Too Late"),
+        &(env.ledger().timestamp() + 100),
+    );
+}
+
+// Ensures drafts cannot be voted on before submission
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #6)")]
+fn test_vote_on_draft_fails() {
+    let (env, user, _, client) = setup();
+    create_draft(&env, &client, &user, env.ledger().timestamp() + 100);
+    client.vote(&user, &1, &symbol_short!("For"));
+}
+
+// Checks the author can cancel a draft and that it cannot be submitted afterwards
+#[test]
+fn test_cancel_draft_proposal() {
+    let (env, user, contract_id, client) = setup();
+    create_draft(&env, &client, &user, env.ledger().timestamp() + 100);
+
+    client.cancel_proposal(&user, &1);
+    assert_event!(env, contract_id, "proposal_cancelled", 1u32);
+    assert!(status_eq(&client.get_proposal(&1).status, "cancelled", &env));
+
+    let result = client.try_submit_proposal(&user, &1);
+    assert_eq!(result, Err(Ok(ProposalError::InvalidStatusTransition)));
+}
+
+// Ensures an active proposal can no longer be cancelled by its author
+#[test]
+fn test_cancel_active_proposal_fails() {
+    let (env, user, _, client) = setup();
+    create_draft(&env, &client, &user, env.ledger().timestamp() + 100);
+    client.submit_proposal(&user, &1);
+
+    let result = client.try_cancel_proposal(&user, &1);
+    assert_eq!(result, Err(Ok(ProposalError::InvalidStatusTransition)));
+}
+
+// Checks that the voting delay keeps a submitted proposal Pending until voting starts
+#[test]
+fn test_voting_delay_keeps_proposal_pending() {
+    let (env, user, contract_id, client) = setup();
+    let admin = init_admin(&env, &client);
+    client.set_lifecycle_config(
+        &admin,
+        &LifecycleConfig { voting_delay: 50, execution_delay: 0, execution_window: 100 },
+    );
+
+    let now = env.ledger().timestamp();
+    create_draft(&env, &client, &user, now + 200);
+    client.submit_proposal(&user, &1);
+    assert_event!(env, contract_id, "proposal_submitted", 1u32);
+
+    let proposal = client.get_proposal(&1);
+    assert!(status_eq(&proposal.status, "pending", &env));
+    assert_eq!(proposal.voting_starts_at, now + 50);
+
+    let result = client.try_vote(&user, &1, &symbol_short!("For"));
+    assert_eq!(result, Err(Ok(ProposalError::VotingNotStarted)));
+
+    env.ledger().set_timestamp(now + 50);
+    client.activate(&1);
+    assert_event!(env, contract_id, "proposal_activated", 1u32);
+    assert!(status_eq(&client.get_proposal(&1).status, "active", &env));
+
+    client.vote(&user, &1, &symbol_short!("For"));
+    assert_eq!(client.get_votes(&1), (1, 0, 0));
+}
+
+// Walks a proposal through Succeeded, Queued and Executed respecting the execution delay
+#[test]
+fn test_queue_and_execute_proposal() {
+    let (env, user, contract_id, client) = setup();
+    let admin = init_admin(&env, &client);
+    client.set_lifecycle_config(
+        &admin,
+        &LifecycleConfig { voting_delay: 0, execution_delay: 30, execution_window: 100 },
+    );
+
+    let now = env.ledger().timestamp();
+    create_draft(&env, &client, &user, now + 10);
+    client.submit_proposal(&user, &1);
+    client.vote(&user, &1, &symbol_short!("For"));
+
+    env.ledger().set_timestamp(now + 10);
+    client.finalize(&1);
+    assert_event!(env, contract_id, "proposal_finalized", (1u32, symbol_short!("succeeded")));
+
+    client.queue(&1);
+    assert_event!(env, contract_id, "proposal_queued", 1u32);
+    assert_eq!(client.get_proposal(&1).executable_at, now + 40);
+
+    let result = client.try_execute(&1);
+    assert_eq!(result, Err(Ok(ProposalError::ExecutionDelayNotMet)));
+
+    env.ledger().set_timestamp(now + 40);
+    client.execute(&1);
+    assert_event!(env, contract_id, "proposal_executed", 1u32);
+    assert!(status_eq(&client.get_proposal(&1).status, "executed", &env));
+}
+
+// Ensures timings past the largest timestamp are rejected instead of overflowing
+#[test]
+fn test_lifecycle_timestamp_overflow() {
+    let (env, user, _, client) = setup();
+    let admin = init_admin(&env, &client);
+    let now = env.ledger().timestamp();
+
+    client.set_lifecycle_config(
+        &admin,
+        &LifecycleConfig { voting_delay: u64::MAX, execution_delay: 0, execution_window: 100 },
+    );
+    create_draft(&env, &client, &user, now + 10);
+    let result = client.try_submit_proposal(&user, &1);
+    assert_eq!(result, Err(Ok(ProposalError::InvalidDeadline)));
+
+    client.set_lifecycle_config(
+        &admin,
+        &LifecycleConfig { voting_delay: 0, execution_delay: 0, execution_window: u64::MAX },
+    );
+    client.submit_proposal(&user, &1);
+    client.vote(&user, &1, &symbol_short!("For"));
+    env.ledger().set_timestamp(now + 10);
+    client.finalize(&1);
+
+    let result = client.try_queue(&1);
+    assert_eq!(result, Err(Ok(ProposalError::InvalidConfig)));
+}
+
+// Ensures defeated proposals cannot be queued
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #14)")]
+fn test_queue_defeated_proposal_fails() {
+    let (env, user, _, client) = setup();
+    let now = env.ledger().timestamp();
+    create_draft(&env, &client, &user, now + 10);
+    client.submit_proposal(&user, &1);
+    client.vote(&user, &1, &symbol_short!("Against"));
+
+    env.ledger().set_timestamp(now + 10);
+    client.finalize(&1);
+    client.queue(&1);
+}
+
+// Checks that the guardian can veto an active proposal and that others cannot
+#[test]
+fn test_guardian_veto() {
+    let (env, user, contract_id, client) = setup();
+    let admin = init_admin(&env, &client);
+    let guardian = Address::generate(&env);
+    client.set_guardian(&admin, &guardian);
+
+    create_draft(&env, &client, &user, env.ledger().timestamp() + 100);
+    client.submit_proposal(&user, &1);
+
+    let result = client.try_veto(&user, &1);
+    assert_eq!(result, Err(Ok(ProposalError::Unauthorized)));
+
+    client.veto(&guardian, &1);
+    assert_event!(env, contract_id, "proposal_vetoed", 1u32);
+    assert!(status_eq(&client.get_proposal(&1).status, "vetoed", &env));
+
+    let result = client.try_vote(&user, &1, &symbol_short!("For"));
+    assert_eq!(result, Err(Ok(ProposalError::ProposalNotOpen)));
+}
+
+// Checks that a succeeded proposal expires when nobody queues it within the execution window
+#[test]
+fn test_expire_unexecuted_proposal() {
+    let (env, user, contract_id, client) = setup();
+    let admin = init_admin(&env, &client);
+    client.set_lifecycle_config(
+        &admin,
+        &LifecycleConfig { voting_delay: 0, execution_delay: 0, execution_window: 100 },
+    );
+
+    let now = env.ledger().timestamp();
+    create_draft(&env, &client, &user, now + 10);
+    client.submit_proposal(&user, &1);
+    client.vote(&user, &1, &symbol_short!("For"));
+    env.ledger().set_timestamp(now + 10);
+    client.finalize(&1);
+
+    let result = client.try_expire(&1);
+    assert_eq!(result, Err(Ok(ProposalError::ExecutionWindowOpen)));
+
+    env.ledger().set_timestamp(now + 111);
+    let result = client.try_queue(&1);
+    assert_eq!(result, Err(Ok(ProposalError::ExecutionWindowPassed)));
+
+    client.expire(&1);
+    assert_event!(env, contract_id, "proposal_expired", 1u32);
+    assert!(status_eq(&client.get_proposal(&1).status, "expired", &env));
+}
+
+// Ensures lifecycle configuration is admin only
+#[test]
+fn test_set_lifecycle_config_requires_admin() {
+    let (env, user, _, client) = setup();
+    init_admin(&env, &client);
+
+    let result = client.try_set_lifecycle_config(
+        &user,
+        &LifecycleConfig { voting_delay: 0, execution_delay: 0, execution_window: 100 },
+    );
+    assert_eq!(result, Err(Ok(ProposalError::Unauthorized)));
+}
+
+// Builds governance parameters with a wide voting period range
+fn governance_params(quorum_bps: u32, approval_threshold_bps: u32, abstain_counts: bool) -> GovernanceParams {
+    GovernanceParams {
+        min_voting_period: 10,
+        max_voting_period: 1_000,
+        quorum_bps,
+        approval_threshold_bps,
+        abstain_counts_toward_quorum: abstain_counts,
+    }
+}
+
+// Casts one vote per generated voter for each entry in `choices`
+fn cast_votes(env: &Env, client: &ProposalContractClient, proposal_id: u32, choices: &[&str]) {
+    for choice in choices {
+        let voter = Address::generate(env);
+        client.vote(&voter, &proposal_id, &Symbol::new(env, choice));
+    }
+}
+
+// Checks that a 2/3 supermajority passes with exactly two thirds of decisive votes and fails below it
+#[test]
+fn test_governance_supermajority_threshold() {
+    let (env, user, _, client) = setup();
+    let admin = init_admin(&env, &client);
+    client.set_total_voting_power(&admin, &10);
+    client.set_governance_params(&admin, &Symbol::new(&env, "governance"), &governance_params(3_000, 6_666, true));
+
+    let now = env.ledger().timestamp();
+    create_draft(&env, &client, &user, now + 100);
+    client.submit_proposal(&user, &1);
+    assert_eq!(client.get_proposal(&1).total_voting_power, 10);
+    cast_votes(&env, &client, 1, &["For", "For", "Against"]);
+
+    create_draft(&env, &client, &user, now + 100);
+    client.submit_proposal(&user, &2);
+    cast_votes(&env, &client, 2, &["For", "For", "For", "Against", "Against"]);
+
+    env.ledger().set_timestamp(now + 100);
+    client.finalize(&1);
+    client.finalize(&2);
+    assert!(status_eq(&client.get_proposal(&1).status, "succeeded", &env));
+    assert!(status_eq(&client.get_proposal(&2).status, "defeated", &env));
+}
+
+// Ensures quorum is a share of total voting power and the proposer cannot lower it
+#[test]
+fn test_quorum_percentage_overrides_proposer_quorum() {
+    let (env, user, _, client) = setup();
+    let admin = init_admin(&env, &client);
+    client.set_total_voting_power(&admin, &10);
+    client.set_governance_params(&admin, &Symbol::new(&env, "treasury"), &governance_params(5_000, 5_000, true));
+
+    let now = env.ledger().timestamp();
+    client.create_proposal(
+        &user,
+        &String::from_str(&env, "Low Quorum"),
+        &String::from_str(&env, "Proposer asks for quorum 1"),
+        &(now + 100),
+        &Symbol::new(&env, "treasury"),
+        &Some(1),
+        &false,
+    );
+    client.submit_proposal(&user, &1);
+    cast_votes(&env, &client, 1, &["For", "For", "For", "For"]);
+
+    env.ledger().set_timestamp(now + 100);
+    client.finalize(&1);
+    assert!(status_eq(&client.get_proposal(&1).status, "defeated", &env));
+}
+
+// Checks that abstentions only help reach quorum when the type allows it
+#[test]
+fn test_abstain_counts_toward_quorum_setting() {
+    let (env, user, _, client) = setup();
+    let admin = init_admin(&env, &client);
+    client.set_total_voting_power(&admin, &4);
+    client.set_governance_params(&admin, &Symbol::new(&env, "governance"), &governance_params(7_500, 5_000, false));
+    client.set_governance_params(&admin, &Symbol::new(&env, "community"), &governance_params(7_500, 5_000, true));
+
+    let now = env.ledger().timestamp();
+    create_draft(&env, &client, &user, now + 100);
+    client.create_proposal(
+        &user,
+        &String::from_str(&env, "Community"),
+        &String::from_str(&env, "Abstain counts"),
+        &(now + 100),
+        &Symbol::new(&env, "community"),
+        &None,
+        &false,
+    );
+    client.submit_proposal(&user, &1);
+    client.submit_proposal(&user, &2);
+    cast_votes(&env, &client, 1, &["For", "For", "Abstain"]);
+    cast_votes(&env, &client, 2, &["For", "For", "Abstain"]);
+
+    env.ledger().set_timestamp(now + 100);
+    client.finalize(&1);
+    client.finalize(&2);
+    assert!(status_eq(&client.get_proposal(&1).status, "defeated", &env));
+    assert!(status_eq(&client.get_proposal(&2).status, "succeeded", &env));
+}
+
+// Ensures submission fails when the voting period is outside the configured bounds
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #21)")]
+fn test_voting_period_outside_bounds() {
+    let (env, user, _, client) = setup();
+    let admin = init_admin(&env, &client);
+    client.set_governance_params(&admin, &Symbol::new(&env, "governance"), &governance_params(0, 5_000, true));
+
+    create_draft(&env, &client, &user, env.ledger().timestamp() + 5);
+    client.submit_proposal(&user, &1);
+}
+
+// Ensures inconsistent parameters are rejected
+#[test]
+fn test_invalid_governance_params() {
+    let (env, _, _, client) = setup();
+    let admin = init_admin(&env, &client);
+    let governance = Symbol::new(&env, "governance");
+
+    let below_majority = governance_params(1_000, 4_000, true);
+    assert_eq!(
+        client.try_set_governance_params(&admin, &governance, &below_majority),
+        Err(Ok(ProposalError::InvalidConfig))
+    );
+
+    let mut inverted_period = governance_params(1_000, 5_000, true);
+    inverted_period.min_voting_period = 2_000;
+    assert_eq!(
+        client.try_set_governance_params(&admin, &governance, &inverted_period),
+        Err(Ok(ProposalError::InvalidConfig))
+    );
+
+    assert_eq!(client.get_governance_params(&governance), None);
+}
+
+// Ensures a quorum percentage cannot be measured against an unset total voting power
+#[test]
+fn test_quorum_requires_total_voting_power() {
+    let (env, user, _, client) = setup();
+    let admin = init_admin(&env, &client);
+    let governance = Symbol::new(&env, "governance");
+
+    assert_eq!(
+        client.try_set_governance_params(&admin, &governance, &governance_params(3_000, 5_000, true)),
+        Err(Ok(ProposalError::TotalPowerNotSet))
+    );
+
+    client.set_total_voting_power(&admin, &10);
+    client.set_governance_params(&admin, &governance, &governance_params(3_000, 5_000, true));
+    client.set_total_voting_power(&admin, &0);
+
+    create_draft(&env, &client, &user, env.ledger().timestamp() + 100);
+    let result = client.try_submit_proposal(&user, &1);
+    assert_eq!(result, Err(Ok(ProposalError::TotalPowerNotSet)));
+}
+
+// Minimal treasury that records deposits like the treasury contract does
+#[contract]
+pub struct MockTreasury;
+
+#[contractimpl]
+impl MockTreasury {
+    pub fn deposit(env: Env, asset: Address, from: Address, amount: i128) {
+        from.require_auth();
+        let balance: i128 = env.storage().persistent().get(&asset).unwrap_or(0);
+        env.storage().persistent().set(&asset, &(balance + amount));
+    }
+
+    pub fn get_balance(env: Env, asset: Address) -> i128 {
+        env.storage().persistent().get(&asset).unwrap_or(0)
+    }
+}
+
+// Registers a token and a mock treasury, and requires a 100 token bond on governance proposals
+fn setup_bonds(
+    env: &Env,
+    client: &ProposalContractClient,
+    admin: &Address,
+    slash_threshold_bps: u32,
+) -> (Address, MockTreasuryClient<'static>) {
+    let token = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let treasury = MockTreasuryClient::new(env, &env.register(MockTreasury, ()));
+
+    client.set_treasury(admin, &treasury.address);
+    client.set_bond_config(
+        admin,
+        &Symbol::new(env, "governance"),
+        &BondConfig { token: token.clone(), amount: 100, slash_threshold_bps },
+    );
+
+    (token, treasury)
+}
+
+// Checks the bond is escrowed on creation and refunded once the proposal reaches quorum
+#[test]
+fn test_bond_refunded_when_quorum_reached() {
+    let (env, user, contract_id, client) = setup();
+    let admin = init_admin(&env, &client);
+    let (token, _) = setup_bonds(&env, &client, &admin, 2_000);
+    StellarAssetClient::new(&env, &token).mint(&user, &150);
+    let token_client = TokenClient::new(&env, &token);
+
+    let now = env.ledger().timestamp();
+    create_draft(&env, &client, &user, now + 100);
+    assert_eq!(token_client.balance(&user), 50);
+    assert_eq!(token_client.balance(&contract_id), 100);
+    assert_eq!(client.get_bond(&1).unwrap().status, BondStatus::Escrowed);
+
+    client.submit_proposal(&user, &1);
+    cast_votes(&env, &client, 1, &["Against"]);
+    env.ledger().set_timestamp(now + 100);
+    client.finalize(&1);
+
+    assert!(status_eq(&client.get_proposal(&1).status, "defeated", &env));
+    assert_eq!(client.get_bond(&1).unwrap().status, BondStatus::Refunded);
+    assert_eq!(token_client.balance(&user), 150);
+    assert_eq!(token_client.balance(&contract_id), 0);
+}
+
+// Checks that cancelling a proposal slashes its bond to the treasury
+#[test]
+fn test_bond_slashed_on_cancel() {
+    let (env, user, _, client) = setup();
+    let admin = init_admin(&env, &client);
+    let (token, treasury) = setup_bonds(&env, &client, &admin, 2_000);
+    StellarAssetClient::new(&env, &token).mint(&user, &100);
+
+    create_draft(&env, &client, &user, env.ledger().timestamp() + 100);
+    client.cancel_proposal(&user, &1);
+
+    assert_eq!(client.get_bond(&1).unwrap().status, BondStatus::Slashed);
+    assert_eq!(TokenClient::new(&env, &token).balance(&treasury.address), 100);
+    assert_eq!(treasury.get_balance(&token), 100);
+}
+
+// Checks that proposals defeated without quorum are slashed only when support is below the threshold
+#[test]
+fn test_bond_slashed_when_defeated_below_threshold() {
+    let (env, user, _, client) = setup();
+    let admin = init_admin(&env, &client);
+    client.set_total_voting_power(&admin, &10);
+    client.set_governance_params(&admin, &Symbol::new(&env, "governance"), &governance_params(5_000, 5_000, true));
+    let (token, treasury) = setup_bonds(&env, &client, &admin, 2_000);
+    StellarAssetClient::new(&env, &token).mint(&user, &200);
+
+    let now = env.ledger().timestamp();
+    create_draft(&env, &client, &user, now + 100);
+    create_draft(&env, &client, &user, now + 100);
+    client.submit_proposal(&user, &1);
+    client.submit_proposal(&user, &2);
+    cast_votes(&env, &client, 1, &["Against", "Against", "Against", "Against"]);
+    cast_votes(&env, &client, 2, &["For", "Against", "Against"]);
+
+    env.ledger().set_timestamp(now + 100);
+    client.finalize(&1);
+    client.finalize(&2);
+
+    assert_eq!(client.get_bond(&1).unwrap().status, BondStatus::Slashed);
+    assert_eq!(client.get_bond(&2).unwrap().status, BondStatus::Refunded);
+    assert_eq!(treasury.get_balance(&token), 100);
+    assert_eq!(TokenClient::new(&env, &token).balance(&user), 100);
+}
+
+// Ensures bonds cannot be configured before a treasury is set
+#[test]
+fn test_bond_config_requires_treasury() {
+    let (env, _, _, client) = setup();
+    let admin = init_admin(&env, &client);
+    let token = env.register_stellar_asset_contract_v2(admin.clone()).address();
+
+    let result = client.try_set_bond_config(
+        &admin,
+        &Symbol::new(&env, "governance"),
+        &BondConfig { token, amount: 100, slash_threshold_bps: 0 },
+    );
+    assert_eq!(result, Err(Ok(ProposalError::TreasuryNotSet)));
+}
+
+// Minimal member registry exposing `get_member` and `get_total_members` like the member-registry contract
+#[contract]
+pub struct MockMemberRegistry;
+
+#[contractimpl]
+impl MockMemberRegistry {
+    pub fn add_member(env: Env, address: Address, is_active: bool, voting_power: u32) {
+        if !env.storage().persistent().has(&address) {
+            let total = Self::get_total_members(env.clone());
+            env.storage().persistent().set(&symbol_short!("total"), &(total + 1));
+        }
+
+        let member = Member {
+            address: address.clone(),
+            role: symbol_short!("member"),
+            is_active,
+            voting_power,
+            joined_at: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&address, &member);
+    }
+
+    pub fn get_member(env: Env, address: Address) -> Member {
+        env.storage()
+            .persistent()
+            .get(&address)
+            .unwrap_or_else(|| panic!("Member not found"))
+    }
+
+    pub fn get_total_members(env: Env) -> u32 {
+        env.storage().persistent().get(&symbol_short!("total")).unwrap_or(0)
+    }
+}
+
+// Minimal voting NFT contract exposing `get_nfts_by_owner`
+#[contract]
+pub struct MockVotingNft;
+
+#[contractimpl]
+impl MockVotingNft {
+    pub fn mint(env: Env, to: Address, category: Symbol) {
+        let nft = VotingNFT {
+            token_id: 1,
+            category,
+            metadata: symbol_short!("meta"),
+            owner: to.clone(),
+            issued_at: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&to, &vec![&env, nft]);
+    }
+
+    pub fn get_nfts_by_owner(env: Env, owner: Address) -> soroban_sdk::Vec<VotingNFT> {
+        env.storage().persistent().get(&owner).unwrap_or(vec![&env])
+    }
+}
+
+// Registers a mock registry and applies it as the eligibility source with the given requirements
+fn setup_registry(
+    env: &Env,
+    client: &ProposalContractClient,
+    admin: &Address,
+    min_voting_power: u32,
+    voting_nft: Option<Address>,
+    required_nft_category: Option<Symbol>,
+) -> MockMemberRegistryClient<'static> {
+    let registry = MockMemberRegistryClient::new(env, &env.register(MockMemberRegistry, ()));
+    client.set_eligibility_config(
+        admin,
+        &EligibilityConfig {
+            member_registry: registry.address.clone(),
+            min_voting_power,
+            voting_nft,
+            required_nft_category,
+        },
+    );
+    registry
+}
+
+// Registers `proposer` and `size - 1` further active members as the only eligible voters and returns the latter
+fn setup_electorate(
+    env: &Env,
+    client: &ProposalContractClient,
+    admin: &Address,
+    proposer: &Address,
+    size: u32,
+) -> std::vec::Vec<Address> {
+    let registry = setup_registry(env, client, admin, 0, None, None);
+    registry.add_member(proposer, &true, &1);
+    (1..size)
+        .map(|_| {
+            let member = Address::generate(env);
+            registry.add_member(&member, &true, &1);
+            member
+        })
+        .collect()
+}
+
+// Casts the vote in `choices` of the member at the same position in `voters`
+fn cast_member_votes(env: &Env, client: &ProposalContractClient, proposal_id: u32, voters: &[Address], choices: &[&str]) {
+    for (voter, choice) in voters.iter().zip(choices) {
+        client.vote(voter, &proposal_id, &Symbol::new(env, choice));
+    }
+}
+
+// Ensures addresses unknown to the registry cannot create proposals
+#[test]
+fn test_non_member_cannot_create_proposal() {
+    let (env, user, _, client) = setup();
+    let admin = init_admin(&env, &client);
+    setup_registry(&env, &client, &admin, 0, None, None);
+
+    let result = client.try_create_proposal(
+        &user,
+        &String::from_str(&env, "Outsider"),
+        &String::from_str(&env, "Not a member"),
+        &(env.ledger().timestamp() + 100),
+        &Symbol::new(&env, "governance"),
+        &None,
+        &false,
+    );
+    assert_eq!(result, Err(Ok(ProposalError::NotActiveMember)));
+}
+
+// Checks that inactive members and members below the minimum power cannot vote
+#[test]
+fn test_vote_requires_active_member_with_min_power() {
+    let (env, user, _, client) = setup();
+    let admin = init_admin(&env, &client);
+    let registry = setup_registry(&env, &client, &admin, 2, None, None);
+    let inactive = Address::generate(&env);
+    let weak = Address::generate(&env);
+    registry.add_member(&user, &true, &3);
+    registry.add_member(&inactive, &false, &3);
+    registry.add_member(&weak, &true, &1);
+
+    create_draft(&env, &client, &user, env.ledger().timestamp() + 100);
+    client.submit_proposal(&user, &1);
+
+    assert_eq!(
+        client.try_vote(&inactive, &1, &symbol_short!("For")),
+        Err(Ok(ProposalError::NotActiveMember))
+    );
+    assert_eq!(
+        client.try_vote(&weak, &1, &symbol_short!("For")),
+        Err(Ok(ProposalError::InsufficientVotingPower))
+    );
+
+    client.vote(&user, &1, &symbol_short!("For"));
+    assert_eq!(client.get_votes(&1), (1, 0, 0));
+}
+
+// Checks the required voting NFT category is enforced when configured
+#[test]
+fn test_vote_requires_nft_category() {
+    let (env, user, _, client) = setup();
+    let admin = init_admin(&env, &client);
+    let nft = MockVotingNftClient::new(&env, &env.register(MockVotingNft, ()));
+    let category = Symbol::new(&env, "board");
+    let registry = setup_registry(&env, &client, &admin, 0, Some(nft.address.clone()), Some(category.clone()));
+    let voter = Address::generate(&env);
+    registry.add_member(&user, &true, &1);
+    registry.add_member(&voter, &true, &1);
+    nft.mint(&user, &category);
+    nft.mint(&voter, &Symbol::new(&env, "community"));
+
+    create_draft(&env, &client, &user, env.ledger().timestamp() + 100);
+    client.submit_proposal(&user, &1);
+
+    assert_eq!(
+        client.try_vote(&voter, &1, &symbol_short!("For")),
+        Err(Ok(ProposalError::MissingRequiredNft))
+    );
+    client.vote(&user, &1, &symbol_short!("For"));
+}
+
+// Checks that vote receipts record the choice, weight and timestamp
+#[test]
+fn test_get_vote_receipt() {
+    let (env, user, _, client) = setup();
+    let now = env.ledger().timestamp();
+    create_draft(&env, &client, &user, now + 100);
+    client.submit_proposal(&user, &1);
+
+    assert_eq!(client.get_vote(&1, &user), None);
+
+    env.ledger().set_timestamp(now + 5);
+    client.vote(&user, &1, &symbol_short!("Against"));
+
+    let receipt = client.get_vote(&1, &user).unwrap();
+    assert_eq!(receipt.voter, user);
+    assert_eq!(receipt.choice, symbol_short!("Against"));
+    assert_eq!(receipt.weight, 1);
+    assert_eq!(receipt.timestamp, now + 5);
+}
+
+// Checks paginated voter listing per proposal
+#[test]
+fn test_get_voters_paginated() {
+    let (env, user, _, client) = setup();
+    create_draft(&env, &client, &user, env.ledger().timestamp() + 100);
+    client.submit_proposal(&user, &1);
+
+    let voters = [Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+    for voter in voters.iter() {
+        client.vote(voter, &1, &symbol_short!("For"));
+    }
+
+    assert_eq!(client.get_voter_count(&1), 3);
+
+    let first_page = client.get_voters(&1, &0, &2);
+    assert_eq!(first_page, vec![&env, voters[0].clone(), voters[1].clone()]);
+
+    let second_page = client.get_voters(&1, &2, &2);
+    assert_eq!(second_page, vec![&env, voters[2].clone()]);
+
+    assert_eq!(client.get_voters(&1, &5, &2).len(), 0);
+}
+
+// Checks the list of proposals an address has voted on
+#[test]
+fn test_get_voted_proposals() {
+    let (env, user, _, client) = setup();
+    let now = env.ledger().timestamp();
+    create_draft(&env, &client, &user, now + 100);
+    create_draft(&env, &client, &user, now + 100);
+    create_draft(&env, &client, &user, now + 100);
+    client.submit_proposal(&user, &1);
+    client.submit_proposal(&user, &2);
+    client.submit_proposal(&user, &3);
+
+    client.vote(&user, &3, &symbol_short!("For"));
+    client.vote(&user, &1, &symbol_short!("Abstain"));
+
+    assert_eq!(client.get_voted_proposal_count(&user), 2);
+    assert_eq!(client.get_voted_proposals(&user, &0, &10), vec![&env, 3u32, 1u32]);
+}
+
+// Returns the IDs of a list of proposals
+fn ids(env: &Env, proposals: &soroban_sdk::Vec<crate::datatypes::Proposal>) -> soroban_sdk::Vec<u32> {
+    let mut ids = vec![env];
+    for proposal in proposals.iter() {
+        ids.push_back(proposal.id);
+    }
+    ids
+}
+
+// Checks that getters return errors instead of panicking for unknown proposals
+#[test]
+fn test_getters_return_not_found() {
+    let (_, _, _, client) = setup();
+    assert_eq!(client.try_get_proposal(&42), Err(Ok(ProposalError::ProposalNotFound)));
+    assert_eq!(client.try_get_votes(&42), Err(Ok(ProposalError::ProposalNotFound)));
+}
+
+// Checks filtering by status follows lifecycle transitions
+#[test]
+fn test_list_proposals_by_status() {
+    let (env, user, _, client) = setup();
+    let now = env.ledger().timestamp();
+    create_draft(&env, &client, &user, now + 100);
+    create_draft(&env, &client, &user, now + 100);
+    create_draft(&env, &client, &user, now + 100);
+    client.submit_proposal(&user, &2);
+    client.cancel_proposal(&user, &3);
+
+    let mut filter = no_filter();
+    filter.status = Some(symbol_short!("draft"));
+    assert_eq!(ids(&env, &client.list_proposals(&filter, &0, &10)), vec![&env, 1u32]);
+
+    filter.status = Some(symbol_short!("active"));
+    assert_eq!(ids(&env, &client.list_proposals(&filter, &0, &10)), vec![&env, 2u32]);
+
+    filter.status = Some(symbol_short!("cancelled"));
+    assert_eq!(ids(&env, &client.list_proposals(&filter, &0, &10)), vec![&env, 3u32]);
+
+    filter.status = Some(symbol_short!("open"));
+    assert_eq!(client.try_list_proposals(&filter, &0, &10), Err(Ok(ProposalError::InvalidFilter)));
+}
+
+// Ensures proposals leaving an index keep the remaining ones in order across pages
+#[test]
+fn test_list_proposals_pages_stay_ordered() {
+    let (env, user, _, client) = setup();
+    let now = env.ledger().timestamp();
+    for _ in 0..4 {
+        create_draft(&env, &client, &user, now + 100);
+    }
+    client.submit_proposal(&user, &1);
+
+    let mut filter = no_filter();
+    filter.status = Some(symbol_short!("draft"));
+    assert_eq!(ids(&env, &client.list_proposals(&filter, &0, &2)), vec![&env, 2u32, 3]);
+    assert_eq!(ids(&env, &client.list_proposals(&filter, &2, &2)), vec![&env, 4u32]);
+}
+
+// Checks filtering by type and creator, combined with pagination
+#[test]
+fn test_list_proposals_by_type_and_creator() {
+    let (env, user, _, client) = setup();
+    let other = Address::generate(&env);
+    let deadline = env.ledger().timestamp() + 100;
+    create_draft(&env, &client, &user, deadline);
+    create_draft(&env, &client, &other, deadline);
+    create_draft(&env, &client, &user, deadline);
+    client.create_proposal(
+        &user,
+        &String::from_str(&env, "Technical"),
+        &String::from_str(&env, "Different type"),
+        &deadline,
+        &Symbol::new(&env, "technical"),
+        &None,
+        &false,
+    );
+
+    let mut filter = no_filter();
+    filter.creator = Some(user.clone());
+    assert_eq!(ids(&env, &client.list_proposals(&filter, &0, &10)), vec![&env, 1u32, 3, 4]);
+
+    filter.proposal_type = Some(Symbol::new(&env, "governance"));
+    assert_eq!(ids(&env, &client.list_proposals(&filter, &0, &1)), vec![&env, 1u32]);
+    assert_eq!(ids(&env, &client.list_proposals(&filter, &1, &1)), vec![&env, 3u32]);
+    assert_eq!(client.list_proposals(&filter, &2, &1).len(), 0);
+}
+
+// Checks filtering by deadline window, including after a draft deadline is edited
+#[test]
+fn test_list_proposals_by_deadline_window() {
+    let (env, user, _, client) = setup();
+    let day = 86_400;
+    let now = env.ledger().timestamp();
+    create_draft(&env, &client, &user, now + day);
+    create_draft(&env, &client, &user, now + 3 * day);
+    create_draft(&env, &client, &user, now + 40 * day);
+
+    let mut filter = no_filter();
+    filter.deadline_from = Some(now);
+    filter.deadline_to = Some(now + 5 * day);
+    assert_eq!(ids(&env, &client.list_proposals(&filter, &0, &10)), vec![&env, 1u32, 2]);
+
+    client.update_proposal(
+        &user,
+        &2,
+        &String::from_str(&env, "Moved"),
+        &String::from_str(&env, "Moved"),
+        &(now + 10 * day),
+    );
+    assert_eq!(ids(&env, &client.list_proposals(&filter, &0, &10)), vec![&env, 1u32]);
+
+    // Windows the deadline index cannot cover would need a full scan and are rejected on their own
+    filter.deadline_to = Some(now + 60 * day);
+    assert_eq!(client.try_list_proposals(&filter, &0, &10), Err(Ok(ProposalError::InvalidFilter)));
+    filter.deadline_to = None;
+    assert_eq!(client.try_list_proposals(&filter, &0, &10), Err(Ok(ProposalError::InvalidFilter)));
+
+    // Another indexed criterion supplies the candidates instead
+    filter.creator = Some(user.clone());
+    assert_eq!(ids(&env, &client.list_proposals(&filter, &0, &10)), vec![&env, 1u32, 2, 3]);
+    filter.creator = None;
+
+    filter.deadline_from = Some(now + 60 * day);
+    filter.deadline_to = Some(now);
+    assert_eq!(client.try_list_proposals(&filter, &0, &10), Err(Ok(ProposalError::InvalidFilter)));
+}
+
+// Computes the commitment `sha256(choice || salt)` a voter submits in commit-reveal mode
+fn commitment(env: &Env, choice: &str, salt: &BytesN<32>) -> BytesN<32> {
+    let mut preimage = Bytes::from_slice(env, choice.as_bytes());
+    preimage.append(&salt.clone().into());
+    env.crypto().sha256(&preimage).into()
+}
+
+// Creates and submits a commit-reveal proposal with the given deadline and reveal period
+fn create_commit_reveal(env: &Env, client: &ProposalContractClient, user: &Address, deadline: u64, reveal_period: u64) {
+    create_draft(env, client, user, deadline);
+    client.set_commit_reveal(user, &1, &reveal_period);
+    client.submit_proposal(user, &1);
+}
+
+// Checks that commitments hide the tally and that finalize counts only revealed votes
+#[test]
+fn test_commit_reveal_counts_only_revealed_votes() {
+    let (env, user, _, client) = setup();
+    let now = env.ledger().timestamp();
+    create_commit_reveal(&env, &client, &user, now + 100, 50);
+
+    let voters = [Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+    let choices = ["For", "For", "Against"];
+    for (i, voter) in voters.iter().enumerate() {
+        let salt = BytesN::from_array(&env, &[i as u8; 32]);
+        client.commit_vote(voter, &1, &commitment(&env, choices[i], &salt));
+    }
+    assert_eq!(client.get_votes(&1), (0, 0, 0));
+    assert_eq!(
+        client.try_vote(&Address::generate(&env), &1, &symbol_short!("For")),
+        Err(Ok(ProposalError::CommitRevealRequired))
+    );
+    assert_eq!(
+        client.try_reveal_vote(&voters[0], &1, &symbol_short!("For"), &BytesN::from_array(&env, &[0; 32])),
+        Err(Ok(ProposalError::RevealWindowNotOpen))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = now + 120);
+    assert_eq!(
+        client.try_commit_vote(&Address::generate(&env), &1, &BytesN::from_array(&env, &[9; 32])),
+        Err(Ok(ProposalError::VotingClosed))
+    );
+    client.reveal_vote(&voters[0], &1, &symbol_short!("For"), &BytesN::from_array(&env, &[0; 32]));
+    client.reveal_vote(&voters[1], &1, &symbol_short!("For"), &BytesN::from_array(&env, &[1; 32]));
+    assert_eq!(client.try_finalize(&1), Err(Ok(ProposalError::DeadlineNotReached)));
+
+    env.ledger().with_mut(|li| li.timestamp = now + 151);
+    client.finalize(&1);
+
+    assert_eq!(client.get_votes(&1), (2, 0, 0));
+    assert_eq!(client.get_unrevealed_count(&1), 1);
+    assert!(client.get_vote(&1, &voters[2]).is_none());
+    assert!(status_eq(&client.get_proposal(&1).status, "succeeded", &env));
+}
+
+// Checks that reveals must match the commitment and fall inside the reveal window
+#[test]
+fn test_reveal_vote_validation() {
+    let (env, user, _, client) = setup();
+    let now = env.ledger().timestamp();
+    create_commit_reveal(&env, &client, &user, now + 100, 50);
+
+    let voter = Address::generate(&env);
+    let late_voter = Address::generate(&env);
+    let salt = BytesN::from_array(&env, &[7; 32]);
+    client.commit_vote(&voter, &1, &commitment(&env, "Against", &salt));
+    client.commit_vote(&late_voter, &1, &commitment(&env, "For", &salt));
+    assert_eq!(
+        client.try_commit_vote(&voter, &1, &commitment(&env, "For", &salt)),
+        Err(Ok(ProposalError::AlreadyVoted))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = now + 110);
+    assert_eq!(
+        client.try_reveal_vote(&voter, &1, &symbol_short!("For"), &salt),
+        Err(Ok(ProposalError::InvalidReveal))
+    );
+    assert_eq!(
+        client.try_reveal_vote(&voter, &1, &symbol_short!("Against"), &BytesN::from_array(&env, &[8; 32])),
+        Err(Ok(ProposalError::InvalidReveal))
+    );
+    assert_eq!(
+        client.try_reveal_vote(&Address::generate(&env), &1, &symbol_short!("For"), &salt),
+        Err(Ok(ProposalError::NoCommitment))
+    );
+
+    client.reveal_vote(&voter, &1, &symbol_short!("Against"), &salt);
+    assert_eq!(
+        client.try_reveal_vote(&voter, &1, &symbol_short!("Against"), &salt),
+        Err(Ok(ProposalError::AlreadyVoted))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = now + 151);
+    assert_eq!(
+        client.try_reveal_vote(&late_voter, &1, &symbol_short!("For"), &salt),
+        Err(Ok(ProposalError::RevealWindowClosed))
+    );
+}
+
+// Checks that commitments are rejected on open proposals and the mode is fixed once submitted
+#[test]
+fn test_commit_reveal_mode_rules() {
+    let (env, user, _, client) = setup();
+    let now = env.ledger().timestamp();
+    create_draft(&env, &client, &user, now + 100);
+    client.submit_proposal(&user, &1);
+
+    assert_eq!(
+        client.try_commit_vote(&Address::generate(&env), &1, &BytesN::from_array(&env, &[1; 32])),
+        Err(Ok(ProposalError::NotCommitReveal))
+    );
+    assert_eq!(
+        client.try_set_commit_reveal(&user, &1, &50),
+        Err(Ok(ProposalError::ProposalNotEditable))
+    );
+}
+
+// Signs a ballot with the ed25519 key derived from `seed`, as a voter's wallet would off-chain
+fn sign_ballot(env: &Env, contract_id: &Address, seed: u8, proposal_id: u32, choice: &str, nonce: u64) -> SignedBallot {
+    let key = SigningKey::from_bytes(&[seed; 32]);
+    let choice = Symbol::new(env, choice);
+    let message: std::vec::Vec<u8> = (contract_id.clone(), proposal_id, choice.clone(), nonce)
+        .to_xdr(env)
+        .iter()
+        .collect();
+
+    SignedBallot {
+        public_key: BytesN::from_array(env, &key.verifying_key().to_bytes()),
+        proposal_id,
+        choice,
+        nonce,
+        signature: BytesN::from_array(env, &key.sign(&message).to_bytes()),
+    }
+}
+
+// Account addresses of the keys derived from seeds 1 and 2 in `sign_ballot`
+const BALLOT_VOTER_1: &str = "GCFIRY65OQE7DFP5KLNS2PF2LVZMUZYJX4OZIEQ36N2IQANUB5XVYOJR";
+const BALLOT_VOTER_2: &str = "GCATS5YOVB6ROX2WUNKGNQ2MP3GMXDMKSG2O4N5CLX3A6W4PZGZZI55U";
+
+// Checks that relayed ballots are tallied for the signing accounts and cannot be replayed
+#[test]
+fn test_submit_signed_votes_tallies_ballots() {
+    let (env, user, contract_id, client) = setup();
+    create_draft(&env, &client, &user, env.ledger().timestamp() + 100);
+    client.submit_proposal(&user, &1);
+
+    let first = sign_ballot(&env, &contract_id, 1, 1, "For", 0);
+    let second = sign_ballot(&env, &contract_id, 2, 1, "Against", 0);
+    client.submit_signed_votes(&vec![&env, first.clone(), second]);
+
+    let voter_1 = Address::from_str(&env, BALLOT_VOTER_1);
+    let voter_2 = Address::from_str(&env, BALLOT_VOTER_2);
+    assert_eq!(client.get_votes(&1), (1, 1, 0));
+    assert_eq!(client.get_vote(&1, &voter_1).unwrap().choice, symbol_short!("For"));
+    assert_eq!(client.get_vote(&1, &voter_2).unwrap().choice, symbol_short!("Against"));
+    assert_eq!(client.get_ballot_nonce(&voter_1), 1);
+
+    assert_eq!(
+        client.try_submit_signed_votes(&vec![&env, first]),
+        Err(Ok(ProposalError::InvalidNonce))
+    );
+    assert_eq!(
+        client.try_submit_signed_votes(&vec![&env, sign_ballot(&env, &contract_id, 1, 1, "For", 1)]),
+        Err(Ok(ProposalError::AlreadyVoted))
+    );
+    assert_eq!(client.try_submit_signed_votes(&Vec::new(&env)), Err(Ok(ProposalError::InvalidBatch)));
+}
+
+// Ensures a ballot whose signature does not match its public key is rejected
+#[test]
+#[should_panic]
+fn test_signed_ballot_with_forged_signature_fails() {
+    let (env, user, contract_id, client) = setup();
+    create_draft(&env, &client, &user, env.ledger().timestamp() + 100);
+    client.submit_proposal(&user, &1);
+
+    let mut ballot = sign_ballot(&env, &contract_id, 1, 1, "For", 0);
+    ballot.signature = sign_ballot(&env, &contract_id, 2, 1, "For", 0).signature;
+    client.submit_signed_votes(&vec![&env, ballot]);
+}
+
+// Checks that ballots go through the same eligibility rules as `vote` and that batches apply atomically
+#[test]
+fn test_signed_ballots_require_eligible_voters() {
+    let (env, user, contract_id, client) = setup();
+    let admin = init_admin(&env, &client);
+    let registry = setup_registry(&env, &client, &admin, 1, None, None);
+    registry.add_member(&user, &true, &1);
+    registry.add_member(&Address::from_str(&env, BALLOT_VOTER_1), &true, &1);
+
+    create_draft(&env, &client, &user, env.ledger().timestamp() + 100);
+    client.submit_proposal(&user, &1);
+
+    let ballots = vec![
+        &env,
+        sign_ballot(&env, &contract_id, 1, 1, "For", 0),
+        sign_ballot(&env, &contract_id, 2, 1, "For", 0),
+    ];
+    assert_eq!(client.try_submit_signed_votes(&ballots), Err(Ok(ProposalError::NotActiveMember)));
+    assert_eq!(client.get_votes(&1), (0, 0, 0));
+    assert_eq!(client.get_ballot_nonce(&Address::from_str(&env, BALLOT_VOTER_1)), 0);
+}
+
+// Creates and submits a quadratic proposal with the given credit allowance
+fn create_quadratic(env: &Env, client: &ProposalContractClient, user: &Address, credits_per_voter: u32, epoch_length: u64) {
+    let admin = init_admin(env, client);
+    client.set_quadratic_config(&admin, &QuadraticConfig { credits_per_voter, epoch_length });
+    create_draft(env, client, user, env.ledger().timestamp() + 100);
+    client.set_tally_mode(user, &1, &TallyMode::Quadratic);
+    client.submit_proposal(user, &1);
+}
+
+// Builds a quadratic vote spending the given credits on For, Against and Abstain
+fn credits(for_credits: u32, against_credits: u32, abstain_credits: u32) -> QuadraticVote {
+    QuadraticVote { for_credits, against_credits, abstain_credits }
+}
+
+// Checks that weights are square roots of credits, so many small holders can outweigh one large one
+#[test]
+fn test_quadratic_vote_weights_and_outcome() {
+    let (env, user, _, client) = setup();
+    create_quadratic(&env, &client, &user, 100, 0);
+
+    let whale = Address::generate(&env);
+    client.vote_quadratic(&whale, &1, &credits(100, 0, 0));
+    client.vote_quadratic(&Address::generate(&env), &1, &credits(0, 36, 0));
+    client.vote_quadratic(&Address::generate(&env), &1, &credits(0, 36, 0));
+    let splitter = Address::generate(&env);
+    client.vote_quadratic(&splitter, &1, &credits(50, 50, 0));
+
+    assert_eq!(
+        client.get_weighted_votes(&1),
+        WeightedTally { for_weight: 17_071_067, against_weight: 19_071_067, abstain_weight: 0 }
+    );
+    assert_eq!(client.get_votes(&1), (2, 3, 0));
+    assert_eq!(client.get_quadratic_vote(&1, &whale), Some(credits(100, 0, 0)));
+    assert_eq!(client.get_vote(&1, &whale).unwrap().weight, 10_000_000);
+    assert_eq!(client.get_vote(&1, &splitter).unwrap().weight, 14_142_134);
+
+    env.ledger().with_mut(|li| li.timestamp += 101);
+    client.finalize(&1);
+    assert!(status_eq(&client.get_proposal(&1).status, "defeated", &env));
+}
+
+// Checks allowance enforcement and that quadratic proposals reject single-choice votes
+#[test]
+fn test_quadratic_vote_validation() {
+    let (env, user, _, client) = setup();
+    create_quadratic(&env, &client, &user, 100, 0);
+    let voter = Address::generate(&env);
+
+    assert_eq!(
+        client.try_vote_quadratic(&voter, &1, &credits(60, 41, 0)),
+        Err(Ok(ProposalError::InsufficientCredits))
+    );
+    assert_eq!(
+        client.try_vote_quadratic(&voter, &1, &credits(0, 0, 0)),
+        Err(Ok(ProposalError::InvalidVoteChoice))
+    );
+    assert_eq!(
+        client.try_vote(&voter, &1, &symbol_short!("For")),
+        Err(Ok(ProposalError::QuadraticVoteRequired))
+    );
+
+    client.vote_quadratic(&voter, &1, &credits(60, 0, 40));
+    assert_eq!(client.get_remaining_credits(&voter, &1), 0);
+    assert_eq!(
+        client.try_vote_quadratic(&voter, &1, &credits(0, 0, 0)),
+        Err(Ok(ProposalError::AlreadyVoted))
+    );
+
+    create_draft(&env, &client, &user, env.ledger().timestamp() + 100);
+    client.submit_proposal(&user, &2);
+    assert_eq!(
+        client.try_vote_quadratic(&voter, &2, &credits(1, 0, 0)),
+        Err(Ok(ProposalError::NotQuadratic))
+    );
+}
+
+// Checks that an epoch allowance is shared across proposals and renews in the next epoch
+#[test]
+fn test_quadratic_epoch_allowance() {
+    let (env, user, _, client) = setup();
+    create_quadratic(&env, &client, &user, 100, 1_000);
+    create_draft(&env, &client, &user, env.ledger().timestamp() + 2_000);
+    client.set_tally_mode(&user, &2, &TallyMode::Quadratic);
+    client.submit_proposal(&user, &2);
+
+    let voter = Address::generate(&env);
+    client.vote_quadratic(&voter, &1, &credits(60, 0, 0));
+    assert_eq!(client.get_remaining_credits(&voter, &2), 40);
+    assert_eq!(
+        client.try_vote_quadratic(&voter, &2, &credits(50, 0, 0)),
+        Err(Ok(ProposalError::InsufficientCredits))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp += 1_000);
+    client.vote_quadratic(&voter, &2, &credits(100, 0, 0));
+    assert_eq!(client.get_weighted_votes(&2).for_weight, 10_000_000);
+}
+
+// Creates and submits an optimistic proposal with a 20% veto threshold over a total power of 10
+fn create_optimistic(env: &Env, client: &ProposalContractClient, user: &Address) -> Address {
+    let admin = init_admin(env, client);
+    client.set_total_voting_power(&admin, &10);
+    client.set_governance_params(&admin, &Symbol::new(env, "governance"), &governance_params(5_000, 5_000, false));
+    client.set_optimistic_config(
+        &admin,
+        &OptimisticConfig { veto_threshold_bps: 2_000, objection_voting_period: 500 },
+    );
+    create_draft(env, client, user, env.ledger().timestamp() + 100);
+    client.set_proposal_kind(user, &1, &ProposalKind::Optimistic);
+    client.submit_proposal(user, &1);
+    admin
+}
+
+// Ensures optimistic proposals cannot be submitted before the total voting power is set
+#[test]
+fn test_optimistic_proposal_requires_total_voting_power() {
+    let (env, user, _, client) = setup();
+    let admin = init_admin(&env, &client);
+    client.set_optimistic_config(
+        &admin,
+        &OptimisticConfig { veto_threshold_bps: 2_000, objection_voting_period: 500 },
+    );
+    create_draft(&env, &client, &user, env.ledger().timestamp() + 100);
+    client.set_proposal_kind(&user, &1, &ProposalKind::Optimistic);
+
+    let result = client.try_submit_proposal(&user, &1);
+    assert_eq!(result, Err(Ok(ProposalError::TotalPowerNotSet)));
+}
+
+// Checks that an optimistic proposal passes at the deadline without quorum when objections stay under the threshold
+#[test]
+fn test_optimistic_proposal_passes_without_quorum() {
+    let (env, user, _, client) = setup();
+    create_optimistic(&env, &client, &user);
+    cast_votes(&env, &client, 1, &["Against", "Against"]);
+
+    env.ledger().with_mut(|li| li.timestamp += 100);
+    client.finalize(&1);
+
+    let proposal = client.get_proposal(&1);
+    assert_eq!(proposal.kind, ProposalKind::Optimistic);
+    assert!(status_eq(&proposal.status, "succeeded", &env));
+}
+
+// Checks that exceeding the veto threshold converts the proposal into a regular vote with a new deadline
+#[test]
+fn test_optimistic_objection_converts_to_regular_vote() {
+    let (env, user, contract_id, client) = setup();
+    create_optimistic(&env, &client, &user);
+    env.ledger().with_mut(|li| li.timestamp += 50);
+    cast_votes(&env, &client, 1, &["Against", "Against"]);
+
+    let objector = Address::generate(&env);
+    client.vote(&objector, &1, &symbol_short!("Against"));
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "proposal_challenged"),).into_val(&env),
+                (1u32, 550u64).into_val(&env),
+            ),
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "vote_cast"),).into_val(&env),
+                (1u32, objector, symbol_short!("Against")).into_val(&env),
+            ),
+        ]
+    );
+
+    let proposal = client.get_proposal(&1);
+    assert_eq!(proposal.kind, ProposalKind::Standard);
+    assert_eq!(proposal.deadline, 550);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(client.try_finalize(&1), Err(Ok(ProposalError::DeadlineNotReached)));
+    cast_votes(&env, &client, 1, &["For", "For"]);
+
+    env.ledger().with_mut(|li| li.timestamp = 550);
+    client.finalize(&1);
+    assert!(status_eq(&client.get_proposal(&1).status, "defeated", &env));
+}
+
+// Ensures optimistic proposals need the optimistic rules configured and cannot use commit-reveal voting
+#[test]
+fn test_optimistic_kind_validation() {
+    let (env, user, _, client) = setup();
+    let admin = init_admin(&env, &client);
+    create_draft(&env, &client, &user, env.ledger().timestamp() + 100);
+    assert_eq!(
+        client.try_set_proposal_kind(&user, &1, &ProposalKind::Optimistic),
+        Err(Ok(ProposalError::OptimisticNotConfigured))
+    );
+
+    client.set_optimistic_config(
+        &admin,
+        &OptimisticConfig { veto_threshold_bps: 2_000, objection_voting_period: 500 },
+    );
+    client.set_commit_reveal(&user, &1, &50);
+    assert_eq!(
+        client.try_set_proposal_kind(&user, &1, &ProposalKind::Optimistic),
+        Err(Ok(ProposalError::InvalidConfig))
+    );
+}
+
+// Sets up conviction voting with two members of power 5, a total power of 10 and a treasury holding 1000
+fn setup_conviction(
+    env: &Env,
+    client: &ProposalContractClient,
+) -> (Address, Address, Address) {
+    let admin = init_admin(env, client);
+    let registry = setup_registry(env, client, &admin, 0, None, None);
+    let treasury = MockTreasuryClient::new(env, &env.register(MockTreasury, ()));
+    let asset = Address::generate(env);
+    let (alice, bob) = (Address::generate(env), Address::generate(env));
+
+    registry.add_member(&alice, &true, &5);
+    registry.add_member(&bob, &true, &5);
+    treasury.deposit(&asset, &admin, &1_000);
+    client.set_treasury(&admin, &treasury.address);
+    client.set_total_voting_power(&admin, &10);
+    client.set_conviction_config(
+        &admin,
+        &ConvictionConfig { half_life: 100, max_ratio_bps: 2_000, weight_bps: 20 },
+    );
+
+    (asset, alice, bob)
+}
+
+// Checks that conviction closes half the gap to the stake every half-life and passes once over the threshold
+#[test]
+fn test_conviction_grows_and_passes() {
+    let (env, _, _, client) = setup();
+    let (asset, alice, _) = setup_conviction(&env, &client);
+    client.create_funding_proposal(&alice, &String::from_str(&env, "Grants"), &asset, &50, &alice);
+
+    // 10 * weight 0.002 / (0.2 - 0.05)^2 = 0.888... units of voting power
+    assert_eq!(client.get_conviction_threshold(&1), 888_888_888);
+
+    client.stake_conviction(&alice, &1, &1);
+    env.ledger().with_mut(|li| li.timestamp += 100);
+    assert_eq!(client.get_funding_proposal(&1).conviction, 500_000_000);
+
+    env.ledger().with_mut(|li| li.timestamp += 50);
+    let conviction = client.get_funding_proposal(&1).conviction;
+    assert!((646_446_000..=646_447_000).contains(&conviction));
+
+    client.update_conviction(&1);
+    assert_eq!(client.get_funding_proposal(&1).status, FundingStatus::Open);
+
+    env.ledger().with_mut(|li| li.timestamp += 250);
+    client.update_conviction(&1);
+    let proposal = client.get_funding_proposal(&1);
+    assert_eq!(proposal.status, FundingStatus::Passed);
+    // Accruing in several steps rounds slightly differently from a single 400 second step
+    assert!((937_499_000..=937_501_000).contains(&proposal.conviction));
+}
+
+// Checks stakes are limited by the member's voting power and decay after unstaking
+#[test]
+fn test_conviction_stake_limits_and_decay() {
+    let (env, _, _, client) = setup();
+    let (asset, alice, bob) = setup_conviction(&env, &client);
+    client.create_funding_proposal(&alice, &String::from_str(&env, "Grants"), &asset, &150, &alice);
+    client.create_funding_proposal(&bob, &String::from_str(&env, "Events"), &asset, &50, &bob);
+
+    // Larger requests need more conviction: 10 * 0.002 / (0.2 - 0.15)^2 = 8 units
+    assert_eq!(client.get_conviction_threshold(&1), 8_000_000_000);
+
+    client.stake_conviction(&alice, &1, &4);
+    assert_eq!(
+        client.try_stake_conviction(&alice, &2, &2),
+        Err(Ok(ProposalError::InsufficientVotingPower))
+    );
+    assert_eq!(
+        client.try_stake_conviction(&Address::generate(&env), &1, &1),
+        Err(Ok(ProposalError::NotActiveMember))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp += 100);
+    client.unstake_conviction(&alice, &1, &4);
+    assert_eq!(
+        client.try_unstake_conviction(&alice, &1, &1),
+        Err(Ok(ProposalError::InsufficientStake))
+    );
+    client.stake_conviction(&alice, &2, &5);
+    assert_eq!(client.get_conviction_stake(&2, &alice), 5);
+
+    env.ledger().with_mut(|li| li.timestamp += 100);
+    assert_eq!(client.get_funding_proposal(&1).conviction, 1_000_000_000);
+}
+
+// Checks that staking without a member registry reports the missing registry rather than missing conviction config
+#[test]
+fn test_conviction_stake_requires_registry() {
+    let (env, _, _, client) = setup();
+    let admin = init_admin(&env, &client);
+    let treasury = MockTreasuryClient::new(&env, &env.register(MockTreasury, ()));
+    let asset = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    treasury.deposit(&asset, &admin, &1_000);
+    client.set_treasury(&admin, &treasury.address);
+    client.set_total_voting_power(&admin, &10);
+    client.set_conviction_config(
+        &admin,
+        &ConvictionConfig { half_life: 100, max_ratio_bps: 2_000, weight_bps: 20 },
+    );
+    client.create_funding_proposal(&alice, &String::from_str(&env, "Grants"), &asset, &50, &alice);
+
+    assert_eq!(
+        client.try_stake_conviction(&alice, &1, &1),
+        Err(Ok(ProposalError::EligibilityNotConfigured))
+    );
+}
+
+// Ensures funding proposals never pass against an unset total voting power
+#[test]
+fn test_conviction_requires_total_voting_power() {
+    let (env, _, _, client) = setup();
+    let (asset, alice, _) = setup_conviction(&env, &client);
+    let admin = init_admin(&env, &client);
+    client.create_funding_proposal(&alice, &String::from_str(&env, "Grants"), &asset, &50, &alice);
+    client.set_total_voting_power(&admin, &0);
+
+    assert_eq!(
+        client.try_create_funding_proposal(&alice, &String::from_str(&env, "More"), &asset, &50, &alice),
+        Err(Ok(ProposalError::TotalPowerNotSet))
+    );
+    assert_eq!(client.try_update_conviction(&1), Err(Ok(ProposalError::TotalPowerNotSet)));
+    assert_eq!(client.try_get_conviction_threshold(&1), Err(Ok(ProposalError::TotalPowerNotSet)));
+    assert_eq!(client.get_funding_proposal(&1).status, FundingStatus::Open);
+}
+
+// Ensures requests at or above the maximum treasury share are rejected
+#[test]
+fn test_funding_request_too_large() {
+    let (env, _, _, client) = setup();
+    let (asset, alice, _) = setup_conviction(&env, &client);
+
+    assert_eq!(
+        client.try_create_funding_proposal(&alice, &String::from_str(&env, "Too much"), &asset, &200, &alice),
+        Err(Ok(ProposalError::FundingRequestTooLarge))
+    );
+    assert_eq!(
+        client.try_create_funding_proposal(&alice, &String::from_str(&env, "Nothing"), &asset, &0, &alice),
+        Err(Ok(ProposalError::InvalidAmount))
+    );
+}
+
+// Checks that late votes flipping the leading outcome extend the deadline, up to the maximum extension
+#[test]
+fn test_late_outcome_change_extends_deadline() {
+    let (env, user, contract_id, client) = setup();
+    let admin = init_admin(&env, &client);
+    client.set_anti_sniping_config(&admin, &AntiSnipingConfig { window: 10, extension: 20, max_extension: 30 });
+    create_draft(&env, &client, &user, 100);
+    client.submit_proposal(&user, &1);
+
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    cast_votes(&env, &client, 1, &["For"]);
+    env.ledger().with_mut(|li| li.timestamp = 95);
+    cast_votes(&env, &client, 1, &["For"]);
+    assert_eq!(client.get_proposal(&1).deadline, 100);
+
+    cast_votes(&env, &client, 1, &["Against"]);
+    assert_eq!(client.get_proposal(&1).deadline, 100);
+
+    let swing = Address::generate(&env);
+    client.vote(&swing, &1, &symbol_short!("Against"));
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "deadline_extended"),).into_val(&env),
+                (1u32, 120u64).into_val(&env),
+            ),
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "vote_cast"),).into_val(&env),
+                (1u32, swing, symbol_short!("Against")).into_val(&env),
+            ),
+        ]
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 115);
+    cast_votes(&env, &client, 1, &["Against"]);
+    let proposal = client.get_proposal(&1);
+    assert_eq!((proposal.deadline, proposal.deadline_extension), (130, 30));
+
+    env.ledger().with_mut(|li| li.timestamp = 125);
+    cast_votes(&env, &client, 1, &["For"]);
+    assert_eq!(client.get_proposal(&1).deadline, 130);
+
+    env.ledger().with_mut(|li| li.timestamp = 120);
+    assert_eq!(client.try_finalize(&1), Err(Ok(ProposalError::DeadlineNotReached)));
+}
+
+// Ensures an enabled anti-sniping rule must be able to extend the deadline
+#[test]
+fn test_anti_sniping_config_requires_extension() {
+    let (env, _, _, client) = setup();
+    let admin = init_admin(&env, &client);
+
+    for config in [
+        AntiSnipingConfig { window: 10, extension: 0, max_extension: 30 },
+        AntiSnipingConfig { window: 10, extension: 20, max_extension: 0 },
+    ] {
+        assert_eq!(client.try_set_anti_sniping_config(&admin, &config), Err(Ok(ProposalError::InvalidConfig)));
+    }
+
+    client.set_anti_sniping_config(&admin, &AntiSnipingConfig { window: 0, extension: 0, max_extension: 0 });
+}
+
+// Checks that reveals flipping the leading outcome neither extend the deadline nor reopen commits
+#[test]
+fn test_reveal_flipping_outcome_keeps_deadline() {
+    let (env, user, _, client) = setup();
+    let admin = init_admin(&env, &client);
+    client.set_anti_sniping_config(&admin, &AntiSnipingConfig { window: 10, extension: 20, max_extension: 30 });
+    let now = env.ledger().timestamp();
+    create_commit_reveal(&env, &client, &user, now + 100, 50);
+
+    let voters = [Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+    let choices = ["For", "Against", "Against"];
+    for (i, voter) in voters.iter().enumerate() {
+        let salt = BytesN::from_array(&env, &[i as u8; 32]);
+        client.commit_vote(voter, &1, &commitment(&env, choices[i], &salt));
+    }
+
+    env.ledger().with_mut(|li| li.timestamp = now + 101);
+    client.reveal_vote(&voters[0], &1, &symbol_short!("For"), &BytesN::from_array(&env, &[0; 32]));
+    client.reveal_vote(&voters[1], &1, &symbol_short!("Against"), &BytesN::from_array(&env, &[1; 32]));
+    client.reveal_vote(&voters[2], &1, &symbol_short!("Against"), &BytesN::from_array(&env, &[2; 32]));
+
+    let proposal = client.get_proposal(&1);
+    assert_eq!((proposal.deadline, proposal.deadline_extension), (now + 100, 0));
+    assert_eq!(
+        client.try_commit_vote(&Address::generate(&env), &1, &BytesN::from_array(&env, &[9; 32])),
+        Err(Ok(ProposalError::VotingClosed))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = now + 151);
+    client.finalize(&1);
+    assert!(status_eq(&client.get_proposal(&1).status, "defeated", &env));
+}
+
+#[contract]
+pub struct MockRoleManager;
+
+#[contractimpl]
+impl MockRoleManager {
+    pub fn assign_role(env: Env, target: Address, role: Symbol) {
+        env.storage().persistent().set(&(target, role), &true);
+    }
+
+    pub fn has_role(env: Env, target: Address, role: Symbol) -> bool {
+        env.storage().persistent().get(&(target, role)).unwrap_or(false)
+    }
+}
+
+// Configures emergency proposals for holders of the `responder` role and grants it to `user`
+fn setup_emergency(env: &Env, client: &ProposalContractClient, admin: &Address, user: &Address) {
+    let roles = MockRoleManagerClient::new(env, &env.register(MockRoleManager, ()));
+    roles.assign_role(user, &symbol_short!("responder"));
+    client.set_emergency_config(
+        admin,
+        &EmergencyConfig {
+            role_manager: roles.address.clone(),
+            role: symbol_short!("responder"),
+            max_voting_period: 3_600,
+            quorum_bps: 6_000,
+            approval_threshold_bps: 6_667,
+        },
+    );
+}
+
+// Creates an emergency governance proposal owned by `user`
+fn create_emergency(env: &Env, client: &ProposalContractClient, user: &Address, deadline: u64) -> Result<(), ProposalError> {
+    match client.try_create_proposal(
+        user,
+        &String::from_str(env, "Incident"),
+        &String::from_str(env, "Pause the bridge"),
+        &deadline,
+        &Symbol::new(env, "governance"),
+        &None,
+        &true,
+    ) {
+        Ok(_) => Ok(()),
+        Err(error) => Err(error.unwrap()),
+    }
+}
+
+// Ensures only holders of the emergency proposer role can create emergency proposals
+#[test]
+fn test_emergency_proposal_requires_role() {
+    let (env, user, _, client) = setup();
+    let admin = init_admin(&env, &client);
+    let deadline = env.ledger().timestamp() + 600;
+    assert_eq!(create_emergency(&env, &client, &user, deadline), Err(ProposalError::EmergencyNotConfigured));
+
+    setup_emergency(&env, &client, &admin, &user);
+    let outsider = Address::generate(&env);
+    assert_eq!(create_emergency(&env, &client, &outsider, deadline), Err(ProposalError::MissingEmergencyRole));
+
+    create_draft(&env, &client, &outsider, deadline);
+    assert_eq!(
+        client.try_set_proposal_kind(&outsider, &1, &ProposalKind::Emergency),
+        Err(Ok(ProposalError::InvalidConfig))
+    );
+
+    create_emergency(&env, &client, &user, env.ledger().timestamp() + 3_601).unwrap();
+    assert_eq!(client.get_proposal(&2).kind, ProposalKind::Emergency);
+    assert_eq!(client.try_submit_proposal(&user, &2), Err(Ok(ProposalError::InvalidVotingPeriod)));
+}
+
+// Checks that emergency proposals skip the voting delay, ignore the type's period bounds and pass early once certain
+#[test]
+fn test_emergency_proposal_fast_track_and_early_success() {
+    let (env, user, _, client) = setup();
+    let admin = init_admin(&env, &client);
+    client.set_lifecycle_config(&admin, &LifecycleConfig { voting_delay: 100, execution_delay: 0, execution_window: 1_000 });
+    client.set_total_voting_power(&admin, &10);
+    client.set_governance_params(&admin, &Symbol::new(&env, "governance"), &governance_params(3_000, 5_000, false));
+    setup_emergency(&env, &client, &admin, &user);
+    let voters = setup_electorate(&env, &client, &admin, &user, 10);
+
+    create_emergency(&env, &client, &user, env.ledger().timestamp() + 2_000).unwrap();
+    client.submit_proposal(&user, &1);
+    assert!(status_eq(&client.get_proposal(&1).status, "active", &env));
+
+    // Six For votes meet the 60% quorum, but four Against could still keep support below two thirds
+    cast_member_votes(&env, &client, 1, &voters[..6], &["For", "For", "For", "For", "For", "For"]);
+    assert_eq!(client.try_finalize(&1), Err(Ok(ProposalError::DeadlineNotReached)));
+
+    cast_member_votes(&env, &client, 1, &voters[6..], &["For"]);
+    client.finalize(&1);
+    assert!(status_eq(&client.get_proposal(&1).status, "succeeded", &env));
+}
+
+// Checks that an emergency proposal is defeated early once its supermajority is out of reach
+#[test]
+fn test_emergency_proposal_early_defeat() {
+    let (env, user, _, client) = setup();
+    let admin = init_admin(&env, &client);
+    client.set_total_voting_power(&admin, &10);
+    setup_emergency(&env, &client, &admin, &user);
+    let voters = setup_electorate(&env, &client, &admin, &user, 10);
+
+    create_emergency(&env, &client, &user, env.ledger().timestamp() + 600).unwrap();
+    client.submit_proposal(&user, &1);
+    cast_member_votes(&env, &client, 1, &voters[..3], &["Against", "Against", "Against"]);
+    assert_eq!(client.try_finalize(&1), Err(Ok(ProposalError::DeadlineNotReached)));
+
+    cast_member_votes(&env, &client, 1, &voters[3..], &["Against"]);
+    client.finalize(&1);
+    assert!(status_eq(&client.get_proposal(&1).status, "defeated", &env));
+}
+
+// Checks that a proposal can be finalized before its deadline once quorum is met and the outcome is locked
+#[test]
+fn test_finalize_early_when_outcome_decided() {
+    let (env, user, _, client) = setup();
+    let admin = init_admin(&env, &client);
+    client.set_total_voting_power(&admin, &10);
+    client.set_governance_params(&admin, &Symbol::new(&env, "governance"), &governance_params(3_000, 5_000, false));
+    let voters = setup_electorate(&env, &client, &admin, &user, 10);
+    let deadline = env.ledger().timestamp() + 600;
+
+    create_draft(&env, &client, &user, deadline);
+    client.submit_proposal(&user, &1);
+    cast_member_votes(&env, &client, 1, &voters[..5], &["For", "For", "For", "For", "For"]);
+    assert_eq!(client.try_finalize(&1), Err(Ok(ProposalError::DeadlineNotReached)));
+
+    cast_member_votes(&env, &client, 1, &voters[5..], &["For"]);
+    client.finalize(&1);
+    let proposal = client.get_proposal(&1);
+    assert!(status_eq(&proposal.status, "succeeded", &env));
+    assert_eq!(proposal.finalization_reason, FinalizationReason::ApprovalLocked);
+
+    create_draft(&env, &client, &user, deadline);
+    client.submit_proposal(&user, &2);
+    cast_member_votes(&env, &client, 2, &voters, &["Against", "Against", "Against", "Against", "Against", "Against"]);
+    client.finalize(&2);
+    let proposal = client.get_proposal(&2);
+    assert!(status_eq(&proposal.status, "defeated", &env));
+    assert_eq!(proposal.finalization_reason, FinalizationReason::RejectionLocked);
+
+    // Without quorum a regular proposal waits for its deadline even when it cannot pass
+    create_draft(&env, &client, &user, deadline);
+    client.submit_proposal(&user, &3);
+    assert_eq!(client.get_proposal(&3).finalization_reason, FinalizationReason::None);
+    cast_member_votes(&env, &client, 3, &voters, &["For", "Against", "Against"]);
+    assert_eq!(client.try_finalize(&3), Err(Ok(ProposalError::DeadlineNotReached)));
+
+    env.ledger().with_mut(|li| li.timestamp = deadline);
+    client.finalize(&3);
+    assert_eq!(client.get_proposal(&3).finalization_reason, FinalizationReason::Deadline);
+}
+
+// Checks that members registering after submission neither vote nor widen the electorate bounding early finalization
+#[test]
+fn test_finalize_early_uses_electorate_at_submission() {
+    let (env, user, _, client) = setup();
+    let admin = init_admin(&env, &client);
+    client.set_total_voting_power(&admin, &4);
+    client.set_governance_params(&admin, &Symbol::new(&env, "governance"), &governance_params(5_000, 5_000, false));
+    let registry = setup_registry(&env, &client, &admin, 0, None, None);
+    let voters: std::vec::Vec<Address> = (0..4).map(|_| Address::generate(&env)).collect();
+    registry.add_member(&user, &true, &1);
+    for voter in &voters[..3] {
+        registry.add_member(voter, &true, &1);
+    }
+
+    create_draft(&env, &client, &user, env.ledger().timestamp() + 600);
+    client.submit_proposal(&user, &1);
+    assert_eq!(client.get_proposal(&1).electorate, Some(4));
+
+    env.ledger().with_mut(|li| li.timestamp += 10);
+    registry.add_member(&voters[3], &true, &1);
+    assert_eq!(
+        client.try_vote(&voters[3], &1, &symbol_short!("Against")),
+        Err(Ok(ProposalError::NotInElectorate))
+    );
+
+    cast_member_votes(&env, &client, 1, &voters[..3], &["For", "For", "For"]);
+    client.finalize(&1);
+    let proposal = client.get_proposal(&1);
+    assert!(status_eq(&proposal.status, "succeeded", &env));
+    assert_eq!(proposal.finalization_reason, FinalizationReason::ApprovalLocked);
+}
+
+// Checks that without a bounded electorate any address could still vote, so proposals wait for their deadline
+#[test]
+fn test_finalize_early_requires_bounded_electorate() {
+    let (env, user, _, client) = setup();
+    let admin = init_admin(&env, &client);
+    client.set_total_voting_power(&admin, &10);
+    client.set_governance_params(&admin, &Symbol::new(&env, "governance"), &governance_params(3_000, 5_000, false));
+    let deadline = env.ledger().timestamp() + 600;
+
+    create_draft(&env, &client, &user, deadline);
+    client.submit_proposal(&user, &1);
+    // Every unit of the admin-set voting power approves, yet further addresses can still vote
+    cast_votes(&env, &client, 1, &["For"; 10]);
+    assert_eq!(client.try_finalize(&1), Err(Ok(ProposalError::DeadlineNotReached)));
+
+    cast_votes(&env, &client, 1, &["Against"; 11]);
+    env.ledger().with_mut(|li| li.timestamp = deadline);
+    client.finalize(&1);
+    let proposal = client.get_proposal(&1);
+    assert!(status_eq(&proposal.status, "defeated", &env));
+    assert_eq!(proposal.finalization_reason, FinalizationReason::Deadline);
+}
+
+// Checks that draft amendments record a version history of content hashes
+#[test]
+fn test_amend_draft_records_versions() {
+    let (env, user, _, client) = setup();
+    create_draft(&env, &client, &user, env.ledger().timestamp() + 100);
+    let first = String::from_str(&env, "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi");
+    let second = String::from_str(&env, "bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku");
+
+    client.amend_proposal(&user, &1, &first);
+    env.ledger().with_mut(|li| li.timestamp += 5);
+    client.amend_proposal(&user, &1, &second);
+
+    let proposal = client.get_proposal(&1);
+    assert_eq!((proposal.version, proposal.content_hash), (3, second.clone()));
+
+    let history = client.get_content_history(&1, &0, &10);
+    assert_eq!(history.len(), 3);
+    assert_eq!(history.get(0).unwrap().content_hash, String::from_str(&env, "bafy-lifecycle"));
+    assert_eq!(history.get(1).unwrap().content_hash, first);
+    assert_eq!(history.get(2).unwrap().timestamp, 5);
+    assert_eq!(client.get_content_history(&1, &1, &10).len(), 2);
+    assert_eq!(client.try_get_content_version(&1, &4), Err(Ok(ProposalError::VersionNotFound)));
+    assert_eq!(
+        client.try_amend_proposal(&Address::generate(&env), &1, &first),
+        Err(Ok(ProposalError::Unauthorized))
+    );
+}
+
+// Sets the governance amendment policy, then creates and submits a governance proposal
+fn create_amendable(env: &Env, client: &ProposalContractClient, user: &Address, amended_votes: AmendedVotes) {
+    let admin = init_admin(env, client);
+    client.set_amendment_policy(
+        &admin,
+        &Symbol::new(env, "governance"),
+        &AmendmentPolicy { discussion_period: 50, amended_votes },
+    );
+    create_draft(env, client, user, env.ledger().timestamp() + 100);
+    client.submit_proposal(user, &1);
+}
+
+// Checks that an amendment under the Reset policy clears the tallies and lets earlier voters vote again
+#[test]
+fn test_amendment_resets_votes() {
+    let (env, user, _, client) = setup();
+    create_amendable(&env, &client, &user, AmendedVotes::Reset);
+    let voter = Address::generate(&env);
+    client.vote(&voter, &1, &symbol_short!("For"));
+    cast_votes(&env, &client, 1, &["For"]);
+
+    env.ledger().with_mut(|li| li.timestamp += 10);
+    client.amend_proposal(&user, &1, &String::from_str(&env, "bafy-revised"));
+    assert_eq!(client.get_votes(&1), (0, 0, 0));
+
+    client.vote(&voter, &1, &symbol_short!("Against"));
+    assert_eq!(client.get_votes(&1), (0, 1, 0));
+    assert_eq!(client.get_vote(&1, &voter).unwrap().content_version, 2);
+    assert_eq!(client.get_voter_count(&1), 2);
+    assert_eq!(
+        client.try_vote(&voter, &1, &symbol_short!("For")),
+        Err(Ok(ProposalError::AlreadyVoted))
+    );
+}
+
+// Checks that the Flag policy keeps earlier votes and that amendments close with the discussion period
+#[test]
+fn test_amendment_flags_votes() {
+    let (env, user, _, client) = setup();
+    create_amendable(&env, &client, &user, AmendedVotes::Flag);
+    let voter = Address::generate(&env);
+    client.vote(&voter, &1, &symbol_short!("For"));
+
+    client.amend_proposal(&user, &1, &String::from_str(&env, "bafy-revised"));
+    assert_eq!(client.get_votes(&1), (1, 0, 0));
+    assert_eq!(client.get_vote(&1, &voter).unwrap().content_version, 1);
+    assert_eq!(client.get_proposal(&1).version, 2);
+    assert_eq!(
+        client.try_vote(&voter, &1, &symbol_short!("Against")),
+        Err(Ok(ProposalError::AlreadyVoted))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp += 50);
+    assert_eq!(
+        client.try_amend_proposal(&user, &1, &String::from_str(&env, "bafy-late")),
+        Err(Ok(ProposalError::ProposalNotEditable))
+    );
 }
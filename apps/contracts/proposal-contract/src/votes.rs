@@ -0,0 +1,131 @@
+use soroban_sdk::{symbol_short, Address, BytesN, Env, Symbol, Vec};
+
+use crate::datatypes::{Proposal, VoteReceipt};
+use crate::MAX_PAGE_SIZE;
+
+/// Returns true if `voter` has a vote on the proposal that is still counted.
+/// Votes cast before an amendment that reset the tallies no longer count.
+pub fn has_voted(env: &Env, proposal: &Proposal, voter: &Address) -> bool {
+    get_receipt(env, proposal.id, voter)
+        .is_some_and(|receipt| receipt.content_version >= proposal.counted_from_version)
+}
+
+/// Returns the receipt of `voter` for the proposal, if they voted.
+pub fn get_receipt(env: &Env, proposal_id: u32, voter: &Address) -> Option<VoteReceipt> {
+    env.storage().persistent().get(&receipt_key(proposal_id, voter))
+}
+
+/// Stores a receipt and appends it to the per-proposal and per-voter indexes.
+/// A voter voting again after their earlier vote was reset keeps their place in the indexes.
+pub fn record(env: &Env, proposal_id: u32, receipt: &VoteReceipt) {
+    let storage = env.storage().persistent();
+    let key = receipt_key(proposal_id, &receipt.voter);
+    let revote = storage.has(&key);
+    storage.set(&key, receipt);
+
+    if revote {
+        return;
+    }
+
+    let voter_index = voter_count(env, proposal_id);
+    storage.set(&(symbol_short!("voter"), proposal_id, voter_index), &receipt.voter);
+    storage.set(&voter_count_key(proposal_id), &(voter_index + 1));
+
+    let voted_index = voted_count(env, &receipt.voter);
+    storage.set(&(symbol_short!("voted"), receipt.voter.clone(), voted_index), &proposal_id);
+    storage.set(&voted_count_key(&receipt.voter), &(voted_index + 1));
+}
+
+/// Number of addresses that voted on the proposal.
+pub fn voter_count(env: &Env, proposal_id: u32) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&voter_count_key(proposal_id))
+        .unwrap_or(0)
+}
+
+/// Voters of a proposal in the order they voted, starting at `start`.
+pub fn voters(env: &Env, proposal_id: u32, start: u32, limit: u32) -> Vec<Address> {
+    let end = page_end(start, limit, voter_count(env, proposal_id));
+    let mut voters = Vec::new(env);
+
+    for index in start..end {
+        if let Some(voter) = env.storage().persistent().get(&(symbol_short!("voter"), proposal_id, index)) {
+            voters.push_back(voter);
+        }
+    }
+
+    voters
+}
+
+/// Number of proposals `voter` has voted on.
+pub fn voted_count(env: &Env, voter: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&voted_count_key(voter))
+        .unwrap_or(0)
+}
+
+/// Proposal IDs `voter` has voted on in the order the votes were cast, starting at `start`.
+pub fn voted_proposals(env: &Env, voter: &Address, start: u32, limit: u32) -> Vec<u32> {
+    let end = page_end(start, limit, voted_count(env, voter));
+    let mut proposal_ids = Vec::new(env);
+
+    for index in start..end {
+        if let Some(id) = env.storage().persistent().get(&(symbol_short!("voted"), voter.clone(), index)) {
+            proposal_ids.push_back(id);
+        }
+    }
+
+    proposal_ids
+}
+
+/// Returns the hidden vote commitment of `voter` on the proposal, if any.
+pub fn get_commitment(env: &Env, proposal_id: u32, voter: &Address) -> Option<BytesN<32>> {
+    env.storage().persistent().get(&commitment_key(proposal_id, voter))
+}
+
+/// Stores a vote commitment and counts it towards the proposal's commitments.
+pub fn commit(env: &Env, proposal_id: u32, voter: &Address, commitment: &BytesN<32>) {
+    let storage = env.storage().persistent();
+    storage.set(&commitment_key(proposal_id, voter), commitment);
+    storage.set(&commitment_count_key(proposal_id), &(commitment_count(env, proposal_id) + 1));
+}
+
+/// Number of vote commitments submitted for the proposal.
+pub fn commitment_count(env: &Env, proposal_id: u32) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&commitment_count_key(proposal_id))
+        .unwrap_or(0)
+}
+
+/// Exclusive end index of a page, clamped to the collection size and `MAX_PAGE_SIZE`.
+fn page_end(start: u32, limit: u32, len: u32) -> u32 {
+    start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(len)
+}
+
+/// Generate storage key for a vote receipt
+fn receipt_key(proposal_id: u32, voter: &Address) -> (Symbol, u32, Address) {
+    (symbol_short!("vote"), proposal_id, voter.clone())
+}
+
+/// Generate storage key for the number of voters on a proposal
+fn voter_count_key(proposal_id: u32) -> (Symbol, u32) {
+    (symbol_short!("voter_cnt"), proposal_id)
+}
+
+/// Generate storage key for the number of proposals an address voted on
+fn voted_count_key(voter: &Address) -> (Symbol, Address) {
+    (symbol_short!("voted_cnt"), voter.clone())
+}
+
+/// Generate storage key for a vote commitment
+fn commitment_key(proposal_id: u32, voter: &Address) -> (Symbol, u32, Address) {
+    (symbol_short!("commit"), proposal_id, voter.clone())
+}
+
+/// Generate storage key for the number of commitments on a proposal
+fn commitment_count_key(proposal_id: u32) -> (Symbol, u32) {
+    (symbol_short!("commit_ct"), proposal_id)
+}
@@ -25,6 +25,8 @@ pub enum DataKey {
     Nft(u32),
     OwnedBy(Address),
     MintedCategory(Address, Symbol),
+    CategorySupply(Symbol),
+    TotalSupply,
 }
 
 #[contracterror]
@@ -82,6 +84,25 @@ impl VotingNFTContract {
 
         e.storage().persistent().set(&minted_category_key, &true);
 
+        let category_supply_key = DataKey::CategorySupply(category.clone());
+        let category_supply: u32 = e
+            .storage()
+            .persistent()
+            .get(&category_supply_key)
+            .unwrap_or(0);
+        e.storage()
+            .persistent()
+            .set(&category_supply_key, &(category_supply + 1));
+
+        let total_supply: u32 = e
+            .storage()
+            .persistent()
+            .get(&DataKey::TotalSupply)
+            .unwrap_or(0);
+        e.storage()
+            .persistent()
+            .set(&DataKey::TotalSupply, &(total_supply + 1));
+
         let mut owned_ids: Vec<u32> = e
             .storage()
             .persistent()
@@ -126,6 +147,20 @@ impl VotingNFTContract {
         nfts
     }
 
+    pub fn get_category_supply(e: Env, category: Symbol) -> u32 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::CategorySupply(category))
+            .unwrap_or(0)
+    }
+
+    pub fn get_total_supply(e: Env) -> u32 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::TotalSupply)
+            .unwrap_or(0)
+    }
+
     pub fn add_minter(e: &Env, caller: Address, minter: Address) {
         grant_role(&e, &caller, &minter, &Symbol::new(&e, "minter"));
     }
@@ -216,6 +216,14 @@ fn test_get_nfts_by_owner_multiple() {
 
     let empty_nfts = client.get_nfts_by_owner(&Address::generate(&env));
     assert!(empty_nfts.is_empty());
+
+    assert_eq!(
+        client.get_category_supply(&Symbol::new(&env, "participation")),
+        2
+    );
+    assert_eq!(client.get_category_supply(&Symbol::new(&env, "referral")), 1);
+    assert_eq!(client.get_category_supply(&Symbol::new(&env, "builder")), 0);
+    assert_eq!(client.get_total_supply(), 3);
 }
 
 #[test]